@@ -0,0 +1,146 @@
+//! Representative decode/encode workloads for benchmarking.
+//!
+//! These helpers build realistic SD messages and run the hot parsing and
+//! resolution paths over them, so benchmarks (e.g. criterion targets in
+//! `benches/`) measure the same workloads from release to release. Every
+//! workload returns a count derived from what it touched, which callers should
+//! pass to `core::hint::black_box` to keep the work from being optimized away.
+
+use crate::builder::MessageBuilder;
+use crate::entries::{EntriesIter, Entry, EntryRepr, ServiceEntryRepr, ServiceKey};
+use crate::error::Error;
+use crate::options::{self, IPv4EndpointOptionRepr, OptionRepr, OptionsIter, TransportProtocol};
+use crate::repr::Repr;
+
+/// Result type alias using the crate's Error type.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Number of offers in the message built by `build_offer_message`.
+pub const OFFER_COUNT: usize = 10;
+
+/// Buffer size large enough for any message built by this module.
+pub const MESSAGE_BUFFER_LEN: usize = 1400;
+
+/// Encode an offer for each service in `services`, each referencing its own
+/// IPv4 UDP endpoint.
+///
+/// # Parameters
+/// * `services` - The catalog to advertise (at most `builder::MAX_ENTRIES`)
+/// * `buffer` - Buffer receiving the message
+///
+/// # Returns
+/// * `Ok(usize)` - Number of bytes written
+/// * `Err(Error)` - If the catalog doesn't fit the builder or the buffer
+pub fn encode_catalog(services: &[ServiceKey], buffer: &mut [u8]) -> Result<usize> {
+    let mut builder = MessageBuilder::new(0xC0);
+    for (i, key) in services.iter().enumerate() {
+        let endpoint = builder.add_option(OptionRepr::IPv4Endpoint(IPv4EndpointOptionRepr {
+            ipv4_address: [192, 168, 0, 1],
+            protocol: TransportProtocol::UDP,
+            port: 30500 + i as u16,
+        }))?;
+        builder.add_entry(EntryRepr::Service(ServiceEntryRepr::offer(*key, 0, 3)), &[endpoint], &[])?;
+    }
+    builder.finish(buffer)
+}
+
+/// Build the reference workload: `OFFER_COUNT` offers, one endpoint each.
+///
+/// # Parameters
+/// * `buffer` - Buffer receiving the message (`MESSAGE_BUFFER_LEN` suffices)
+///
+/// # Returns
+/// * `Ok(usize)` - Number of bytes written
+/// * `Err(Error::BufferTooShort)` - If `buffer` is too small
+pub fn build_offer_message(buffer: &mut [u8]) -> Result<usize> {
+    let mut services = [ServiceKey::new(0, 0, 0); OFFER_COUNT];
+    for (i, key) in services.iter_mut().enumerate() {
+        *key = ServiceKey::new(0x1000 + i as u16, 0x0001, 1);
+    }
+    encode_catalog(&services, buffer)
+}
+
+/// Parse a message and decode every entry and option.
+///
+/// # Parameters
+/// * `msg` - A complete SD message
+///
+/// # Returns
+/// * `Ok(usize)` - Number of entries plus number of options decoded
+/// * `Err(Error)` - If any part of the message fails to parse
+pub fn decode_message(msg: &[u8]) -> Result<usize> {
+    let repr = Repr::parse_bytes(msg)?;
+    let mut count = 0;
+    for entry in EntriesIter::new(repr.entries) {
+        entry?;
+        count += 1;
+    }
+    for option in OptionsIter::new_lenient(repr.options) {
+        option?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Parse a message and resolve every entry's option runs to option bytes.
+///
+/// # Parameters
+/// * `msg` - A complete SD message
+///
+/// # Returns
+/// * `Ok(usize)` - Total number of option bytes referenced by all runs
+/// * `Err(Error)` - If the message fails to parse or a run points past the
+///   options array
+pub fn resolve_option_runs(msg: &[u8]) -> Result<usize> {
+    let repr = Repr::parse_bytes(msg)?;
+    let mut total = 0;
+    for chunk in repr.entries.chunks(Entry::<&[u8]>::LENGTH) {
+        let entry = Entry::from_buffer(chunk)?;
+        for (index, count) in [entry.first_run(), entry.second_run()].into_iter().flatten() {
+            let mut pos = options::offset_of(repr.options, index as usize)?;
+            for _ in 0..count {
+                let len = options::option_wire_len(repr.options.get(pos..).ok_or(Error::LengthOverflow)?)?;
+                pos += len;
+                total += len;
+            }
+        }
+    }
+    Ok(total)
+}
+
+/// Run `workload` `iterations` times and sum its results.
+///
+/// # Parameters
+/// * `iterations` - Number of times to run the workload
+/// * `workload` - One of the functions above, typically wrapped in a closure
+///
+/// # Returns
+/// * `Ok(usize)` - Wrapping sum of all results, for feeding to `black_box`
+/// * `Err(Error)` - The first error a run produced
+pub fn run_iterations<F>(iterations: usize, mut workload: F) -> Result<usize>
+where
+    F: FnMut() -> Result<usize>,
+{
+    let mut sum = 0usize;
+    for _ in 0..iterations {
+        sum = sum.wrapping_add(core::hint::black_box(workload()?));
+    }
+    Ok(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bench_workloads() {
+        let mut buffer = [0u8; MESSAGE_BUFFER_LEN];
+        let len = build_offer_message(&mut buffer).unwrap();
+        let msg = &buffer[..len];
+
+        assert_eq!(decode_message(msg), Ok(2 * OFFER_COUNT));
+        assert_eq!(resolve_option_runs(msg), Ok(12 * OFFER_COUNT));
+        assert_eq!(run_iterations(3, || decode_message(msg)), Ok(6 * OFFER_COUNT));
+        assert_eq!(run_iterations(1, || decode_message(&msg[..4])), Err(Error::BufferTooShort));
+    }
+}
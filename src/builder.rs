@@ -0,0 +1,707 @@
+//! Handle-based builder for complete SOME/IP-SD messages.
+//!
+//! Options are added first and identified by `OptionRef` handles; entries
+//! then name the options of their two runs by handle. The builder resolves
+//! the handles to option-run indices and counts when the message is written,
+//! so callers never compute the 4-bit counts or indices by hand.
+
+use crate::entries::{EntryRepr, EventGroupEntryRepr, EventGroupKey, NumberOfOptions, ServiceKey};
+use crate::error::Error;
+use crate::field;
+use crate::options::{IPv4EndpointOptionRepr, OptionRepr, TransportProtocol};
+use crate::packet::Packet;
+
+/// Result type alias using the crate's Error type.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Maximum number of entries a `MessageBuilder` can hold.
+pub const MAX_ENTRIES: usize = 32;
+
+/// Maximum number of distinct options a `MessageBuilder` can hold.
+pub const MAX_OPTIONS: usize = 32;
+
+/// Maximum number of options in a single option run (4-bit count).
+const MAX_RUN_LEN: usize = 15;
+
+/// Handle to an option added to a `MessageBuilder`.
+///
+/// Handles are only meaningful for the builder that returned them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OptionRef {
+    index: u8,
+}
+
+impl OptionRef {
+    /// Get the index of the option in the options array.
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+}
+
+/// An option run resolved to an index and count.
+#[derive(Debug, Clone, Copy, Default)]
+struct Run {
+    index: u8,
+    count: u8,
+}
+
+impl Run {
+    /// Resolve a list of handles to a run, checking length and contiguity.
+    fn from_refs(refs: &[OptionRef]) -> Result<Self> {
+        let Some(first) = refs.first() else {
+            return Ok(Run::default());
+        };
+        if refs.len() > MAX_RUN_LEN {
+            return Err(Error::LengthOverflow);
+        }
+        let contiguous = refs
+            .iter()
+            .enumerate()
+            .all(|(i, r)| r.index as usize == first.index as usize + i);
+        if !contiguous {
+            return Err(Error::LengthOverflow);
+        }
+        Ok(Run {
+            index: first.index,
+            count: refs.len() as u8,
+        })
+    }
+}
+
+/// Largest option an `OptionPool` can hold (an IPv6 endpoint option).
+pub const MAX_POOLED_OPTION_LEN: usize = 24;
+
+/// Handle to an option interned in an `OptionPool`.
+///
+/// Handles are only meaningful for the pool that returned them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PoolRef {
+    index: u8,
+}
+
+/// Interned, pre-serialized options shared by many messages.
+///
+/// A long-lived sender interns each distinct option once, then references it
+/// from every `MessageBuilder` via `MessageBuilder::add_pooled`, which copies
+/// the stored bytes instead of serializing the option again.
+#[derive(Debug, Clone)]
+pub struct OptionPool<const N: usize> {
+    options: [([u8; MAX_POOLED_OPTION_LEN], u8); N],
+    count: usize,
+}
+
+impl<const N: usize> Default for OptionPool<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> OptionPool<N> {
+    /// Create an empty pool.
+    pub fn new() -> Self {
+        OptionPool {
+            options: [([0; MAX_POOLED_OPTION_LEN], 0); N],
+            count: 0,
+        }
+    }
+
+    /// Serialize an option into the pool, or find it if already present.
+    ///
+    /// # Parameters
+    /// * `option` - The option to intern
+    ///
+    /// # Returns
+    /// * `Ok(PoolRef)` - Handle to the interned option
+    /// * `Err(Error::LengthOverflow)` if the pool is full or the option is
+    ///   longer than `MAX_POOLED_OPTION_LEN` bytes
+    pub fn intern(&mut self, option: &OptionRepr) -> Result<PoolRef> {
+        let len = option.buffer_len();
+        if len > MAX_POOLED_OPTION_LEN {
+            return Err(Error::LengthOverflow);
+        }
+        let mut bytes = [0u8; MAX_POOLED_OPTION_LEN];
+        option.emit(&mut bytes);
+        let entry = (bytes, len as u8);
+        if let Some(index) = self.options[..self.count].iter().position(|e| *e == entry) {
+            return Ok(PoolRef { index: index as u8 });
+        }
+        if self.count == N || self.count > u8::MAX as usize {
+            return Err(Error::LengthOverflow);
+        }
+        self.options[self.count] = entry;
+        self.count += 1;
+        Ok(PoolRef {
+            index: (self.count - 1) as u8,
+        })
+    }
+
+    /// Get the wire bytes of an interned option, header included.
+    ///
+    /// # Parameters
+    /// * `handle` - Handle returned by `intern` on this pool
+    pub fn get(&self, handle: PoolRef) -> &[u8] {
+        let (bytes, len) = &self.options[handle.index as usize];
+        &bytes[..*len as usize]
+    }
+
+    /// Get the number of distinct options in the pool.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns true if the pool holds no options.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+}
+
+/// Builder for a complete SOME/IP-SD message.
+///
+/// # Example
+///
+/// ```
+/// use someip_sd_wire::builder::MessageBuilder;
+/// use someip_sd_wire::entries::{EntryRepr, EntryType, NumberOfOptions, ServiceEntryRepr};
+/// use someip_sd_wire::options::{IPv4EndpointOptionRepr, OptionRepr, TransportProtocol};
+///
+/// let mut builder = MessageBuilder::new(0xC0);
+/// let endpoint = builder
+///     .add_option(OptionRepr::IPv4Endpoint(IPv4EndpointOptionRepr {
+///         ipv4_address: [192, 168, 0, 1],
+///         protocol: TransportProtocol::UDP,
+///         port: 30509,
+///     }))
+///     .unwrap();
+/// let offer = ServiceEntryRepr {
+///     entry_type: EntryType::OfferService,
+///     index_first_option_run: 0,
+///     index_second_option_run: 0,
+///     number_of_options: NumberOfOptions::new(),
+///     service_id: 0x1234,
+///     instance_id: 0x0001,
+///     major_version: 1,
+///     ttl: 3,
+///     minor_version: 0,
+/// };
+/// builder.add_entry(EntryRepr::Service(offer), &[endpoint], &[]).unwrap();
+///
+/// let mut buffer = [0u8; 64];
+/// let len = builder.finish(&mut buffer).unwrap();
+/// assert_eq!(len, 12 + 16 + 12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MessageBuilder<'a> {
+    flags: u8,
+    entries: [Option<(EntryRepr, Run, Run)>; MAX_ENTRIES],
+    entry_count: usize,
+    options: [Option<OptionRepr<'a>>; MAX_OPTIONS],
+    option_count: usize,
+    dedup: bool,
+}
+
+impl<'a> MessageBuilder<'a> {
+    /// Create an empty builder.
+    ///
+    /// # Parameters
+    /// * `flags` - Flags byte of the message (reboot, unicast flags)
+    pub fn new(flags: u8) -> Self {
+        MessageBuilder {
+            flags,
+            entries: [None; MAX_ENTRIES],
+            entry_count: 0,
+            options: [None; MAX_OPTIONS],
+            option_count: 0,
+            dedup: true,
+        }
+    }
+
+    /// Enable or disable option deduplication (enabled by default).
+    ///
+    /// With deduplication disabled every added option gets its own slot in
+    /// the options array, giving a literal layout.
+    ///
+    /// # Parameters
+    /// * `dedup` - Whether identical options share one slot
+    pub fn dedup_options(mut self, dedup: bool) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Add an option to the options array.
+    ///
+    /// Adding an option identical to one already present returns the handle
+    /// of the existing option instead of storing it twice, unless
+    /// deduplication was disabled with `dedup_options(false)`.
+    ///
+    /// # Parameters
+    /// * `option` - The option to add
+    ///
+    /// # Returns
+    /// * `Ok(OptionRef)` - Handle to reference the option from entries
+    /// * `Err(Error::LengthOverflow)` if `MAX_OPTIONS` options were already added
+    pub fn add_option(&mut self, option: OptionRepr<'a>) -> Result<OptionRef> {
+        let existing = self.options[..self.option_count]
+            .iter()
+            .position(|o| *o == Some(option));
+        if let Some(index) = existing.filter(|_| self.dedup) {
+            return Ok(OptionRef { index: index as u8 });
+        }
+        if self.option_count == MAX_OPTIONS {
+            return Err(Error::LengthOverflow);
+        }
+        self.options[self.option_count] = Some(option);
+        self.option_count += 1;
+        Ok(OptionRef {
+            index: (self.option_count - 1) as u8,
+        })
+    }
+
+    /// Add an option interned in an `OptionPool`.
+    ///
+    /// The pooled bytes are copied verbatim when the message is written.
+    /// Adding the same pooled option twice returns the existing handle, unless
+    /// deduplication was disabled with `dedup_options(false)`.
+    ///
+    /// # Parameters
+    /// * `pool` - The pool holding the option
+    /// * `handle` - Handle returned by `pool.intern`
+    ///
+    /// # Returns
+    /// * `Ok(OptionRef)` - Handle to reference the option from entries
+    /// * `Err(Error::LengthOverflow)` if `MAX_OPTIONS` options were already added
+    pub fn add_pooled<const N: usize>(&mut self, pool: &'a OptionPool<N>, handle: PoolRef) -> Result<OptionRef> {
+        self.add_option(OptionRepr::Raw(pool.get(handle)))
+    }
+
+    /// Add an entry referencing options by handle.
+    ///
+    /// The option-run indices and counts already present in `entry` are
+    /// ignored and replaced by the runs given here.
+    ///
+    /// # Parameters
+    /// * `entry` - The entry to add
+    /// * `first_run` - Handles of the options in the first run, in order
+    /// * `second_run` - Handles of the options in the second run, in order
+    ///
+    /// # Returns
+    /// * `Ok(())` if the entry was added
+    /// * `Err(Error::LengthOverflow)` if a run has more than 15 options, its
+    ///   handles aren't consecutive options, or `MAX_ENTRIES` entries were
+    ///   already added
+    pub fn add_entry(&mut self, entry: EntryRepr, first_run: &[OptionRef], second_run: &[OptionRef]) -> Result<()> {
+        let first = Run::from_refs(first_run)?;
+        let second = Run::from_refs(second_run)?;
+        if self.entry_count == MAX_ENTRIES {
+            return Err(Error::LengthOverflow);
+        }
+        self.entries[self.entry_count] = Some((entry, first, second));
+        self.entry_count += 1;
+        Ok(())
+    }
+
+    /// Add an entry together with the options it references.
+    ///
+    /// Each option is added as with `add_option`, so options already present
+    /// are shared rather than stored again. The resulting handles are split
+    /// into runs of consecutive options, which become the entry's first and
+    /// second run. If the entry can't be added, options added by this call
+    /// are removed again.
+    ///
+    /// # Parameters
+    /// * `entry` - The entry to add
+    /// * `options` - The options the entry references, in order
+    ///
+    /// # Returns
+    /// * `Ok(())` if the entry was added
+    /// * `Err(Error::LengthOverflow)` if the options don't fit in two runs of
+    ///   consecutive options, or a `MAX_OPTIONS`/`MAX_ENTRIES` limit is hit
+    pub fn add_entry_with_options(&mut self, entry: EntryRepr, options: &[OptionRepr<'a>]) -> Result<()> {
+        let option_count = self.option_count;
+        let result = self.link_options(entry, options);
+        if result.is_err() {
+            for slot in &mut self.options[option_count..self.option_count] {
+                *slot = None;
+            }
+            self.option_count = option_count;
+        }
+        result
+    }
+
+    fn link_options(&mut self, entry: EntryRepr, options: &[OptionRepr<'a>]) -> Result<()> {
+        let mut refs = [OptionRef { index: 0 }; 2 * MAX_RUN_LEN];
+        let refs = refs.get_mut(..options.len()).ok_or(Error::LengthOverflow)?;
+        for (r, option) in refs.iter_mut().zip(options) {
+            *r = self.add_option(*option)?;
+        }
+        let split = refs
+            .windows(2)
+            .position(|pair| pair[1].index as usize != pair[0].index as usize + 1)
+            .map_or(refs.len(), |i| i + 1);
+        let (first_run, second_run) = refs.split_at(split);
+        self.add_entry(entry, first_run, second_run)
+    }
+
+    /// Get the total wire format size of the message built so far.
+    pub fn buffer_len(&self) -> usize {
+        field::entries::OPTIONS_ARRAY(self.entries_len(), self.options_len()).end
+    }
+
+    /// Write the message into `buffer`.
+    ///
+    /// # Parameters
+    /// * `buffer` - Buffer receiving the SD message
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of bytes written
+    /// * `Err(Error::BufferTooShort)` if `buffer` is smaller than `buffer_len()`
+    /// * `Err(Error::DanglingOptionRef)` if an entry references an option
+    ///   that wasn't added to this builder
+    pub fn finish(&self, buffer: &mut [u8]) -> Result<usize> {
+        let dangling = self.entries[..self.entry_count]
+            .iter()
+            .flatten()
+            .flat_map(|(_, first, second)| [first, second])
+            .any(|run| run.count > 0 && run.index as usize + run.count as usize > self.option_count);
+        if dangling {
+            return Err(Error::DanglingOptionRef);
+        }
+
+        let entries_len = self.entries_len();
+        let options_len = self.options_len();
+        let total = self.buffer_len();
+        if buffer.len() < total {
+            return Err(Error::BufferTooShort);
+        }
+
+        let mut packet = Packet::new_unchecked(&mut buffer[..total]);
+        packet.set_flags(self.flags);
+        packet.set_reserved(0);
+        packet.set_entries_length(entries_len as u32);
+        packet.set_options_length(options_len as u32);
+
+        let entries = packet.entries_array_mut();
+        for (i, (entry, first, second)) in self.entries[..self.entry_count].iter().flatten().enumerate() {
+            let mut entry = *entry;
+            entry.set_option_run_indices(first.index, second.index);
+            entry.set_number_of_options(NumberOfOptions::from_options(first.count, second.count));
+            entry.emit(&mut entries[i * EntryRepr::buffer_len()..(i + 1) * EntryRepr::buffer_len()]);
+        }
+
+        let options = packet.options_array_mut();
+        let mut offset = 0;
+        for option in self.options[..self.option_count].iter().flatten() {
+            offset += option.emit(&mut options[offset..]);
+        }
+
+        Ok(total)
+    }
+
+    fn entries_len(&self) -> usize {
+        self.entry_count * EntryRepr::buffer_len()
+    }
+
+    fn options_len(&self) -> usize {
+        self.options[..self.option_count]
+            .iter()
+            .flatten()
+            .map(OptionRepr::buffer_len)
+            .sum()
+    }
+}
+
+/// Build a complete Subscribe message with one IPv4 UDP endpoint.
+///
+/// The message has flags 0xC0 (reboot, unicast), a single Subscribe entry and
+/// the endpoint option its first run references, i.e. where the subscriber
+/// wants events delivered.
+///
+/// # Parameters
+/// * `buf` - Buffer receiving the message (40 bytes suffice)
+/// * `service_id` - Service ID
+/// * `instance_id` - Instance ID
+/// * `major` - Major version
+/// * `eventgroup_id` - EventGroup ID
+/// * `counter` - 4-bit subscription counter
+/// * `ttl` - TTL in seconds (0 = StopSubscribe)
+/// * `addr` - IPv4 address to deliver events to
+/// * `port` - UDP port to deliver events to
+///
+/// # Returns
+/// * `Ok(usize)` - Number of bytes written
+/// * `Err(Error::BufferTooShort)` if `buf` is too small
+#[allow(clippy::too_many_arguments)]
+pub fn build_subscribe_ipv4_udp(
+    buf: &mut [u8],
+    service_id: u16,
+    instance_id: u16,
+    major: u8,
+    eventgroup_id: u16,
+    counter: u8,
+    ttl: u32,
+    addr: [u8; 4],
+    port: u16,
+) -> Result<usize> {
+    let key = EventGroupKey::new(ServiceKey::new(service_id, instance_id, major), eventgroup_id);
+    let mut builder = MessageBuilder::new(0xC0);
+    let endpoint = builder.add_option(OptionRepr::IPv4Endpoint(IPv4EndpointOptionRepr {
+        ipv4_address: addr,
+        protocol: TransportProtocol::UDP,
+        port,
+    }))?;
+    builder.add_entry(EventGroupEntryRepr::subscribe(key, counter, ttl).into(), &[endpoint], &[])?;
+    builder.finish(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entries::{EntryType, ReservedAndCounter, ServiceEntryRepr};
+    use crate::options::LoadBalancingOptionRepr;
+    use crate::repr::Repr;
+
+    fn offer(service_id: u16) -> EntryRepr {
+        EntryRepr::Service(ServiceEntryRepr {
+            entry_type: EntryType::OfferService,
+            index_first_option_run: 0,
+            index_second_option_run: 0,
+            number_of_options: NumberOfOptions::new(),
+            service_id,
+            instance_id: 1,
+            major_version: 1,
+            ttl: 3,
+            minor_version: 0,
+        })
+    }
+
+    fn endpoint(port: u16) -> OptionRepr<'static> {
+        OptionRepr::IPv4Endpoint(IPv4EndpointOptionRepr {
+            ipv4_address: [192, 168, 0, 1],
+            protocol: TransportProtocol::UDP,
+            port,
+        })
+    }
+
+    #[test]
+    fn test_builder_resolves_handles() {
+        let mut builder = MessageBuilder::new(0xC0);
+        let ep = builder.add_option(endpoint(30509)).unwrap();
+        let lb = builder.add_option(OptionRepr::LoadBalancing(LoadBalancingOptionRepr { priority: 1, weight: 2 })).unwrap();
+        // Identical options are stored once
+        assert_eq!(builder.add_option(endpoint(30509)).unwrap(), ep);
+
+        builder.add_entry(offer(0x1000), &[ep, lb], &[]).unwrap();
+        builder.add_entry(offer(0x1001), &[lb], &[ep]).unwrap();
+        let subscribe = EventGroupEntryRepr {
+            entry_type: EntryType::Subscribe,
+            index_first_option_run: 7,
+            index_second_option_run: 7,
+            number_of_options: NumberOfOptions::from_options(3, 3),
+            service_id: 0x1000,
+            instance_id: 1,
+            major_version: 1,
+            ttl: 3,
+            reserved_and_counter: ReservedAndCounter::new(),
+            eventgroup_id: 0x10,
+        };
+        builder.add_entry(EntryRepr::EventGroup(subscribe), &[], &[]).unwrap();
+
+        let mut buffer = [0u8; 128];
+        let len = builder.finish(&mut buffer).unwrap();
+        assert_eq!(len, builder.buffer_len());
+        assert_eq!(len, 12 + 3 * 16 + 12 + 8);
+
+        let repr = Repr::parse_bytes(&buffer[..len]).unwrap();
+        assert_eq!(repr.flags, 0xC0);
+        assert_eq!(repr.check_option_sharing(), Ok(()));
+        assert_eq!(&repr.entries[1..4], &[0, 0, 0x20]);
+        assert_eq!(&repr.entries[17..20], &[1, 0, 0x11]);
+        assert_eq!(&repr.entries[33..36], &[0, 0, 0x00]);
+        assert_eq!(repr.options[2], 0x04);
+        assert_eq!(repr.options[14], 0x02);
+
+        assert_eq!(builder.finish(&mut buffer[..len - 1]), Err(Error::BufferTooShort));
+    }
+
+    #[test]
+    fn test_builder_preserves_mixed_entry_order() {
+        let key = ServiceKey::new(0x1000, 1, 1);
+        let ack = EventGroupEntryRepr {
+            entry_type: EntryType::SubscribeAck,
+            ..EventGroupEntryRepr::subscribe(EventGroupKey::new(key, 0x10), 0, 3)
+        };
+        let entries: [EntryRepr; 3] = [
+            ServiceEntryRepr::offer(key, 0, 3).into(),
+            ack.into(),
+            ServiceEntryRepr::offer(ServiceKey::new(0x1001, 1, 1), 0, 3).into(),
+        ];
+
+        let mut builder = MessageBuilder::new(0xC0);
+        for entry in entries {
+            builder.add_entry(entry, &[], &[]).unwrap();
+        }
+        let mut buffer = [0u8; 64];
+        let len = builder.finish(&mut buffer).unwrap();
+
+        let repr = Repr::parse_bytes(&buffer[..len]).unwrap();
+        let mut parsed = repr.entries.chunks(16).map(EntryRepr::parse);
+        for entry in entries {
+            assert_eq!(parsed.next(), Some(Ok(entry)));
+        }
+        assert!(parsed.next().is_none());
+    }
+
+    #[test]
+    fn test_builder_rejects_bad_runs() {
+        let mut builder = MessageBuilder::new(0x00);
+        let a = builder.add_option(endpoint(1)).unwrap();
+        let b = builder.add_option(endpoint(2)).unwrap();
+        let c = builder.add_option(endpoint(3)).unwrap();
+
+        assert_eq!(builder.add_entry(offer(0x1000), &[a, c], &[]), Err(Error::LengthOverflow));
+        assert_eq!(builder.add_entry(offer(0x1000), &[], &[b, a]), Err(Error::LengthOverflow));
+        assert_eq!(builder.add_entry(offer(0x1000), &[a, b, c], &[]), Ok(()));
+
+        let mut refs = [a; 16];
+        for port in 4..=16 {
+            builder.add_option(endpoint(port)).unwrap();
+        }
+        for (i, r) in refs.iter_mut().enumerate() {
+            r.index = i as u8;
+        }
+        assert_eq!(builder.add_entry(offer(0x1001), &refs[..15], &[]), Ok(()));
+        assert_eq!(builder.add_entry(offer(0x1001), &refs, &[]), Err(Error::LengthOverflow));
+    }
+
+    #[test]
+    fn test_builder_entry_with_options() {
+        let mut builder = MessageBuilder::new(0xC0);
+        let lb = OptionRepr::LoadBalancing(LoadBalancingOptionRepr { priority: 1, weight: 2 });
+        builder.add_entry_with_options(offer(0x1000), &[endpoint(1), lb]).unwrap();
+        // Shares option 0, then appends a new one: two runs
+        builder.add_entry_with_options(offer(0x1001), &[endpoint(1), endpoint(2)]).unwrap();
+        builder.add_entry_with_options(offer(0x1002), &[]).unwrap();
+
+        let mut buffer = [0u8; 128];
+        let len = builder.finish(&mut buffer).unwrap();
+        assert_eq!(len, 12 + 3 * 16 + 12 + 8 + 12);
+        let repr = Repr::parse_bytes(&buffer[..len]).unwrap();
+        assert_eq!(&repr.entries[1..4], &[0, 0, 0x20]);
+        assert_eq!(&repr.entries[17..20], &[0, 2, 0x11]);
+        assert_eq!(&repr.entries[33..36], &[0, 0, 0x00]);
+        assert_eq!(repr.check_no_orphan_options(), Ok(()));
+
+        // Three runs don't fit; the new option is rolled back
+        let before = builder.buffer_len();
+        assert_eq!(
+            builder.add_entry_with_options(offer(0x1003), &[endpoint(1), endpoint(2), endpoint(3), lb]),
+            Err(Error::LengthOverflow)
+        );
+        assert_eq!(builder.buffer_len(), before);
+        assert_eq!(builder.finish(&mut buffer[..len - 1]), Err(Error::BufferTooShort));
+    }
+
+    #[test]
+    fn test_builder_dedup_toggle() {
+        let mut buffer = [0u8; 128];
+
+        let mut builder = MessageBuilder::new(0xC0);
+        builder.add_entry_with_options(offer(0x1000), &[endpoint(1)]).unwrap();
+        builder.add_entry_with_options(offer(0x1001), &[endpoint(1)]).unwrap();
+        let len = builder.finish(&mut buffer).unwrap();
+        let repr = Repr::parse_bytes(&buffer[..len]).unwrap();
+        assert_eq!(repr.options.len(), 12);
+        assert_eq!(repr.entries[1], 0);
+        assert_eq!(repr.entries[17], 0);
+
+        let mut builder = MessageBuilder::new(0xC0).dedup_options(false);
+        builder.add_entry_with_options(offer(0x1000), &[endpoint(1)]).unwrap();
+        builder.add_entry_with_options(offer(0x1001), &[endpoint(1)]).unwrap();
+        let len = builder.finish(&mut buffer).unwrap();
+        let repr = Repr::parse_bytes(&buffer[..len]).unwrap();
+        assert_eq!(repr.options.len(), 24);
+        assert_eq!(repr.entries[1], 0);
+        assert_eq!(repr.entries[17], 1);
+    }
+
+    #[test]
+    fn test_builder_rejects_dangling_refs() {
+        let mut other = MessageBuilder::new(0x00);
+        other.add_option(endpoint(1)).unwrap();
+        let foreign = other.add_option(endpoint(2)).unwrap();
+
+        let mut builder = MessageBuilder::new(0x00);
+        let own = builder.add_option(endpoint(1)).unwrap();
+        builder.add_entry(offer(0x1000), &[own], &[]).unwrap();
+        builder.add_entry(offer(0x1001), &[], &[foreign]).unwrap();
+        let mut buffer = [0u8; 128];
+        assert_eq!(builder.finish(&mut buffer), Err(Error::DanglingOptionRef));
+
+        builder.add_option(endpoint(2)).unwrap();
+        assert!(builder.finish(&mut buffer).is_ok());
+    }
+
+    #[test]
+    fn test_option_pool_shared_across_messages() {
+        let mut pool = OptionPool::<4>::new();
+        let ep = pool.intern(&endpoint(30509)).unwrap();
+        assert_eq!(pool.intern(&endpoint(30509)).unwrap(), ep);
+        let lb = pool.intern(&OptionRepr::LoadBalancing(LoadBalancingOptionRepr { priority: 1, weight: 2 })).unwrap();
+        assert_eq!(pool.len(), 2);
+
+        let mut first = [0u8; 128];
+        let mut builder = MessageBuilder::new(0xC0);
+        let handle = builder.add_pooled(&pool, ep).unwrap();
+        assert_eq!(builder.add_pooled(&pool, ep).unwrap(), handle);
+        builder.add_entry(offer(0x1000), &[handle], &[]).unwrap();
+        let first_len = builder.finish(&mut first).unwrap();
+
+        let mut second = [0u8; 128];
+        let mut builder = MessageBuilder::new(0xC0);
+        let lb_handle = builder.add_pooled(&pool, lb).unwrap();
+        let handle = builder.add_pooled(&pool, ep).unwrap();
+        builder.add_entry(offer(0x2000), &[lb_handle, handle], &[]).unwrap();
+        let second_len = builder.finish(&mut second).unwrap();
+
+        let first = Repr::parse_bytes(&first[..first_len]).unwrap();
+        let second = Repr::parse_bytes(&second[..second_len]).unwrap();
+        assert_eq!(first.options, pool.get(ep));
+        assert_eq!(&second.options[8..], first.options);
+        let mut expected = [0u8; 12];
+        endpoint(30509).emit(&mut expected);
+        assert_eq!(pool.get(ep), expected);
+
+        let mut full = OptionPool::<1>::default();
+        full.intern(&endpoint(1)).unwrap();
+        assert_eq!(full.intern(&endpoint(2)), Err(Error::LengthOverflow));
+        let config = [1u8; 40];
+        assert_eq!(pool.intern(&OptionRepr::Configuration(&config)), Err(Error::LengthOverflow));
+    }
+
+    #[test]
+    fn test_build_subscribe_ipv4_udp() {
+        use crate::entries::{Entry, EventGroupEntry};
+        use crate::options::IPv4EndpointOption;
+
+        let mut buffer = [0u8; 64];
+        let len = build_subscribe_ipv4_udp(&mut buffer, 0x1234, 1, 2, 0x10, 3, 5, [10, 0, 0, 1], 30509).unwrap();
+        assert_eq!(len, 12 + 16 + 12);
+
+        let repr = Repr::parse_bytes(&buffer[..len]).unwrap();
+        assert_eq!(repr.flags, 0xC0);
+        assert_eq!(repr.check_lengths_exact(), Ok(()));
+        let subscribe = EventGroupEntryRepr::parse(&EventGroupEntry::new_checked(repr.entries).unwrap()).unwrap();
+        assert_eq!(subscribe.entry_type, EntryType::Subscribe);
+        assert_eq!(EventGroupKey::from(&subscribe), EventGroupKey::new(ServiceKey::new(0x1234, 1, 2), 0x10));
+        assert_eq!((subscribe.reserved_and_counter.counter(), subscribe.ttl), (3, 5));
+        assert_eq!(subscribe.validate_subscribe(repr.options), Ok(()));
+
+        let run = Entry::from_buffer(repr.entries).unwrap().first_run_bytes(repr.options).unwrap();
+        let endpoint = IPv4EndpointOptionRepr::parse(&IPv4EndpointOption::parse_from(run).unwrap()).unwrap();
+        assert_eq!(endpoint.endpoint(), ([10, 0, 0, 1], 30509, TransportProtocol::UDP));
+
+        assert_eq!(
+            build_subscribe_ipv4_udp(&mut buffer[..39], 0x1234, 1, 2, 0x10, 3, 5, [10, 0, 0, 1], 30509),
+            Err(Error::BufferTooShort)
+        );
+    }
+}
@@ -1,4 +1,5 @@
-use crate::error::ConfigError;
+use crate::error::{ConfigError, Error};
+use crate::options::{DiscardableFlag, OptionHeader, OptionType};
 
 /// A single configuration entry reference (zero-copy, no_std compatible).
 ///
@@ -91,6 +92,7 @@ impl<'a> ConfigEntry<'a> {
     /// # Returns
     /// * `Ok(ConfigEntry)` if parse succeeds
     /// * `Err(ConfigError)` if format is invalid
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &'a str) -> Result<Self, ConfigError> {
         if s.is_empty() {
             return Err(ConfigError::InvalidKey);
@@ -132,7 +134,7 @@ impl<'a> ConfigEntry<'a> {
 
         // Key must be printable US-ASCII (0x20-0x7E), excluding '='
         for &byte in key.as_bytes() {
-            if byte < 0x20 || byte > 0x7E || byte == b'=' {
+            if !(0x20..=0x7E).contains(&byte) || byte == b'=' {
                 return Err(ConfigError::InvalidKey);
             }
             if byte != b' ' && byte != b'\t' {
@@ -193,6 +195,61 @@ impl<'a> ConfigEntry<'a> {
     }
 }
 
+/// A configuration entry that owns its key and value in fixed-size storage.
+///
+/// `ConfigEntry` borrows its strings, which is awkward for values built at
+/// runtime. This stores up to `N` bytes each of key and value inline, without
+/// allocating, and lends them out via `as_entry` for serialization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OwnedConfigEntry<const N: usize> {
+    key: [u8; N],
+    key_len: usize,
+    value: [u8; N],
+    /// None = boolean flag
+    value_len: Option<usize>,
+}
+
+impl<const N: usize> OwnedConfigEntry<N> {
+    /// Copy a key and optional value into a new owned entry.
+    ///
+    /// # Parameters
+    /// * `key` - The entry key (printable ASCII, no '=')
+    /// * `value` - None for boolean flag, Some(str) for key=value
+    ///
+    /// # Returns
+    /// * `Ok(OwnedConfigEntry)` if the key is valid and both strings fit
+    /// * `Err(ConfigError::InvalidKey)` if key is malformed
+    /// * `Err(ConfigError::BufferTooSmall)` if key or value exceed `N` bytes
+    pub fn new(key: &str, value: Option<&str>) -> Result<Self, ConfigError> {
+        ConfigEntry::new(key, value)?;
+        if key.len() > N || value.is_some_and(|v| v.len() > N) {
+            return Err(ConfigError::BufferTooSmall);
+        }
+
+        let mut entry = OwnedConfigEntry { key: [0; N], key_len: key.len(), value: [0; N], value_len: None };
+        entry.key[..key.len()].copy_from_slice(key.as_bytes());
+        if let Some(value) = value {
+            entry.value[..value.len()].copy_from_slice(value.as_bytes());
+            entry.value_len = Some(value.len());
+        }
+        Ok(entry)
+    }
+
+    /// Borrow this entry as a zero-copy `ConfigEntry`.
+    pub fn as_entry(&self) -> ConfigEntry<'_> {
+        ConfigEntry {
+            key: Self::as_str(&self.key[..self.key_len]),
+            value: self.value_len.map(|len| Self::as_str(&self.value[..len])),
+        }
+    }
+
+    /// Both strings are only ever copied whole from a `&str` in `new`, so
+    /// they are valid UTF-8.
+    fn as_str(bytes: &[u8]) -> &str {
+        core::str::from_utf8(bytes).expect("OwnedConfigEntry holds bytes copied from a &str")
+    }
+}
+
 /// Iterator over configuration entries in wire format.
 ///
 /// Parses entries from the DNS-SD TXT record format:
@@ -297,6 +354,125 @@ impl ConfigurationOption {
         ConfigEntryIter::new(data)
     }
 
+    /// Parse every configuration entry into a fixed-size array.
+    ///
+    /// The allocation-free way to index entries; slots past the returned
+    /// count are `None`.
+    ///
+    /// # Parameters
+    /// * `data` - Wire format buffer: `[len][string][len][string]...[0x00]`
+    ///
+    /// # Returns
+    /// * `Ok((array, count))` with the parsed entries and how many there are
+    /// * `Err(ConfigError::TooManyEntries)` if there are more than `N` entries
+    /// * `Err(ConfigError)` if an entry fails to parse
+    pub fn collect_bounded<'a, const N: usize>(
+        data: &'a [u8],
+    ) -> Result<([Option<ConfigEntry<'a>>; N], usize), ConfigError> {
+        let mut entries = [None; N];
+        let mut count = 0;
+        for entry in Self::parse(data) {
+            let slot = entries.get_mut(count).ok_or(ConfigError::TooManyEntries)?;
+            *slot = Some(entry?);
+            count += 1;
+        }
+        Ok((entries, count))
+    }
+
+    /// Find the value of a key, DNS-SD style.
+    ///
+    /// Keys match case-insensitively and only the first occurrence of a key
+    /// counts, as RFC 6763 requires.
+    ///
+    /// # Parameters
+    /// * `data` - Wire format buffer: `[len][string][len][string]...[0x00]`
+    /// * `key` - Key to look up
+    ///
+    /// # Returns
+    /// * `Ok(Some(entry))` with the first entry for `key`
+    /// * `Ok(None)` if the key is absent
+    /// * `Err(ConfigError)` if an entry before it fails to parse
+    fn find<'a>(data: &'a [u8], key: &str) -> Result<Option<ConfigEntry<'a>>, ConfigError> {
+        for entry in Self::parse(data) {
+            let entry = entry?;
+            if entry.key().eq_ignore_ascii_case(key) {
+                return Ok(Some(entry));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Read a key's value as a decimal `u16`.
+    ///
+    /// # Parameters
+    /// * `data` - Wire format buffer: `[len][string][len][string]...[0x00]`
+    /// * `key` - Key to look up (case-insensitive, first occurrence wins)
+    ///
+    /// # Returns
+    /// * `Ok(Some(value))` if the key is present with a numeric value
+    /// * `Ok(None)` if the key is absent
+    /// * `Err(ConfigError::InvalidValue)` if the key is a flag or its value
+    ///   isn't a decimal number in `0..=65535`
+    /// * `Err(ConfigError)` if an entry fails to parse
+    ///
+    /// # Example
+    /// ```
+    /// use someip_sd_wire::config::ConfigurationOption;
+    ///
+    /// let data = b"\x0aport=30509\x00";
+    /// assert_eq!(ConfigurationOption::get_u16(data, "port"), Ok(Some(30509)));
+    /// assert_eq!(ConfigurationOption::get_u16(data, "weight"), Ok(None));
+    /// ```
+    pub fn get_u16(data: &[u8], key: &str) -> Result<Option<u16>, ConfigError> {
+        Self::find(data, key)?
+            .map(|entry| {
+                entry
+                    .value()
+                    .and_then(|value| value.parse().ok())
+                    .ok_or(ConfigError::InvalidValue)
+            })
+            .transpose()
+    }
+
+    /// Read the DNS-SD `txtvers` key.
+    ///
+    /// # Parameters
+    /// * `data` - Wire format buffer: `[len][string][len][string]...[0x00]`
+    ///
+    /// # Returns
+    /// * `Ok(Some(version))` if `txtvers` is present with a numeric value
+    /// * `Ok(None)` if `txtvers` is absent
+    /// * `Err(ConfigError::InvalidValue)` if the value isn't a decimal number
+    ///   in `0..=255`
+    /// * `Err(ConfigError)` if an entry fails to parse
+    pub fn txtvers(data: &[u8]) -> Result<Option<u8>, ConfigError> {
+        Self::get_u16(data, "txtvers")?
+            .map(|version| u8::try_from(version).map_err(|_| ConfigError::InvalidValue))
+            .transpose()
+    }
+
+    /// Parse a complete configuration option (header included) strictly.
+    ///
+    /// Verifies the header type byte (0x01) and that the header length field
+    /// fits the buffer, then iterates the TXT data following the header.
+    ///
+    /// # Parameters
+    /// * `buf` - Buffer starting with the configuration option
+    ///
+    /// # Returns
+    /// * `Ok(ConfigEntryIter)` over the option's configuration entries
+    /// * `Err(Error::BufferTooShort)` if the buffer is shorter than the header
+    /// * `Err(Error::InvalidOptionType)` if the option has another type
+    /// * `Err(Error::LengthOverflow)` if the header length field is wrong
+    pub fn parse_from(buf: &[u8]) -> Result<ConfigEntryIter<'_>, Error> {
+        let header = OptionHeader::new_checked(buf)?;
+        if header.option_type() != OptionType::Configuration.as_u8() {
+            return Err(Error::InvalidOptionType(header.option_type()));
+        }
+        let len = crate::options::option_wire_len(buf)?;
+        Ok(ConfigEntryIter::new(&buf[OptionHeader::<&[u8]>::LENGTH..len]))
+    }
+
     /// Serialize configuration entries to wire format.
     ///
     /// # Parameters
@@ -358,6 +534,50 @@ impl ConfigurationOption {
         Ok(pos)
     }
 
+    /// Build a complete configuration option (header included) from key/value pairs.
+    ///
+    /// Each pair is validated like `ConfigEntry::new`, serialized as a TXT
+    /// record, and the records are wrapped in a 4-byte option header with
+    /// type 0x01 and the matching length.
+    ///
+    /// # Parameters
+    /// * `pairs` - `(key, value)` pairs; a `None` value produces a boolean flag
+    /// * `buf` - Output buffer for the option
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of bytes written
+    /// * `Err(Error::ConfigurationError)` - If a key is invalid or the buffer is too small
+    /// * `Err(Error::LengthOverflow)` - If the option exceeds the 16-bit length field
+    ///
+    /// # Example
+    /// ```
+    /// use someip_sd_wire::config::ConfigurationOption;
+    ///
+    /// let mut buf = [0u8; 64];
+    /// let len = ConfigurationOption::build_option(&[("enabled", None), ("version", Some("1.0"))], &mut buf).unwrap();
+    /// assert_eq!(buf[2], 0x01); // Configuration option type
+    /// assert_eq!(ConfigurationOption::parse_from(&buf[..len]).unwrap().count(), 2);
+    /// ```
+    pub fn build_option(pairs: &[(&str, Option<&str>)], buf: &mut [u8]) -> Result<usize, Error> {
+        for (key, value) in pairs {
+            ConfigEntry::new(key, *value)?;
+        }
+        if buf.len() < OptionHeader::<&[u8]>::LENGTH {
+            return Err(ConfigError::BufferTooSmall.into());
+        }
+
+        let entries = pairs.iter().filter_map(|(key, value)| ConfigEntry::new(key, *value).ok());
+        let data_len = Self::serialize(entries, &mut buf[OptionHeader::<&[u8]>::LENGTH..])?;
+        // Length covers the discardable/reserved byte plus the TXT data
+        let length = u16::try_from(1 + data_len).map_err(|_| Error::LengthOverflow)?;
+
+        let mut header = OptionHeader::new_unchecked(&mut buf[..OptionHeader::<&[u8]>::LENGTH]);
+        header.set_length(length);
+        header.set_option_type(OptionType::Configuration.as_u8());
+        header.set_discardable_flag(DiscardableFlag::new());
+        Ok(OptionHeader::<&[u8]>::LENGTH + data_len)
+    }
+
     /// Calculate total wire format size for entries
     pub fn wire_size<'a, I>(entries: I) -> usize
     where
@@ -376,6 +596,107 @@ impl ConfigurationOption {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_configuration_option_build_option() {
+        let mut buf = [0u8; 32];
+        let len = ConfigurationOption::build_option(&[("a", Some("xyz")), ("on", None)], &mut buf).unwrap();
+        assert_eq!(len, 4 + 1 + 5 + 1 + 2 + 1);
+        assert_eq!(&buf[..len], b"\x00\x0b\x01\x00\x05a=xyz\x02on\x00");
+        assert_eq!(crate::options::option_wire_len(&buf[..len]), Ok(len));
+
+        assert_eq!(
+            ConfigurationOption::build_option(&[("=bad", None)], &mut buf),
+            Err(Error::ConfigurationError(ConfigError::InvalidKey))
+        );
+        assert_eq!(
+            ConfigurationOption::build_option(&[("a", Some("xyz"))], &mut buf[..8]),
+            Err(Error::ConfigurationError(ConfigError::BufferTooSmall))
+        );
+        assert_eq!(
+            ConfigurationOption::build_option(&[], &mut buf[..3]),
+            Err(Error::ConfigurationError(ConfigError::BufferTooSmall))
+        );
+    }
+
+    #[test]
+    fn test_owned_config_entry() {
+        let owned = OwnedConfigEntry::<8>::new("version", Some("1234")).unwrap();
+        assert_eq!(owned.as_entry(), ConfigEntry::with_value("version", "1234").unwrap());
+
+        let flag = OwnedConfigEntry::<8>::new("enabled", None).unwrap();
+        assert!(flag.as_entry().is_flag());
+
+        let mut buf = [0u8; 32];
+        let len = ConfigurationOption::serialize([owned.as_entry(), flag.as_entry()], &mut buf).unwrap();
+        assert_eq!(&buf[..len], b"\x0cversion=1234\x07enabled\x00");
+
+        assert_eq!(OwnedConfigEntry::<4>::new("version", None), Err(ConfigError::BufferTooSmall));
+        assert_eq!(OwnedConfigEntry::<8>::new("key", Some("too long!")), Err(ConfigError::BufferTooSmall));
+        assert_eq!(OwnedConfigEntry::<8>::new("k=v", None), Err(ConfigError::InvalidKey));
+    }
+
+    #[test]
+    fn test_configuration_option_collect_bounded() {
+        let data = b"\x07enabled\x0cversion=1.0a\x00";
+        let (entries, count) = ConfigurationOption::collect_bounded::<4>(data).unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(entries[0].map(|e| e.key()), Some("enabled"));
+        assert_eq!(entries[1].and_then(|e| e.value()), Some("1.0a"));
+        assert!(entries[2].is_none());
+
+        assert_eq!(ConfigurationOption::collect_bounded::<2>(data).unwrap().1, 2);
+        assert_eq!(
+            ConfigurationOption::collect_bounded::<1>(data).map(|(_, n)| n),
+            Err(ConfigError::TooManyEntries)
+        );
+        assert_eq!(
+            ConfigurationOption::collect_bounded::<4>(&data[..5]).map(|(_, n)| n),
+            Err(ConfigError::LengthOverflow)
+        );
+    }
+
+    #[test]
+    fn test_configuration_option_parse_from() {
+        let option = b"\x00\x0a\x01\x00\x07enabled\x00";
+        let mut entries = ConfigurationOption::parse_from(option).unwrap();
+        assert_eq!(entries.next().unwrap().unwrap().key(), "enabled");
+        assert!(entries.next().is_none());
+
+        let mut wrong_type = *option;
+        wrong_type[2] = 0x02;
+        assert_eq!(ConfigurationOption::parse_from(&wrong_type).err(), Some(Error::InvalidOptionType(0x02)));
+        assert_eq!(ConfigurationOption::parse_from(&option[..12]).err(), Some(Error::LengthOverflow));
+        assert_eq!(ConfigurationOption::parse_from(&option[..3]).err(), Some(Error::BufferTooShort));
+    }
+
+    #[test]
+    fn test_configuration_option_numeric_values() {
+        let mut buf = [0u8; 64];
+        let len = ConfigurationOption::serialize(
+            [
+                ConfigEntry::with_value("TxtVers", "1").unwrap(),
+                ConfigEntry::with_value("port", "30509").unwrap(),
+                ConfigEntry::with_value("port", "1").unwrap(),
+                ConfigEntry::with_value("name", "x1").unwrap(),
+                ConfigEntry::with_value("big", "70000").unwrap(),
+                ConfigEntry::flag("flag").unwrap(),
+            ],
+            &mut buf,
+        )
+        .unwrap();
+        let data = &buf[..len];
+        assert_eq!(ConfigurationOption::txtvers(data), Ok(Some(1)));
+        assert_eq!(ConfigurationOption::get_u16(data, "port"), Ok(Some(30509)));
+        assert_eq!(ConfigurationOption::get_u16(data, "missing"), Ok(None));
+        assert_eq!(ConfigurationOption::get_u16(data, "name"), Err(ConfigError::InvalidValue));
+        assert_eq!(ConfigurationOption::get_u16(data, "big"), Err(ConfigError::InvalidValue));
+        assert_eq!(ConfigurationOption::get_u16(data, "flag"), Err(ConfigError::InvalidValue));
+
+        let len = ConfigurationOption::serialize([ConfigEntry::with_value("txtvers", "300").unwrap()], &mut buf).unwrap();
+        assert_eq!(ConfigurationOption::txtvers(&buf[..len]), Err(ConfigError::InvalidValue));
+        assert_eq!(ConfigurationOption::txtvers(b"\x00"), Ok(None));
+    }
+
     #[test]
     fn test_config_entry_flag() {
         let entry = ConfigEntry::flag("debug").unwrap();
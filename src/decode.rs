@@ -0,0 +1,93 @@
+//! Reading SOME/IP-SD messages from `std::io` streams.
+//!
+//! `Repr::write_to` covers the encode side; this module reads messages back
+//! from stream transports and file-based test vectors on the host.
+
+use crate::error::Error;
+use crate::field;
+use crate::repr::Repr;
+use byteorder::{ByteOrder, NetworkEndian};
+use std::io::{self, Read};
+use std::vec::Vec;
+
+/// Read exactly one SD message from a byte stream.
+///
+/// Reads the 8-byte header to learn the entries length, then the entries and
+/// the options length field, then the options, so no bytes past the end of the
+/// message are consumed. `buf` is cleared and receives the raw message, which
+/// the returned `Repr` borrows. It only grows as data actually arrives, so a
+/// bogus length field can't trigger a huge allocation up front.
+///
+/// # Arguments
+///
+/// * `r` - Stream positioned at the start of a message
+/// * `buf` - Storage for the message bytes
+///
+/// # Returns
+///
+/// * `Ok(Repr)` - The parsed message
+/// * `Err(io::Error)` - `UnexpectedEof` if the stream ends mid-message,
+///   `InvalidData` wrapping the crate's `Error` if the message fails to parse,
+///   or any error reported by the reader
+pub fn read_from<'b, R: Read>(r: &mut R, buf: &'b mut Vec<u8>) -> io::Result<Repr<'b>> {
+    let invalid = |err: Error| io::Error::new(io::ErrorKind::InvalidData, err);
+
+    buf.clear();
+    read_more(r, buf, field::entries::MIN_HEADER_LEN)?;
+    let entries_len = NetworkEndian::read_u32(&buf[field::entries::LENGTH]) as usize;
+    let options_length = field::entries::try_options_length(entries_len)
+        .ok_or(Error::LengthOverflow)
+        .map_err(invalid)?;
+    read_more(r, buf, options_length.end - buf.len())?;
+    let options_len = NetworkEndian::read_u32(&buf[options_length]) as usize;
+    read_more(r, buf, options_len)?;
+    Repr::parse_bytes(buf).map_err(invalid)
+}
+
+/// Append exactly `len` bytes from `r` to `buf`.
+fn read_more<R: Read>(r: &mut R, buf: &mut Vec<u8>, len: usize) -> io::Result<()> {
+    let want = buf.len() + len;
+    r.take(len as u64).read_to_end(buf)?;
+    if buf.len() < want {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::MessageBuilder;
+    use crate::entries::{EntryRepr, ServiceEntryRepr, ServiceKey};
+    use crate::options::{IPv4EndpointOptionRepr, OptionRepr, TransportProtocol};
+
+    #[test]
+    fn test_read_from() {
+        let mut builder = MessageBuilder::new(0xC0);
+        let endpoint = OptionRepr::IPv4Endpoint(IPv4EndpointOptionRepr {
+            ipv4_address: [10, 0, 0, 1],
+            protocol: TransportProtocol::UDP,
+            port: 30509,
+        });
+        let offer = EntryRepr::Service(ServiceEntryRepr::offer(ServiceKey::new(0x1234, 1, 1), 0, 3));
+        builder.add_entry_with_options(offer, &[endpoint]).unwrap();
+        let mut stream = [0u8; 128];
+        let len = builder.finish(&mut stream).unwrap();
+        let second = MessageBuilder::new(0x00).finish(&mut stream[len..]).unwrap();
+
+        // Reads exactly one message, leaving the next one in the stream.
+        let mut reader = &stream[..len + second];
+        let mut buf = Vec::new();
+        let repr = read_from(&mut reader, &mut buf).unwrap();
+        assert_eq!(repr.entries.len(), 16);
+        assert_eq!(repr.options.len(), 12);
+        assert_eq!(reader.len(), second);
+        assert!(read_from(&mut reader, &mut buf).unwrap().is_empty());
+
+        // EOF in the header, the entries and the options
+        for cut in [4, 20, len - 1] {
+            let err = read_from(&mut &stream[..cut], &mut buf).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        }
+    }
+}
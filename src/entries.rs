@@ -1,15 +1,90 @@
-/// Entry types for SOME/IP-SD messages.
-///
-/// This module provides zero-copy wrappers around service and eventgroup entries,
-/// as well as helper types for packed bitfields used within entries.
+//! Entry types for SOME/IP-SD messages.
+//!
+//! This module provides zero-copy wrappers around service and eventgroup entries,
+//! as well as helper types for packed bitfields used within entries.
 
 use crate::error::Error;
 use crate::field;
+use crate::options::{OptionsIter, SdOption};
 use byteorder::{ByteOrder, NetworkEndian};
 
 /// Result type for entry parsing operations.
 pub type Result<T> = core::result::Result<T, Error>;
 
+/// TTL value meaning the entry never expires.
+pub const TTL_INFINITE: u32 = 0xFFFFFF;
+
+/// 24-bit TTL of an entry, in seconds.
+///
+/// Wraps the two magic values: `Ttl::STOP` (0) withdraws an offer or
+/// subscription, `Ttl::INFINITE` (0xFFFFFF) never expires. Because infinite is
+/// the largest 24-bit value, ordering treats it as greater than any finite TTL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ttl(u32);
+
+impl Ttl {
+    /// TTL of a StopOffer/StopSubscribe entry.
+    pub const STOP: Ttl = Ttl(0);
+    /// TTL that never expires.
+    pub const INFINITE: Ttl = Ttl(TTL_INFINITE);
+
+    /// Creates a TTL from a value in seconds.
+    ///
+    /// # Parameters
+    ///
+    /// * `secs` - TTL in seconds (values above 24 bits saturate to infinite)
+    pub fn new(secs: u32) -> Self {
+        Ttl(secs.min(TTL_INFINITE))
+    }
+
+    /// Creates a TTL from a value in seconds, rejecting values that don't fit.
+    ///
+    /// Unlike `new`, a value above 24 bits is an error instead of being
+    /// saturated to infinite.
+    ///
+    /// # Parameters
+    ///
+    /// * `secs` - TTL in seconds (0xFFFFFF = infinite, 0 = stop)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Ttl)` if `secs` fits in 24 bits
+    /// * `Err(Error::TtlOutOfRange)` if `secs` exceeds 0xFFFFFF
+    pub fn from_seconds(secs: u32) -> Result<Self> {
+        if secs > TTL_INFINITE {
+            return Err(Error::TtlOutOfRange);
+        }
+        Ok(Ttl(secs))
+    }
+
+    /// Returns true if this TTL never expires.
+    pub fn is_infinite(&self) -> bool {
+        *self == Self::INFINITE
+    }
+
+    /// Returns true if this TTL marks a StopOffer/StopSubscribe.
+    pub fn is_stop(&self) -> bool {
+        *self == Self::STOP
+    }
+
+    /// Converts to raw u32 value for wire format.
+    pub fn as_u32(&self) -> u32 {
+        self.0
+    }
+}
+
+impl core::fmt::Display for Ttl {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.is_infinite() {
+            write!(f, "infinite")
+        } else if self.is_stop() {
+            write!(f, "stop")
+        } else {
+            write!(f, "{}s", self.0)
+        }
+    }
+}
+
 /// Entry type codes for SOME/IP-SD entries.
 ///
 /// Each SOME/IP-SD entry starts with a type field that identifies whether
@@ -77,6 +152,49 @@ impl EntryType {
     pub fn is_eventgroup_entry(&self) -> bool {
         matches!(self, EntryType::Subscribe | EntryType::SubscribeAck)
     }
+
+    /// Returns the entry type's name for logging, e.g. "OfferService".
+    pub fn name(&self) -> &'static str {
+        match self {
+            EntryType::FindService => "FindService",
+            EntryType::OfferService => "OfferService",
+            EntryType::Subscribe => "Subscribe",
+            EntryType::SubscribeAck => "SubscribeAck",
+        }
+    }
+}
+
+/// Length of an "Unknown(0xNN)" name.
+const UNKNOWN_NAME_LEN: usize = 13;
+
+/// "Unknown(0xNN)" names for every byte value, built at compile time so
+/// `entry_type_name` can hand out `&'static str` without allocating.
+static UNKNOWN_NAMES: [[u8; UNKNOWN_NAME_LEN]; 256] = {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    let mut names = [*b"Unknown(0x00)"; 256];
+    let mut i = 0;
+    while i < 256 {
+        names[i][10] = HEX[i >> 4];
+        names[i][11] = HEX[i & 0x0F];
+        i += 1;
+    }
+    names
+};
+
+/// Get a human-readable name for a raw entry type byte.
+///
+/// # Parameters
+///
+/// * `byte` - Raw entry type byte from wire format
+///
+/// # Returns
+///
+/// The `EntryType::name` for known types, otherwise "Unknown(0xNN)"
+pub fn entry_type_name(byte: u8) -> &'static str {
+    match EntryType::from_u8(byte) {
+        Some(entry_type) => entry_type.name(),
+        None => core::str::from_utf8(&UNKNOWN_NAMES[byte as usize]).unwrap_or("Unknown"),
+    }
 }
 
 /// Two 4-bit fields packed into a single byte.
@@ -161,6 +279,12 @@ impl NumberOfOptions {
     }
 }
 
+impl Default for NumberOfOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// 12-bit reserved field + 4-bit counter packed into a u16.
 ///
 /// Used in EventGroup entries. The reserved field must be 0x000 per specification.
@@ -251,6 +375,12 @@ impl ReservedAndCounter {
     }
 }
 
+impl Default for ReservedAndCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Zero-copy wrapper around a Service Entry (16 bytes).
 ///
 /// Service entries are used for FindService and OfferService messages in SOME/IP-SD.
@@ -318,6 +448,16 @@ impl<T: AsRef<[u8]>> ServiceEntry<T> {
         Ok(())
     }
 
+    /// Get a reference to the underlying buffer.
+    pub fn buffer(&self) -> &T {
+        &self.buffer
+    }
+
+    /// Consume the wrapper and return the underlying buffer.
+    pub fn into_buffer(self) -> T {
+        self.buffer
+    }
+
     /// Validate the entry has a valid service entry type.
     ///
     /// # Returns
@@ -397,6 +537,11 @@ impl<T: AsRef<[u8]>> ServiceEntry<T> {
         ((bytes[0] as u32) << 16) | ((bytes[1] as u32) << 8) | (bytes[2] as u32)
     }
 
+    /// Get the TTL field as a typed `Ttl`.
+    pub fn ttl_typed(&self) -> Ttl {
+        Ttl::new(self.ttl())
+    }
+
     /// Get the Minor Version (4 bytes at offset 12-15, network byte order).
     ///
     /// # Returns
@@ -549,6 +694,16 @@ impl<T: AsRef<[u8]>> EventGroupEntry<T> {
         Ok(())
     }
 
+    /// Get a reference to the underlying buffer.
+    pub fn buffer(&self) -> &T {
+        &self.buffer
+    }
+
+    /// Consume the wrapper and return the underlying buffer.
+    pub fn into_buffer(self) -> T {
+        self.buffer
+    }
+
     /// Validate the entry has a valid eventgroup entry type.
     ///
     /// # Returns
@@ -628,6 +783,11 @@ impl<T: AsRef<[u8]>> EventGroupEntry<T> {
         ((bytes[0] as u32) << 16) | ((bytes[1] as u32) << 8) | (bytes[2] as u32)
     }
 
+    /// Get the TTL field as a typed `Ttl`.
+    pub fn ttl_typed(&self) -> Ttl {
+        Ttl::new(self.ttl())
+    }
+
     /// Get the packed reserved and counter field (2 bytes at offset 12-13).
     ///
     /// # Returns
@@ -731,10 +891,535 @@ impl<T: AsRef<[u8]> + AsMut<[u8]>> EventGroupEntry<T> {
     }
 }
 
+/// Zero-copy view of any SOME/IP-SD entry, dispatched on the type byte.
+///
+/// All entries are 16 bytes long; the type byte decides whether the entry
+/// is interpreted as a service entry or an eventgroup entry.
+#[derive(Debug, Clone, Copy)]
+pub enum Entry<T: AsRef<[u8]>> {
+    /// FindService or OfferService entry.
+    Service(ServiceEntry<T>),
+    /// Subscribe or SubscribeAck entry.
+    EventGroup(EventGroupEntry<T>),
+    /// Entry with an unrecognized type byte (only produced by lenient parsing).
+    Unknown {
+        /// Raw entry type byte.
+        type_byte: u8,
+        /// The complete 16-byte entry as received.
+        raw: [u8; 16],
+    },
+}
+
+impl<T: AsRef<[u8]>> Entry<T> {
+    /// Size of any entry in bytes.
+    pub const LENGTH: usize = 16;
+
+    /// Create an Entry from a buffer, dispatching on the type byte.
+    ///
+    /// # Parameters
+    /// * `buffer` - The buffer containing the 16-byte entry
+    ///
+    /// # Returns
+    /// * `Ok(Entry)` if the buffer is long enough and the type is known
+    /// * `Err(Error::BufferTooShort)` if buffer is shorter than 16 bytes
+    /// * `Err(Error::InvalidEntryType)` if the type byte is unknown
+    pub fn from_buffer(buffer: T) -> Result<Self> {
+        match Self::from_buffer_lenient(buffer)? {
+            Entry::Unknown { type_byte, .. } => Err(Error::InvalidEntryType(type_byte)),
+            entry => Ok(entry),
+        }
+    }
+
+    /// Create an Entry from a buffer, tolerating unknown type bytes.
+    ///
+    /// Unknown entry types are returned as `Entry::Unknown` carrying a copy
+    /// of the raw entry instead of producing an error.
+    ///
+    /// # Parameters
+    /// * `buffer` - The buffer containing the 16-byte entry
+    ///
+    /// # Returns
+    /// * `Ok(Entry)` if the buffer is at least 16 bytes
+    /// * `Err(Error::BufferTooShort)` if buffer is too short
+    pub fn from_buffer_lenient(buffer: T) -> Result<Self> {
+        let bytes = buffer.as_ref();
+        if bytes.len() < Self::LENGTH {
+            return Err(Error::BufferTooShort);
+        }
+
+        let type_byte = bytes[field::service_entry::TYPE.start];
+        match EntryType::from_u8(type_byte) {
+            Some(et) if et.is_service_entry() => Ok(Entry::Service(ServiceEntry::new_unchecked(buffer))),
+            Some(_) => Ok(Entry::EventGroup(EventGroupEntry::new_unchecked(buffer))),
+            None => {
+                let mut raw = [0u8; 16];
+                raw.copy_from_slice(&bytes[..Self::LENGTH]);
+                Ok(Entry::Unknown { type_byte, raw })
+            }
+        }
+    }
+
+    /// Get the underlying 16 entry bytes regardless of variant.
+    fn bytes(&self) -> &[u8] {
+        match self {
+            Entry::Service(entry) => entry.buffer.as_ref(),
+            Entry::EventGroup(entry) => entry.buffer.as_ref(),
+            Entry::Unknown { raw, .. } => raw,
+        }
+    }
+
+    /// Check whether the entry has a second option run.
+    ///
+    /// A nonzero second-run index with a zero count is not a run.
+    pub fn uses_two_runs(&self) -> bool {
+        self.second_run().is_some()
+    }
+
+    /// Check whether the entry references any options in either run.
+    pub fn uses_any_options(&self) -> bool {
+        self.first_run().is_some() || self.second_run().is_some()
+    }
+
+    /// Validate that the entry's two option runs are coherent with each other.
+    ///
+    /// The runs must not overlap: an option may be shared between entries,
+    /// but one entry referencing the same option from both runs indicates
+    /// corruption. A run with a zero count is absent whatever its index, so
+    /// a stale index with count 0 is accepted. The options array itself is
+    /// not consulted.
+    ///
+    /// # Returns
+    /// * `Ok(())` if the runs are disjoint or at most one run is present
+    /// * `Err(Error::LengthOverflow)` if the two runs overlap
+    pub fn check_option_run_consistency(&self) -> Result<()> {
+        if let (Some((first, first_count)), Some((second, second_count))) = (self.first_run(), self.second_run()) {
+            let first_end = first as usize + first_count as usize;
+            let second_end = second as usize + second_count as usize;
+            if (first as usize) < second_end && (second as usize) < first_end {
+                return Err(Error::LengthOverflow);
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the raw entry type byte, for known and unknown entries alike.
+    pub fn type_byte(&self) -> u8 {
+        self.bytes()[field::service_entry::TYPE.start]
+    }
+
+    /// Get the Service ID (2 bytes at offset 4-5).
+    pub fn service_id(&self) -> u16 {
+        NetworkEndian::read_u16(&self.bytes()[field::service_entry::SERVICE_ID])
+    }
+
+    /// Get the Instance ID (2 bytes at offset 6-7).
+    pub fn instance_id(&self) -> u16 {
+        NetworkEndian::read_u16(&self.bytes()[field::service_entry::INSTANCE_ID])
+    }
+
+    /// Get the major version (1 byte at offset 8).
+    pub fn major_version(&self) -> u8 {
+        self.bytes()[field::service_entry::MAJOR_VERSION.start]
+    }
+
+    /// Get the TTL in seconds (3 bytes at offset 9-11).
+    pub fn ttl(&self) -> u32 {
+        let bytes = &self.bytes()[field::service_entry::TTL];
+        u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]])
+    }
+
+    /// Returns true if the TTL is zero, i.e. a StopOffer or StopSubscribe.
+    pub fn ttl_is_stop(&self) -> bool {
+        self.ttl() == 0
+    }
+
+    /// Get the raw index of the first option run (1 byte at offset 1).
+    ///
+    /// Unlike `first_run`, this is returned even if the run is empty.
+    pub fn index_first_option_run(&self) -> u8 {
+        self.bytes()[field::service_entry::INDEX_FIRST_OPTION_RUN.start]
+    }
+
+    /// Get the first option run as `(index, count)`.
+    ///
+    /// # Returns
+    /// * `Some((index, count))` if the first run references at least one option
+    /// * `None` if the first run is empty
+    pub fn first_run(&self) -> Option<(u8, u8)> {
+        let bytes = self.bytes();
+        let count = NumberOfOptions::from_u8(bytes[field::service_entry::NUMBER_OF_OPTIONS.start]).options1();
+        if count == 0 {
+            return None;
+        }
+        Some((bytes[field::service_entry::INDEX_FIRST_OPTION_RUN.start], count))
+    }
+
+    /// Get the second option run as `(index, count)`.
+    ///
+    /// # Returns
+    /// * `Some((index, count))` if the second run references at least one option
+    /// * `None` if the second run is empty
+    pub fn second_run(&self) -> Option<(u8, u8)> {
+        let bytes = self.bytes();
+        let count = NumberOfOptions::from_u8(bytes[field::service_entry::NUMBER_OF_OPTIONS.start]).options2();
+        if count == 0 {
+            return None;
+        }
+        Some((bytes[field::service_entry::INDEX_SECOND_OPTION_RUN.start], count))
+    }
+
+    /// Get the bytes of the options in the first run.
+    ///
+    /// The options of a run have consecutive indices, so they form one
+    /// contiguous sub-slice of the options array that can be forwarded as is.
+    ///
+    /// # Parameters
+    /// * `opts` - The options array of the message carrying this entry
+    ///
+    /// # Returns
+    /// * `Ok(&[u8])` - The run's options (empty if the run is empty)
+    /// * `Err(Error::LengthOverflow)` if the run extends past the options array
+    pub fn first_run_bytes<'a>(&self, opts: &'a [u8]) -> Result<&'a [u8]> {
+        run_bytes(self.first_run(), opts)
+    }
+
+    /// Get the bytes of the options in the second run.
+    ///
+    /// # Parameters
+    /// * `opts` - The options array of the message carrying this entry
+    ///
+    /// # Returns
+    /// * `Ok(&[u8])` - The run's options (empty if the run is empty)
+    /// * `Err(Error::LengthOverflow)` if the run extends past the options array
+    pub fn second_run_bytes<'a>(&self, opts: &'a [u8]) -> Result<&'a [u8]> {
+        run_bytes(self.second_run(), opts)
+    }
+}
+
+/// Slice the options of an `(index, count)` run out of an options array.
+fn run_bytes(run: Option<(u8, u8)>, opts: &[u8]) -> Result<&[u8]> {
+    let Some((index, count)) = run else {
+        return Ok(&opts[..0]);
+    };
+    let start = crate::options::offset_of(opts, index as usize)?;
+    let mut end = start;
+    for _ in 0..count {
+        if end >= opts.len() {
+            return Err(Error::LengthOverflow);
+        }
+        end += crate::options::option_wire_len(&opts[end..])?;
+    }
+    Ok(&opts[start..end])
+}
+
+/// Iterator over the 16-byte entries of an entries array.
+///
+/// In strict mode (`new`) an unknown entry type yields `Error::InvalidEntryType`.
+/// In lenient mode (`new_lenient`) it yields `Entry::Unknown` instead. Either way
+/// the iterator advances by 16 bytes, so one bad entry never desynchronizes the
+/// rest of the array. A trailing partial entry yields `Error::BufferTooShort`
+/// and ends the iteration.
+#[derive(Debug, Clone)]
+pub struct EntriesIter<'a> {
+    data: &'a [u8],
+    pos: usize,
+    lenient: bool,
+}
+
+impl<'a> EntriesIter<'a> {
+    /// Create a strict iterator over an entries array.
+    ///
+    /// # Parameters
+    /// * `data` - The entries array (e.g. `Repr::entries`)
+    pub fn new(data: &'a [u8]) -> Self {
+        EntriesIter { data, pos: 0, lenient: false }
+    }
+
+    /// Create a lenient iterator that yields unknown entry types as `Entry::Unknown`.
+    ///
+    /// # Parameters
+    /// * `data` - The entries array (e.g. `Repr::entries`)
+    pub fn new_lenient(data: &'a [u8]) -> Self {
+        EntriesIter { data, pos: 0, lenient: true }
+    }
+}
+
+impl<'a> Iterator for EntriesIter<'a> {
+    type Item = Result<Entry<&'a [u8]>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.data.len() {
+            return None;
+        }
+
+        let end = self.pos + Entry::<&[u8]>::LENGTH;
+        if end > self.data.len() {
+            self.pos = self.data.len();
+            return Some(Err(Error::BufferTooShort));
+        }
+
+        let chunk = &self.data[self.pos..end];
+        self.pos = end;
+        if self.lenient {
+            Some(Entry::from_buffer_lenient(chunk))
+        } else {
+            Some(Entry::from_buffer(chunk))
+        }
+    }
+}
+
+/// Mutable view of an entry, for editing fields in place.
+pub type EntryMut<'a> = Entry<&'a mut [u8]>;
+
+/// Iterator over the entries of an entries array, yielding mutable views.
+///
+/// Each item borrows its own disjoint 16-byte chunk, so the setters of
+/// several entries can be used in turn without re-encoding the message.
+/// Unknown entry types are yielded as `Entry::Unknown` (a copy, so edits to
+/// it don't reach the buffer) and a trailing partial entry is skipped.
+#[derive(Debug)]
+pub struct EntriesIterMut<'a> {
+    chunks: core::slice::ChunksExactMut<'a, u8>,
+}
+
+impl<'a> EntriesIterMut<'a> {
+    /// Create a mutable iterator over an entries array.
+    ///
+    /// # Parameters
+    /// * `data` - The entries array (e.g. `Packet::entries_array_mut`)
+    pub fn new(data: &'a mut [u8]) -> Self {
+        EntriesIterMut {
+            chunks: data.chunks_exact_mut(Entry::<&[u8]>::LENGTH),
+        }
+    }
+}
+
+impl<'a> Iterator for EntriesIterMut<'a> {
+    type Item = EntryMut<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Chunks are exactly 16 bytes, so lenient dispatch can't fail
+        self.chunks.next().and_then(|chunk| Entry::from_buffer_lenient(chunk).ok())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_entry_constructors_from_key() {
+        let key = ServiceKey::new(0x1234, 0x0001, 2);
+        let offer = ServiceEntryRepr::offer(key, 7, 3);
+        assert_eq!(offer.entry_type, EntryType::OfferService);
+        assert_eq!((offer.minor_version, offer.ttl), (7, 3));
+        assert_eq!(ServiceKey::from(&offer), key);
+        assert_eq!(offer.validate_as_offer(), Ok(()));
+
+        let find = ServiceEntryRepr::find(key, 0xFFFF_FFFF, 3);
+        assert_eq!(find.entry_type, EntryType::FindService);
+        assert_eq!(find.validate_as_find(), Ok(()));
+
+        let group = EventGroupKey::new(key, 0x0010);
+        let subscribe = EventGroupEntryRepr::subscribe(group, 1, 3);
+        assert_eq!(subscribe.entry_type, EntryType::Subscribe);
+        assert_eq!(subscribe.reserved_and_counter.counter(), 1);
+        assert_eq!(EventGroupKey::from(&subscribe), group);
+    }
+
+    #[test]
+    fn test_stop_constructors() {
+        let stop = ServiceEntryRepr::stop_offer(0x1234, 0x0001, 2, 7);
+        assert_eq!(stop, ServiceEntryRepr::offer(ServiceKey::new(0x1234, 0x0001, 2), 7, 0));
+        assert!(stop.is_stop());
+        assert!(!ServiceEntryRepr::offer(ServiceKey::new(0x1234, 0x0001, 2), 7, 3).is_stop());
+        assert!(!ServiceEntryRepr::find(ServiceKey::new(0x1234, 0x0001, 2), 7, 0).is_stop());
+
+        let stop = EventGroupEntryRepr::stop_subscribe(0x1234, 0x0001, 2, 0x0010, 3);
+        assert_eq!(stop.entry_type, EntryType::Subscribe);
+        assert_eq!((stop.eventgroup_id, stop.ttl), (0x0010, 0));
+        assert_eq!(stop.reserved_and_counter.counter(), 3);
+        assert!(stop.is_stop());
+        assert!(!EventGroupEntryRepr::nack_for(&stop).is_stop());
+    }
+
+    #[test]
+    fn test_entry_type_names() {
+        assert_eq!(EntryType::FindService.name(), "FindService");
+        assert_eq!(EntryType::SubscribeAck.name(), "SubscribeAck");
+        assert_eq!(entry_type_name(0x01), "OfferService");
+        assert_eq!(entry_type_name(0x06), "Subscribe");
+        assert_eq!(entry_type_name(0x42), "Unknown(0x42)");
+        assert_eq!(entry_type_name(0xFF), "Unknown(0xff)");
+    }
+
+    #[test]
+    fn test_entry_shared_accessors() {
+        let key = ServiceKey::new(0x1234, 0x5678, 2);
+        let mut data = [0u8; 32];
+        ServiceEntryRepr::offer(key, 0, 0).emit(&mut ServiceEntry::new_unchecked(&mut data[..16]));
+        let mut subscribe = EventGroupEntryRepr::subscribe(EventGroupKey::new(key, 0x10), 0, 0x00AB_CDEF);
+        subscribe.index_first_option_run = 3;
+        subscribe.emit(&mut EventGroupEntry::new_unchecked(&mut data[16..]));
+
+        for entry in EntriesIter::new(&data) {
+            let entry = entry.unwrap();
+            assert_eq!((entry.service_id(), entry.instance_id(), entry.major_version()), (0x1234, 0x5678, 2));
+        }
+        let offer = Entry::from_buffer(&data[..16]).unwrap();
+        assert!(offer.ttl_is_stop());
+        assert_eq!(offer.index_first_option_run(), 0);
+        let subscribe = Entry::from_buffer(&data[16..]).unwrap();
+        assert_eq!(subscribe.ttl(), 0x00AB_CDEF);
+        assert!(!subscribe.ttl_is_stop());
+        assert_eq!(subscribe.index_first_option_run(), 3);
+    }
+
+    #[test]
+    fn test_entries_iter_mut() {
+        let key = ServiceKey::new(0x1234, 0x0001, 1);
+        let mut data = [0u8; 16 * 3 + 4];
+        ServiceEntryRepr::offer(key, 0, 10).emit(&mut ServiceEntry::new_unchecked(&mut data[..16]));
+        EventGroupEntryRepr::subscribe(EventGroupKey::new(key, 0x10), 0, 10)
+            .emit(&mut EventGroupEntry::new_unchecked(&mut data[16..32]));
+        data[32] = 0x42;
+
+        let mut count = 0;
+        for entry in EntriesIterMut::new(&mut data) {
+            match entry {
+                Entry::Service(mut e) => e.set_ttl(e.ttl() - 4),
+                Entry::EventGroup(mut e) => e.set_ttl(e.ttl() - 4),
+                Entry::Unknown { type_byte, .. } => assert_eq!(type_byte, 0x42),
+            }
+            count += 1;
+        }
+        assert_eq!(count, 3);
+        assert_eq!(ServiceEntry::new_unchecked(&data[..16]).ttl(), 6);
+        assert_eq!(EventGroupEntry::new_unchecked(&data[16..32]).ttl(), 6);
+    }
+
+    #[test]
+    fn test_entry_run_bytes() {
+        use crate::options::LoadBalancingOptionRepr;
+
+        let mut opts = [0u8; 24];
+        for (i, chunk) in opts.chunks_mut(8).enumerate() {
+            LoadBalancingOptionRepr { priority: i as u16, weight: 0 }.emit(chunk);
+        }
+        let offer = ServiceEntryRepr {
+            index_first_option_run: 1,
+            index_second_option_run: 0,
+            number_of_options: NumberOfOptions::from_options(2, 1),
+            ..ServiceEntryRepr::offer(ServiceKey::new(0x1234, 1, 1), 0, 3)
+        };
+        let mut buffer = [0u8; 16];
+        offer.emit(&mut ServiceEntry::new_unchecked(&mut buffer[..]));
+        let entry = Entry::from_buffer(&buffer[..]).unwrap();
+
+        assert_eq!(entry.first_run_bytes(&opts), Ok(&opts[8..24]));
+        assert_eq!(entry.second_run_bytes(&opts), Ok(&opts[..8]));
+        assert_eq!(entry.first_run_bytes(&opts[..16]), Err(Error::LengthOverflow));
+
+        buffer[3] = 0x00;
+        let entry = Entry::from_buffer(&buffer[..]).unwrap();
+        assert_eq!(entry.first_run_bytes(&opts), Ok(&[][..]));
+    }
+
+    #[test]
+    fn test_ack_for() {
+        let key = EventGroupKey::new(ServiceKey::new(0x1234, 1, 1), 0x10);
+        let subscribe = EventGroupEntryRepr {
+            index_first_option_run: 2,
+            number_of_options: NumberOfOptions::from_options(1, 0),
+            ..EventGroupEntryRepr::subscribe(key, 5, 3)
+        };
+
+        let ack = EventGroupEntryRepr::ack_for(&subscribe, 10);
+        assert_eq!(ack.entry_type, EntryType::SubscribeAck);
+        assert_eq!(ack.reserved_and_counter.counter(), 5);
+        assert_eq!(ack.ttl, 10);
+        assert_eq!(EventGroupKey::from(&ack), key);
+        assert_eq!(ack.number_of_options, NumberOfOptions::new());
+        assert!(!ack.is_subscribe_nack());
+
+        let nack = EventGroupEntryRepr::nack_for(&subscribe);
+        assert!(nack.is_subscribe_nack());
+        assert_eq!(nack.reserved_and_counter, ReservedAndCounter::from_counter(5));
+        assert_eq!(EventGroupKey::from(&nack), key);
+        assert!(!subscribe.is_subscribe_nack());
+        assert!(!EventGroupEntryRepr { ttl: 0, ..subscribe }.is_subscribe_nack());
+    }
+
+    #[test]
+    fn test_validate_subscribe() {
+        use crate::options::{IPv4SdEndpointOptionRepr, IPv4EndpointOptionRepr, LoadBalancingOptionRepr, TransportProtocol};
+
+        let mut opts = [0u8; 20];
+        LoadBalancingOptionRepr { priority: 1, weight: 2 }.emit(&mut opts[..8]);
+        IPv4EndpointOptionRepr { ipv4_address: [10, 0, 0, 1], protocol: TransportProtocol::UDP, port: 30509 }
+            .emit(&mut opts[8..]);
+
+        let key = EventGroupKey::new(ServiceKey::new(0x1234, 1, 1), 0x10);
+        let subscribe = EventGroupEntryRepr::subscribe(key, 0, 3);
+        assert_eq!(subscribe.validate_subscribe(&opts), Err(Error::MissingEndpoint));
+        assert_eq!(EventGroupEntryRepr { ttl: 0, ..subscribe }.validate_subscribe(&opts), Ok(()));
+
+        let lb_only = EventGroupEntryRepr { number_of_options: NumberOfOptions::from_options(1, 0), ..subscribe };
+        assert_eq!(lb_only.validate_subscribe(&opts), Err(Error::MissingEndpoint));
+        let with_endpoint = EventGroupEntryRepr {
+            index_second_option_run: 1,
+            number_of_options: NumberOfOptions::from_options(1, 1),
+            ..subscribe
+        };
+        assert_eq!(with_endpoint.validate_subscribe(&opts), Ok(()));
+        let past_end = EventGroupEntryRepr { number_of_options: NumberOfOptions::from_options(3, 0), ..subscribe };
+        assert_eq!(past_end.validate_subscribe(&opts), Err(Error::LengthOverflow));
+
+        // An SD endpoint is not where events are delivered
+        IPv4SdEndpointOptionRepr::default_port([10, 0, 0, 1]).emit(&mut opts[8..]);
+        assert_eq!(with_endpoint.validate_subscribe(&opts), Err(Error::MissingEndpoint));
+    }
+
+    #[test]
+    fn test_wrapper_buffer_access() {
+        let mut original = [0u8; 16];
+        ServiceEntryRepr::offer(ServiceKey::new(0x1234, 1, 1), 0, 3)
+            .emit(&mut ServiceEntry::new_unchecked(&mut original[..]));
+
+        let view = ServiceEntry::new_checked(&original[..]).unwrap();
+        assert_eq!(*view.buffer(), &original[..]);
+        let mut copy = [0u8; 16];
+        copy.copy_from_slice(view.into_buffer());
+        let mut entry = ServiceEntry::new_unchecked(&mut copy[..]);
+        entry.set_ttl(0);
+        assert_eq!(ServiceEntry::new_unchecked(entry.into_buffer()).ttl(), 0);
+        assert_eq!(ServiceEntry::new_unchecked(&original[..]).ttl(), 3);
+    }
+
+    #[test]
+    fn test_ttl() {
+        assert!(Ttl::INFINITE > Ttl::new(0xFFFFFE));
+        assert!(Ttl::STOP < Ttl::new(1));
+        assert!(Ttl::new(0x1FFFFFF).is_infinite());
+        assert!(Ttl::new(0).is_stop());
+        assert_eq!(format!("{}", Ttl::INFINITE), "infinite");
+        assert_eq!(format!("{}", Ttl::STOP), "stop");
+        assert_eq!(format!("{}", Ttl::new(3)), "3s");
+        assert_eq!(Ttl::from_seconds(TTL_INFINITE), Ok(Ttl::INFINITE));
+        assert_eq!(Ttl::from_seconds(0), Ok(Ttl::STOP));
+        assert_eq!(Ttl::from_seconds(TTL_INFINITE + 1), Err(Error::TtlOutOfRange));
+        assert_eq!(Ttl::from_seconds(3).unwrap().as_u32(), 3);
+        assert_eq!(Ttl::new(0x0100_0000), Ttl::INFINITE);
+        let offer = ServiceEntryRepr::offer(ServiceKey::new(0x1234, 1, 1), 0, 0x0100_0000);
+        assert!(offer.ttl_typed().is_infinite());
+
+        let mut buffer = [0u8; 16];
+        let mut entry = EventGroupEntry::new_unchecked(&mut buffer[..]);
+        entry.set_ttl(TTL_INFINITE);
+        assert!(entry.ttl_typed().is_infinite());
+        assert_eq!(entry.ttl(), TTL_INFINITE);
+    }
+
     #[test]
     fn test_service_entry() {
         let mut buffer = [0u8; 16];
@@ -866,6 +1551,257 @@ mod tests {
         let entry = EventGroupEntry::new_unchecked(&buffer[..]);
         assert_eq!(entry.check_entry_type(), Err(Error::InvalidEntryType(0x99)));
     }
+
+    #[test]
+    fn test_service_entry_network_byte_order() {
+        let mut buffer = [0u8; 16];
+        let mut entry = ServiceEntry::new_unchecked(&mut buffer[..]);
+        entry.set_service_id(0x0102);
+        entry.set_instance_id(0x0304);
+        entry.set_ttl(0x050607);
+        entry.set_minor_version(0x08090A0B);
+
+        assert_eq!(buffer[4..6], [0x01, 0x02]);
+        assert_eq!(buffer[6..8], [0x03, 0x04]);
+        assert_eq!(buffer[9..12], [0x05, 0x06, 0x07]);
+        assert_eq!(buffer[12..16], [0x08, 0x09, 0x0A, 0x0B]);
+    }
+
+    #[test]
+    fn test_eventgroup_entry_network_byte_order() {
+        let mut buffer = [0u8; 16];
+        let mut entry = EventGroupEntry::new_unchecked(&mut buffer[..]);
+        entry.set_service_id(0x0102);
+        entry.set_instance_id(0x0304);
+        entry.set_ttl(0x050607);
+        entry.set_reserved_and_counter(ReservedAndCounter::from_fields(0x080, 0x9));
+        entry.set_eventgroup_id(0x0A0B);
+
+        assert_eq!(buffer[4..6], [0x01, 0x02]);
+        assert_eq!(buffer[6..8], [0x03, 0x04]);
+        assert_eq!(buffer[9..12], [0x05, 0x06, 0x07]);
+        assert_eq!(buffer[12..14], [0x08, 0x09]);
+        assert_eq!(buffer[14..16], [0x0A, 0x0B]);
+    }
+
+    #[test]
+    fn test_service_entry_repr_validate() {
+        let find = ServiceEntryRepr {
+            entry_type: EntryType::FindService,
+            index_first_option_run: 0,
+            index_second_option_run: 0,
+            number_of_options: NumberOfOptions::new(),
+            service_id: 0x1234,
+            instance_id: 0xFFFF,
+            major_version: 0xFF,
+            ttl: 3,
+            minor_version: 0xFFFF_FFFF,
+        };
+        assert_eq!(find.validate_as_find(), Ok(()));
+        assert_eq!(ServiceEntryRepr { ttl: 0, ..find }.validate_as_find(), Err(Error::InvalidEntry));
+        assert_eq!(
+            ServiceEntryRepr { number_of_options: NumberOfOptions::from_options(0, 1), ..find }.validate_as_find(),
+            Err(Error::InvalidEntry)
+        );
+        assert_eq!(find.validate_as_offer(), Err(Error::InvalidEntryType(0x00)));
+
+        let offer = ServiceEntryRepr {
+            entry_type: EntryType::OfferService,
+            number_of_options: NumberOfOptions::from_options(1, 0),
+            instance_id: 0x0001,
+            major_version: 1,
+            minor_version: 0,
+            ..find
+        };
+        assert_eq!(offer.validate_as_offer(), Ok(()));
+        assert_eq!(ServiceEntryRepr { ttl: 0, ..offer }.validate_as_offer(), Ok(()));
+        assert_eq!(ServiceEntryRepr { major_version: 0xFF, ..offer }.validate_as_offer(), Err(Error::InvalidVersion));
+        assert_eq!(
+            ServiceEntryRepr { minor_version: 0xFFFF_FFFF, ..offer }.validate_as_offer(),
+            Err(Error::InvalidVersion)
+        );
+        assert_eq!(ServiceEntryRepr { instance_id: 0xFFFF, ..offer }.validate_as_offer(), Err(Error::InvalidEntry));
+        assert_eq!(offer.validate_as_find(), Err(Error::InvalidEntryType(0x01)));
+    }
+
+    #[test]
+    fn test_differs_ignoring_ttl() {
+        let offer = ServiceEntryRepr {
+            entry_type: EntryType::OfferService,
+            index_first_option_run: 0,
+            index_second_option_run: 0,
+            number_of_options: NumberOfOptions::from_options(1, 0),
+            service_id: 0x1234,
+            instance_id: 0x0001,
+            major_version: 1,
+            ttl: 3,
+            minor_version: 0,
+        };
+        assert!(!offer.differs_ignoring_ttl(&ServiceEntryRepr { ttl: 100, ..offer }));
+        assert!(offer.ttl_below(4));
+        assert!(!offer.ttl_below(3));
+        assert!(!ServiceEntryRepr { ttl: TTL_INFINITE, ..offer }.ttl_below(u32::MAX));
+        assert!(offer.differs_ignoring_ttl(&ServiceEntryRepr { minor_version: 1, ..offer }));
+
+        let subscribe = EventGroupEntryRepr {
+            entry_type: EntryType::Subscribe,
+            index_first_option_run: 0,
+            index_second_option_run: 0,
+            number_of_options: NumberOfOptions::from_options(1, 0),
+            service_id: 0x1234,
+            instance_id: 0x0001,
+            major_version: 1,
+            ttl: 3,
+            reserved_and_counter: ReservedAndCounter::from_counter(0),
+            eventgroup_id: 0x0010,
+        };
+        assert!(!subscribe.differs_ignoring_ttl(&EventGroupEntryRepr { ttl: 0, ..subscribe }));
+        assert!(subscribe.differs_ignoring_ttl(&EventGroupEntryRepr { eventgroup_id: 0x11, ..subscribe }));
+    }
+
+    #[test]
+    fn test_entry_repr_emit() {
+        let mut repr = EntryRepr::EventGroup(EventGroupEntryRepr {
+            entry_type: EntryType::Subscribe,
+            index_first_option_run: 2,
+            index_second_option_run: 0,
+            number_of_options: NumberOfOptions::from_options(1, 0),
+            service_id: 0x1234,
+            instance_id: 0x0001,
+            major_version: 1,
+            ttl: 3,
+            reserved_and_counter: ReservedAndCounter::from_counter(0),
+            eventgroup_id: 0x0010,
+        });
+        assert_eq!(repr.index_first_option_run(), 2);
+        repr.set_option_run_indices(5, 6);
+        assert_eq!(repr.index_first_option_run(), 5);
+        assert_eq!(repr.index_second_option_run(), 6);
+        assert_eq!(repr.number_of_options().options1(), 1);
+
+        let mut buf = [0u8; 16];
+        repr.emit(&mut buf);
+        assert_eq!(EntryRepr::parse(&buf), Ok(repr));
+        let entry = EventGroupEntry::new_unchecked(&buf[..]);
+        assert_eq!(entry.entry_type(), EntryType::Subscribe.as_u8());
+        assert_eq!(entry.index_first_option_run(), 5);
+        assert_eq!(entry.index_second_option_run(), 6);
+        assert_eq!(entry.eventgroup_id(), 0x0010);
+    }
+
+    #[test]
+    fn test_entries_iter_strict() {
+        let mut buffer = [0u8; 48];
+        buffer[0] = 0x01; // OfferService
+        buffer[16] = 0x42; // Unknown type
+        buffer[32] = 0x06; // Subscribe
+
+        let mut iter = EntriesIter::new(&buffer);
+        assert!(matches!(iter.next(), Some(Ok(Entry::Service(_)))));
+        assert!(matches!(iter.next(), Some(Err(Error::InvalidEntryType(0x42)))));
+        assert!(matches!(iter.next(), Some(Ok(Entry::EventGroup(_)))));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_entries_iter_lenient() {
+        let mut buffer = [0u8; 32];
+        buffer[0] = 0x42; // Unknown type
+        buffer[4] = 0x12;
+        buffer[5] = 0x34;
+        buffer[16] = 0x07; // SubscribeAck
+
+        let mut iter = EntriesIter::new_lenient(&buffer);
+        match iter.next() {
+            Some(Ok(Entry::Unknown { type_byte, raw })) => {
+                assert_eq!(type_byte, 0x42);
+                assert_eq!(raw, buffer[..16]);
+            }
+            other => panic!("expected unknown entry, got {:?}", other),
+        }
+        let entry = iter.next().unwrap().unwrap();
+        assert!(matches!(entry, Entry::EventGroup(_)));
+        assert_eq!(entry.type_byte(), 0x07);
+        assert!(iter.next().is_none());
+
+        let unknown = EntriesIter::new_lenient(&buffer).next().unwrap().unwrap();
+        assert_eq!(unknown.type_byte(), 0x42);
+    }
+
+    #[test]
+    fn test_entry_uses_runs() {
+        let mut buffer = [0u8; 16];
+        buffer[0] = 0x06;
+        // Both indices set, both counts zero
+        buffer[1] = 2;
+        buffer[2] = 4;
+        let entry = Entry::from_buffer(&buffer[..]).unwrap();
+        assert!(!entry.uses_any_options());
+        assert!(!entry.uses_two_runs());
+
+        buffer[3] = NumberOfOptions::from_options(0, 1).as_u8();
+        let entry = Entry::from_buffer(&buffer[..]).unwrap();
+        assert!(entry.uses_any_options());
+        assert!(entry.uses_two_runs());
+
+        buffer[3] = NumberOfOptions::from_options(1, 0).as_u8();
+        let entry = Entry::from_buffer(&buffer[..]).unwrap();
+        assert!(entry.uses_any_options());
+        assert!(!entry.uses_two_runs());
+    }
+
+    #[test]
+    fn test_entry_check_option_run_consistency() {
+        let mut buffer = [0u8; 16];
+        buffer[0] = 0x01;
+        // Run 1 = [0, 2), run 2 = [2, 3)
+        buffer[1..4].copy_from_slice(&[0, 2, 0x21]);
+        assert_eq!(Entry::from_buffer(&buffer[..]).unwrap().check_option_run_consistency(), Ok(()));
+
+        // Run 2 = [1, 2) overlaps run 1
+        buffer[2] = 1;
+        assert_eq!(
+            Entry::from_buffer(&buffer[..]).unwrap().check_option_run_consistency(),
+            Err(Error::LengthOverflow)
+        );
+
+        // Index set but count zero is not a run, so nothing overlaps
+        buffer[3] = 0x20;
+        assert_eq!(Entry::from_buffer(&buffer[..]).unwrap().check_option_run_consistency(), Ok(()));
+    }
+
+    #[test]
+    fn test_entry_option_runs() {
+        // No runs
+        let mut buffer = [0u8; 16];
+        buffer[0] = 0x01;
+        buffer[1] = 3; // index set but count zero
+        let entry = Entry::from_buffer(&buffer[..]).unwrap();
+        assert_eq!(entry.first_run(), None);
+        assert_eq!(entry.second_run(), None);
+
+        // One run
+        buffer[3] = NumberOfOptions::from_options(2, 0).as_u8();
+        let entry = Entry::from_buffer(&buffer[..]).unwrap();
+        assert_eq!(entry.first_run(), Some((3, 2)));
+        assert_eq!(entry.second_run(), None);
+
+        // Two runs
+        buffer[2] = 5;
+        buffer[3] = NumberOfOptions::from_options(2, 1).as_u8();
+        let entry = Entry::from_buffer(&buffer[..]).unwrap();
+        assert_eq!(entry.first_run(), Some((3, 2)));
+        assert_eq!(entry.second_run(), Some((5, 1)));
+    }
+
+    #[test]
+    fn test_entries_iter_trailing_partial() {
+        let buffer = [0u8; 20];
+        let mut iter = EntriesIter::new_lenient(&buffer);
+        assert!(matches!(iter.next(), Some(Ok(Entry::Service(_)))));
+        assert!(matches!(iter.next(), Some(Err(Error::BufferTooShort))));
+        assert!(iter.next().is_none());
+    }
 }
 
 /// High-level representation of a Service Entry.
@@ -948,6 +1884,148 @@ impl ServiceEntryRepr {
     pub const fn buffer_len() -> usize {
         field::service_entry::MINOR_VERSION.end
     }
+
+    /// Build an OfferService entry for a service.
+    ///
+    /// Option runs are left empty; set them (or use `MessageBuilder`) to
+    /// attach endpoints.
+    ///
+    /// # Parameters
+    /// * `key` - The service being offered
+    /// * `minor` - Minor version
+    /// * `ttl` - TTL in seconds (0 = StopOffer)
+    pub fn offer(key: ServiceKey, minor: u32, ttl: u32) -> Self {
+        Self::for_key(EntryType::OfferService, key, minor, ttl)
+    }
+
+    /// Build a FindService entry for a service.
+    ///
+    /// # Parameters
+    /// * `key` - The service being looked for
+    /// * `minor` - Minor version (0xFFFFFFFF = any)
+    /// * `ttl` - TTL in seconds
+    pub fn find(key: ServiceKey, minor: u32, ttl: u32) -> Self {
+        Self::for_key(EntryType::FindService, key, minor, ttl)
+    }
+
+    /// Build a StopOfferService entry for a service.
+    ///
+    /// SOME/IP-SD has no dedicated StopOffer entry type; a StopOffer is an
+    /// OfferService with TTL 0.
+    ///
+    /// # Parameters
+    /// * `service_id` - Service ID
+    /// * `instance_id` - Instance ID
+    /// * `major` - Major version
+    /// * `minor` - Minor version
+    pub fn stop_offer(service_id: u16, instance_id: u16, major: u8, minor: u32) -> Self {
+        Self::offer(ServiceKey::new(service_id, instance_id, major), minor, 0)
+    }
+
+    /// Returns true if this entry is a StopOfferService (OfferService with TTL 0).
+    pub fn is_stop(&self) -> bool {
+        self.entry_type == EntryType::OfferService && self.ttl == 0
+    }
+
+    fn for_key(entry_type: EntryType, key: ServiceKey, minor: u32, ttl: u32) -> Self {
+        ServiceEntryRepr {
+            entry_type,
+            index_first_option_run: 0,
+            index_second_option_run: 0,
+            number_of_options: NumberOfOptions::new(),
+            service_id: key.service_id,
+            instance_id: key.instance_id,
+            major_version: key.major_version,
+            ttl,
+            minor_version: minor,
+        }
+    }
+
+    /// Validate this entry as a FindService query.
+    ///
+    /// A find must have a nonzero TTL and carry no options. There is no
+    /// "stop find" in SOME/IP-SD, so a TTL of 0 is rejected.
+    ///
+    /// # Returns
+    /// * `Ok(())` if the entry is a valid FindService entry
+    /// * `Err(Error::InvalidEntryType)` if the entry is not a FindService
+    /// * `Err(Error::InvalidEntry)` if the TTL is zero or options are referenced
+    pub fn validate_as_find(&self) -> Result<()> {
+        if self.entry_type != EntryType::FindService {
+            return Err(Error::InvalidEntryType(self.entry_type.as_u8()));
+        }
+        let options = self.number_of_options.options1() + self.number_of_options.options2();
+        if self.ttl == 0 || options != 0 {
+            return Err(Error::InvalidEntry);
+        }
+        Ok(())
+    }
+
+    /// Validate this entry as an OfferService (or StopOfferService, TTL=0).
+    ///
+    /// An offer must name one concrete service instance and version, so the
+    /// wildcard values allowed in finds (0xFFFF service/instance ID, 0xFF major
+    /// version, 0xFFFFFFFF minor version) are rejected. Endpoint options are
+    /// not checked here since they live in the message's options array.
+    ///
+    /// This is a strict check on top of `parse`, which accepts wildcards.
+    ///
+    /// # Returns
+    /// * `Ok(())` if the entry is a valid OfferService entry
+    /// * `Err(Error::InvalidEntryType)` if the entry is not an OfferService
+    /// * `Err(Error::InvalidEntry)` if the service or instance ID is a wildcard
+    /// * `Err(Error::InvalidVersion)` if the major or minor version is a wildcard
+    pub fn validate_as_offer(&self) -> Result<()> {
+        if self.entry_type != EntryType::OfferService {
+            return Err(Error::InvalidEntryType(self.entry_type.as_u8()));
+        }
+        if self.service_id == 0xFFFF || self.instance_id == 0xFFFF {
+            return Err(Error::InvalidEntry);
+        }
+        if self.major_version == 0xFF || self.minor_version == 0xFFFF_FFFF {
+            return Err(Error::InvalidVersion);
+        }
+        Ok(())
+    }
+
+    /// Check whether this is a StopOfferService that still references options.
+    ///
+    /// A StopOffer (OfferService with TTL 0) only withdraws a service, so it
+    /// normally carries no options. Some senders copy the offer's options
+    /// anyway; that is not strictly illegal, but worth a conformance warning.
+    pub fn is_stop_offer_with_options(&self) -> bool {
+        self.entry_type == EntryType::OfferService
+            && self.ttl_typed().is_stop()
+            && (self.number_of_options.options1() != 0 || self.number_of_options.options2() != 0)
+    }
+
+    /// Check whether the TTL has dropped below a refresh threshold.
+    ///
+    /// An infinite TTL (`TTL_INFINITE`) never expires, so it is never below
+    /// the threshold.
+    ///
+    /// # Parameters
+    /// * `threshold_secs` - Threshold in seconds
+    pub fn ttl_below(&self, threshold_secs: u32) -> bool {
+        !self.ttl_typed().is_infinite() && self.ttl < threshold_secs
+    }
+
+    /// Get the TTL as a typed `Ttl`.
+    pub fn ttl_typed(&self) -> Ttl {
+        Ttl::new(self.ttl)
+    }
+
+    /// Check whether two entries differ in any field other than the TTL.
+    ///
+    /// The TTL is refreshed on every announcement, so a cache comparing a
+    /// re-received offer against its stored copy should only treat changes to
+    /// the other fields as material.
+    ///
+    /// # Parameters
+    /// * `other` - The entry to compare against
+    pub fn differs_ignoring_ttl(&self, other: &Self) -> bool {
+        *self != Self { ttl: self.ttl, ..*other }
+    }
 }
 
 /// High-level representation of an EventGroup Entry.
@@ -1030,8 +2108,320 @@ impl EventGroupEntryRepr {
         entry.set_eventgroup_id(self.eventgroup_id);
     }
 
+    /// Build the SubscribeAck answering a Subscribe.
+    ///
+    /// Copies the service, instance, major version, eventgroup and counter
+    /// from the Subscribe; the counter echo is what lets the subscriber match
+    /// the acknowledgment to one of several parallel subscriptions. Option
+    /// runs are left empty.
+    ///
+    /// # Parameters
+    /// * `subscribe` - The Subscribe being acknowledged
+    /// * `ttl` - TTL in seconds (0 = SubscribeNack)
+    pub fn ack_for(subscribe: &EventGroupEntryRepr, ttl: u32) -> Self {
+        EventGroupEntryRepr {
+            entry_type: EntryType::SubscribeAck,
+            index_first_option_run: 0,
+            index_second_option_run: 0,
+            number_of_options: NumberOfOptions::new(),
+            ttl,
+            reserved_and_counter: ReservedAndCounter::from_counter(subscribe.reserved_and_counter.counter()),
+            ..*subscribe
+        }
+    }
+
+    /// Build the SubscribeNack rejecting a Subscribe.
+    ///
+    /// A NACK is a SubscribeAck with TTL 0; the counter is echoed as for
+    /// `ack_for` and the reserved bits stay zero.
+    ///
+    /// # Parameters
+    /// * `subscribe` - The Subscribe being rejected
+    pub fn nack_for(subscribe: &EventGroupEntryRepr) -> Self {
+        Self::ack_for(subscribe, 0)
+    }
+
+    /// Returns true if this entry is a SubscribeNack (SubscribeAck with TTL 0).
+    pub fn is_subscribe_nack(&self) -> bool {
+        self.entry_type == EntryType::SubscribeAck && self.ttl == 0
+    }
+
+    /// Validate this entry as a Subscribe that a server can fulfil.
+    ///
+    /// A Subscribe (TTL > 0) must reference at least one endpoint or
+    /// multicast option in either of its option runs. StopSubscribe (TTL = 0)
+    /// is exempt.
+    ///
+    /// # Parameters
+    /// * `opts` - The options array of the message carrying this entry
+    ///
+    /// # Returns
+    /// * `Ok(())` if the entry is a stop or references an endpoint
+    /// * `Err(Error::InvalidEntryType)` if the entry is not a Subscribe
+    /// * `Err(Error::MissingEndpoint)` if no referenced option is an endpoint
+    /// * `Err(Error::LengthOverflow)` if a run points past the options array
+    pub fn validate_subscribe(&self, opts: &[u8]) -> Result<()> {
+        if self.entry_type != EntryType::Subscribe {
+            return Err(Error::InvalidEntryType(self.entry_type.as_u8()));
+        }
+        if self.ttl == 0 {
+            return Ok(());
+        }
+
+        let runs = [
+            (self.index_first_option_run, self.number_of_options.options1()),
+            (self.index_second_option_run, self.number_of_options.options2()),
+        ];
+        let mut found = false;
+        for (index, count) in runs {
+            let run = run_bytes((count != 0).then_some((index, count)), opts)?;
+            for option in OptionsIter::new_lenient(run) {
+                found |= matches!(
+                    option?,
+                    SdOption::IPv4Endpoint(_)
+                        | SdOption::IPv6Endpoint(_)
+                        | SdOption::IPv4Multicast(_)
+                        | SdOption::IPv6Multicast(_)
+                );
+            }
+        }
+        if found { Ok(()) } else { Err(Error::MissingEndpoint) }
+    }
+
+    /// Build a SubscribeEventgroup entry for an eventgroup.
+    ///
+    /// # Parameters
+    /// * `key` - The eventgroup being subscribed to
+    /// * `counter` - 4-bit counter distinguishing parallel subscriptions
+    /// * `ttl` - TTL in seconds (0 = StopSubscribe)
+    pub fn subscribe(key: EventGroupKey, counter: u8, ttl: u32) -> Self {
+        EventGroupEntryRepr {
+            entry_type: EntryType::Subscribe,
+            index_first_option_run: 0,
+            index_second_option_run: 0,
+            number_of_options: NumberOfOptions::new(),
+            service_id: key.service.service_id,
+            instance_id: key.service.instance_id,
+            major_version: key.service.major_version,
+            ttl,
+            reserved_and_counter: ReservedAndCounter::from_counter(counter),
+            eventgroup_id: key.eventgroup_id,
+        }
+    }
+
+    /// Build a StopSubscribeEventgroup entry for an eventgroup.
+    ///
+    /// A StopSubscribe is a Subscribe with TTL 0. The counter must match the
+    /// one used in the Subscribe being stopped.
+    ///
+    /// # Parameters
+    /// * `service_id` - Service ID
+    /// * `instance_id` - Instance ID
+    /// * `major` - Major version
+    /// * `eventgroup_id` - EventGroup ID
+    /// * `counter` - 4-bit counter of the subscription
+    pub fn stop_subscribe(service_id: u16, instance_id: u16, major: u8, eventgroup_id: u16, counter: u8) -> Self {
+        let key = EventGroupKey::new(ServiceKey::new(service_id, instance_id, major), eventgroup_id);
+        Self::subscribe(key, counter, 0)
+    }
+
+    /// Returns true if this entry is a StopSubscribe (Subscribe with TTL 0).
+    pub fn is_stop(&self) -> bool {
+        self.entry_type == EntryType::Subscribe && self.ttl == 0
+    }
+
     /// Get the wire format size of this entry (always 16 bytes).
     pub const fn buffer_len() -> usize {
         field::event_group_entry::EVENTGROUP_ID.end
     }
+
+    /// Check whether two entries differ in any field other than the TTL.
+    ///
+    /// # Parameters
+    /// * `other` - The entry to compare against
+    pub fn differs_ignoring_ttl(&self, other: &Self) -> bool {
+        *self != Self { ttl: self.ttl, ..*other }
+    }
+
+    /// Get the TTL as a typed `Ttl`.
+    pub fn ttl_typed(&self) -> Ttl {
+        Ttl::new(self.ttl)
+    }
+}
+
+/// High-level representation of any SOME/IP-SD entry.
+///
+/// Used wherever service and eventgroup entries are handled together, e.g.
+/// when building or splitting a message with a mixed entries array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryRepr {
+    /// FindService or OfferService entry.
+    Service(ServiceEntryRepr),
+    /// Subscribe or SubscribeAck entry.
+    EventGroup(EventGroupEntryRepr),
+}
+
+impl EntryRepr {
+    /// Parse a 16-byte entry, dispatching on its type byte.
+    ///
+    /// # Parameters
+    /// * `buffer` - Buffer starting with the entry
+    ///
+    /// # Returns
+    /// * `Ok(EntryRepr)` for a known entry type
+    /// * `Err(Error::BufferTooShort)` if the buffer is shorter than 16 bytes
+    /// * `Err(Error::InvalidEntryType)` if the type byte is unknown
+    pub fn parse(buffer: &[u8]) -> Result<Self> {
+        match Entry::from_buffer(buffer)? {
+            Entry::Service(entry) => Ok(EntryRepr::Service(ServiceEntryRepr::parse(&entry)?)),
+            Entry::EventGroup(entry) => Ok(EntryRepr::EventGroup(EventGroupEntryRepr::parse(&entry)?)),
+            Entry::Unknown { type_byte, .. } => Err(Error::InvalidEntryType(type_byte)),
+        }
+    }
+
+    /// Get the index of the first option run.
+    pub fn index_first_option_run(&self) -> u8 {
+        match self {
+            EntryRepr::Service(repr) => repr.index_first_option_run,
+            EntryRepr::EventGroup(repr) => repr.index_first_option_run,
+        }
+    }
+
+    /// Get the index of the second option run.
+    pub fn index_second_option_run(&self) -> u8 {
+        match self {
+            EntryRepr::Service(repr) => repr.index_second_option_run,
+            EntryRepr::EventGroup(repr) => repr.index_second_option_run,
+        }
+    }
+
+    /// Get the number of options in both runs.
+    pub fn number_of_options(&self) -> NumberOfOptions {
+        match self {
+            EntryRepr::Service(repr) => repr.number_of_options,
+            EntryRepr::EventGroup(repr) => repr.number_of_options,
+        }
+    }
+
+    /// Set the number of options in both runs.
+    ///
+    /// # Parameters
+    /// * `value` - Packed option counts of the first and second run
+    pub fn set_number_of_options(&mut self, value: NumberOfOptions) {
+        match self {
+            EntryRepr::Service(repr) => repr.number_of_options = value,
+            EntryRepr::EventGroup(repr) => repr.number_of_options = value,
+        }
+    }
+
+    /// Set the indices of both option runs, keeping the option counts.
+    ///
+    /// # Parameters
+    /// * `first` - Index of the first option run
+    /// * `second` - Index of the second option run
+    pub fn set_option_run_indices(&mut self, first: u8, second: u8) {
+        match self {
+            EntryRepr::Service(repr) => {
+                repr.index_first_option_run = first;
+                repr.index_second_option_run = second;
+            }
+            EntryRepr::EventGroup(repr) => {
+                repr.index_first_option_run = first;
+                repr.index_second_option_run = second;
+            }
+        }
+    }
+
+    /// Emit this representation into a 16-byte buffer.
+    ///
+    /// # Parameters
+    /// * `buffer` - Buffer of at least 16 bytes to write the entry into
+    pub fn emit(&self, buffer: &mut [u8]) {
+        match self {
+            EntryRepr::Service(repr) => repr.emit(&mut ServiceEntry::new_unchecked(buffer)),
+            EntryRepr::EventGroup(repr) => repr.emit(&mut EventGroupEntry::new_unchecked(buffer)),
+        }
+    }
+
+    /// Get the wire format size of an entry (always 16 bytes).
+    pub const fn buffer_len() -> usize {
+        16
+    }
+}
+
+impl From<ServiceEntryRepr> for EntryRepr {
+    fn from(repr: ServiceEntryRepr) -> Self {
+        EntryRepr::Service(repr)
+    }
+}
+
+impl From<EventGroupEntryRepr> for EntryRepr {
+    fn from(repr: EventGroupEntryRepr) -> Self {
+        EntryRepr::EventGroup(repr)
+    }
+}
+
+/// Identifies one version of one service instance.
+///
+/// Entries for the same service share the service ID, instance ID, and major
+/// version; this key groups them, e.g. to pick the eventgroup entries that
+/// belong to a tracked service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ServiceKey {
+    /// Service ID
+    pub service_id: u16,
+    /// Instance ID
+    pub instance_id: u16,
+    /// Major version
+    pub major_version: u8,
+}
+
+impl ServiceKey {
+    /// Create a new service key.
+    ///
+    /// # Parameters
+    /// * `service_id` - Service ID
+    /// * `instance_id` - Instance ID
+    /// * `major_version` - Major version
+    pub fn new(service_id: u16, instance_id: u16, major_version: u8) -> Self {
+        ServiceKey { service_id, instance_id, major_version }
+    }
+}
+
+impl From<&ServiceEntryRepr> for ServiceKey {
+    fn from(repr: &ServiceEntryRepr) -> Self {
+        ServiceKey::new(repr.service_id, repr.instance_id, repr.major_version)
+    }
+}
+
+impl From<&EventGroupEntryRepr> for ServiceKey {
+    fn from(repr: &EventGroupEntryRepr) -> Self {
+        ServiceKey::new(repr.service_id, repr.instance_id, repr.major_version)
+    }
+}
+
+/// Identifies one eventgroup of one service instance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EventGroupKey {
+    /// The service the eventgroup belongs to
+    pub service: ServiceKey,
+    /// EventGroup ID
+    pub eventgroup_id: u16,
+}
+
+impl EventGroupKey {
+    /// Create a new eventgroup key.
+    ///
+    /// # Parameters
+    /// * `service` - The service the eventgroup belongs to
+    /// * `eventgroup_id` - EventGroup ID
+    pub fn new(service: ServiceKey, eventgroup_id: u16) -> Self {
+        EventGroupKey { service, eventgroup_id }
+    }
+}
+
+impl From<&EventGroupEntryRepr> for EventGroupKey {
+    fn from(repr: &EventGroupEntryRepr) -> Self {
+        EventGroupKey::new(ServiceKey::from(repr), repr.eventgroup_id)
+    }
 }
@@ -61,6 +61,65 @@ pub enum Error {
     /// - Option length field exceeds remaining buffer
     LengthOverflow,
 
+    /// Entry is well-formed but semantically invalid for its type.
+    ///
+    /// This occurs when:
+    /// - A FindService entry has a zero TTL or references options
+    /// - An OfferService entry uses a wildcard service or instance ID
+    InvalidEntry,
+
+    /// Wildcard version in an entry that requires a concrete version.
+    ///
+    /// OfferService entries must not use major version 0xFF or minor
+    /// version 0xFFFFFFFF, which are only meaningful in FindService queries.
+    InvalidVersion,
+
+    /// Reserved bits are set where the specification requires zero.
+    ///
+    /// Carries the index of the offending option within the options array.
+    ReservedNotZero(usize),
+
+    /// An entry that must reference an endpoint option doesn't.
+    ///
+    /// A Subscribe has to tell the server where to deliver events, so it
+    /// needs at least one endpoint or multicast option.
+    MissingEndpoint,
+
+    /// An option is not referenced by any entry's option runs.
+    ///
+    /// Carries the index of the first unreferenced option.
+    OrphanOption(usize),
+
+    /// An address is outside the range its option type requires.
+    ///
+    /// Multicast options must carry an IPv4 address in 224.0.0.0/4 or an
+    /// IPv6 address in ff00::/8.
+    InvalidAddress,
+
+    /// A message exceeds the caller's parse budget.
+    ///
+    /// Returned by `Repr::parse_within` when a message holds more entries,
+    /// options or configuration entries than the `Budget` allows.
+    BudgetExceeded,
+
+    /// An entry references an option that was never added.
+    ///
+    /// Returned by `MessageBuilder::finish` when an option run points past
+    /// the builder's options, e.g. because its `OptionRef` came from a
+    /// different builder.
+    DanglingOptionRef,
+
+    /// A TTL doesn't fit the 24-bit TTL field.
+    ///
+    /// Returned by `Ttl::from_seconds` for values above 0xFFFFFF.
+    TtlOutOfRange,
+
+    /// The reserved field of the SD header is not zero.
+    ///
+    /// Returned by `Packet::check_all`; the 24 bits after the flags byte
+    /// must be sent as zero.
+    HeaderReservedNotZero,
+
     /// Invalid configuration entry format.
     ///
     /// Configuration entries must follow DNS-SD TXT record format.
@@ -108,6 +167,84 @@ pub enum ConfigError {
     ///
     /// Configuration strings must be valid UTF-8.
     InvalidUtf8,
+
+    /// More entries than the caller's fixed-size storage can hold.
+    TooManyEntries,
+
+    /// A value doesn't have the format its key requires.
+    ///
+    /// Returned by the typed accessors such as `ConfigurationOption::get_u16`
+    /// when the value is missing or not a number in range.
+    InvalidValue,
+}
+
+impl Error {
+    /// Get a stable numeric code for this error, for FFI callers.
+    ///
+    /// The upper 16 bits identify the variant, the lower 16 bits carry its
+    /// payload: the offending byte, the option index (saturated at 0xFFFF),
+    /// or the `ConfigError::code`. These assignments are stable; new
+    /// variants only ever get new numbers.
+    ///
+    /// | Variant | Code |
+    /// |---------|------|
+    /// | `BufferTooShort` | `0x0001_0000` |
+    /// | `InvalidEntryType(t)` | `0x0002_0000 \| t` |
+    /// | `InvalidOptionType(t)` | `0x0003_0000 \| t` |
+    /// | `InvalidProtocol(p)` | `0x0004_0000 \| p` |
+    /// | `LengthOverflow` | `0x0005_0000` |
+    /// | `InvalidEntry` | `0x0006_0000` |
+    /// | `InvalidVersion` | `0x0007_0000` |
+    /// | `ReservedNotZero(i)` | `0x0008_0000 \| i` |
+    /// | `MissingEndpoint` | `0x0009_0000` |
+    /// | `OrphanOption(i)` | `0x000A_0000 \| i` |
+    /// | `InvalidAddress` | `0x000B_0000` |
+    /// | `BudgetExceeded` | `0x000C_0000` |
+    /// | `ConfigurationError(e)` | `0x000D_0000 \| e.code()` |
+    /// | `DanglingOptionRef` | `0x000E_0000` |
+    /// | `TtlOutOfRange` | `0x000F_0000` |
+    /// | `HeaderReservedNotZero` | `0x0010_0000` |
+    pub fn code(&self) -> u32 {
+        let index = |i: usize| i.min(0xFFFF) as u32;
+        let (variant, payload) = match *self {
+            Error::BufferTooShort => (0x01, 0),
+            Error::InvalidEntryType(t) => (0x02, t as u32),
+            Error::InvalidOptionType(t) => (0x03, t as u32),
+            Error::InvalidProtocol(p) => (0x04, p as u32),
+            Error::LengthOverflow => (0x05, 0),
+            Error::InvalidEntry => (0x06, 0),
+            Error::InvalidVersion => (0x07, 0),
+            Error::ReservedNotZero(i) => (0x08, index(i)),
+            Error::MissingEndpoint => (0x09, 0),
+            Error::OrphanOption(i) => (0x0A, index(i)),
+            Error::InvalidAddress => (0x0B, 0),
+            Error::BudgetExceeded => (0x0C, 0),
+            Error::ConfigurationError(e) => (0x0D, e.code()),
+            Error::DanglingOptionRef => (0x0E, 0),
+            Error::TtlOutOfRange => (0x0F, 0),
+            Error::HeaderReservedNotZero => (0x10, 0),
+        };
+        (variant << 16) | payload
+    }
+}
+
+impl ConfigError {
+    /// Get a stable numeric code for this error, for FFI callers.
+    ///
+    /// Codes are assigned in declaration order starting at 1 (`InvalidKey`)
+    /// through 8 (`InvalidValue`) and are stable.
+    pub fn code(&self) -> u32 {
+        match self {
+            ConfigError::InvalidKey => 1,
+            ConfigError::KeyStartsWithEquals => 2,
+            ConfigError::UnexpectedEnd => 3,
+            ConfigError::LengthOverflow => 4,
+            ConfigError::BufferTooSmall => 5,
+            ConfigError::InvalidUtf8 => 6,
+            ConfigError::TooManyEntries => 7,
+            ConfigError::InvalidValue => 8,
+        }
+    }
 }
 
 impl From<ConfigError> for Error {
@@ -124,11 +261,23 @@ impl core::fmt::Display for Error {
             Error::InvalidOptionType(t) => write!(f, "invalid option type: 0x{:02x}", t),
             Error::InvalidProtocol(p) => write!(f, "invalid transport protocol: 0x{:02x}", p),
             Error::LengthOverflow => write!(f, "length field overflow"),
+            Error::InvalidEntry => write!(f, "semantically invalid entry"),
+            Error::InvalidVersion => write!(f, "wildcard version where a concrete version is required"),
+            Error::ReservedNotZero(i) => write!(f, "reserved bits set in option {}", i),
+            Error::MissingEndpoint => write!(f, "entry references no endpoint option"),
+            Error::OrphanOption(i) => write!(f, "option {} is not referenced by any entry", i),
+            Error::InvalidAddress => write!(f, "address out of range for option type"),
+            Error::BudgetExceeded => write!(f, "parse budget exceeded"),
+            Error::DanglingOptionRef => write!(f, "entry references an option that was never added"),
+            Error::TtlOutOfRange => write!(f, "TTL exceeds 24 bits"),
+            Error::HeaderReservedNotZero => write!(f, "reserved header field not zero"),
             Error::ConfigurationError(e) => write!(f, "configuration error: {}", e),
         }
     }
 }
 
+impl core::error::Error for Error {}
+
 impl core::fmt::Display for ConfigError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
@@ -138,6 +287,8 @@ impl core::fmt::Display for ConfigError {
             ConfigError::LengthOverflow => write!(f, "length field overflow"),
             ConfigError::BufferTooSmall => write!(f, "buffer too small"),
             ConfigError::InvalidUtf8 => write!(f, "invalid UTF-8"),
+            ConfigError::TooManyEntries => write!(f, "too many entries"),
+            ConfigError::InvalidValue => write!(f, "invalid value"),
         }
     }
 }
@@ -153,6 +304,22 @@ mod tests {
         assert_eq!(format!("{}", Error::InvalidOptionType(0xAB)), "invalid option type: 0xab");
         assert_eq!(format!("{}", Error::InvalidProtocol(0x99)), "invalid transport protocol: 0x99");
         assert_eq!(format!("{}", Error::LengthOverflow), "length field overflow");
+        assert_eq!(format!("{}", Error::InvalidEntry), "semantically invalid entry");
+        assert_eq!(
+            format!("{}", Error::InvalidVersion),
+            "wildcard version where a concrete version is required"
+        );
+        assert_eq!(format!("{}", Error::ReservedNotZero(1)), "reserved bits set in option 1");
+        assert_eq!(format!("{}", Error::MissingEndpoint), "entry references no endpoint option");
+        assert_eq!(format!("{}", Error::OrphanOption(2)), "option 2 is not referenced by any entry");
+        assert_eq!(format!("{}", Error::InvalidAddress), "address out of range for option type");
+        assert_eq!(format!("{}", Error::BudgetExceeded), "parse budget exceeded");
+        assert_eq!(
+            format!("{}", Error::DanglingOptionRef),
+            "entry references an option that was never added"
+        );
+        assert_eq!(format!("{}", Error::TtlOutOfRange), "TTL exceeds 24 bits");
+        assert_eq!(format!("{}", Error::HeaderReservedNotZero), "reserved header field not zero");
     }
 
     #[test]
@@ -163,6 +330,25 @@ mod tests {
         assert_eq!(format!("{}", ConfigError::LengthOverflow), "length field overflow");
         assert_eq!(format!("{}", ConfigError::BufferTooSmall), "buffer too small");
         assert_eq!(format!("{}", ConfigError::InvalidUtf8), "invalid UTF-8");
+        assert_eq!(format!("{}", ConfigError::TooManyEntries), "too many entries");
+        assert_eq!(format!("{}", ConfigError::InvalidValue), "invalid value");
+    }
+
+    #[test]
+    fn test_error_code() {
+        assert_eq!(Error::BufferTooShort.code(), 0x0001_0000);
+        assert_eq!(Error::InvalidEntryType(0x42).code(), 0x0002_0042);
+        assert_eq!(Error::InvalidProtocol(0x99).code(), 0x0004_0099);
+        assert_eq!(Error::ReservedNotZero(3).code(), 0x0008_0003);
+        assert_eq!(Error::OrphanOption(1 << 20).code(), 0x000A_FFFF);
+        assert_eq!(Error::BudgetExceeded.code(), 0x000C_0000);
+        assert_eq!(Error::from(ConfigError::InvalidUtf8).code(), 0x000D_0006);
+        assert_eq!(Error::DanglingOptionRef.code(), 0x000E_0000);
+        assert_eq!(Error::TtlOutOfRange.code(), 0x000F_0000);
+        assert_eq!(Error::HeaderReservedNotZero.code(), 0x0010_0000);
+        assert_eq!(ConfigError::InvalidKey.code(), 1);
+        assert_eq!(ConfigError::TooManyEntries.code(), 7);
+        assert_eq!(ConfigError::InvalidValue.code(), 8);
     }
 
     #[test]
@@ -181,6 +367,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_error_clone_copy() {
         let err = Error::BufferTooShort;
         let err2 = err;
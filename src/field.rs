@@ -98,6 +98,63 @@ pub mod entries {
         let start = OPTIONS_LENGTH(entries_len).end;
         start..(start + options_len)
     }
+
+    /// Checked variant of `ENTRIES_ARRAY` for untrusted lengths.
+    ///
+    /// # Parameters
+    ///
+    /// * `length` - The length of the entries array in bytes
+    ///
+    /// # Returns
+    ///
+    /// Field range covering the entries array, or `None` if the end overflows
+    pub const fn try_entries_array(length: usize) -> Option<Field> {
+        match MIN_HEADER_LEN.checked_add(length) {
+            Some(end) => Some(MIN_HEADER_LEN..end),
+            None => None,
+        }
+    }
+
+    /// Checked variant of `OPTIONS_LENGTH` for untrusted lengths.
+    ///
+    /// # Parameters
+    ///
+    /// * `entries_len` - The length of the entries array in bytes
+    ///
+    /// # Returns
+    ///
+    /// Field range for the options length field, or `None` if it overflows
+    pub const fn try_options_length(entries_len: usize) -> Option<Field> {
+        let start = match try_entries_array(entries_len) {
+            Some(entries) => entries.end,
+            None => return None,
+        };
+        match start.checked_add(4) {
+            Some(end) => Some(start..end),
+            None => None,
+        }
+    }
+
+    /// Checked variant of `OPTIONS_ARRAY` for untrusted lengths.
+    ///
+    /// # Parameters
+    ///
+    /// * `entries_len` - The length of the entries array in bytes
+    /// * `options_len` - The length of the options array in bytes
+    ///
+    /// # Returns
+    ///
+    /// Field range covering the options array, or `None` if it overflows
+    pub const fn try_options_array(entries_len: usize, options_len: usize) -> Option<Field> {
+        let start = match try_options_length(entries_len) {
+            Some(field) => field.end,
+            None => return None,
+        };
+        match start.checked_add(options_len) {
+            Some(end) => Some(start..end),
+            None => None,
+        }
+    }
 }
 
 /// Option-specific field offsets (relative within an option structure).
@@ -361,4 +418,20 @@ pub mod ipv6_sd_endpoint_option {
     
     /// Port number (2 bytes at offset 18-19).
     pub const PORT: Field = 18..20;
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_entries_fields() {
+        assert_eq!(entries::try_entries_array(16), Some(entries::ENTRIES_ARRAY(16)));
+        assert_eq!(entries::try_options_length(16), Some(entries::OPTIONS_LENGTH(16)));
+        assert_eq!(entries::try_options_array(16, 12), Some(entries::OPTIONS_ARRAY(16, 12)));
+
+        assert_eq!(entries::try_entries_array(usize::MAX), None);
+        assert_eq!(entries::try_options_length(usize::MAX - 8), None);
+        assert_eq!(entries::try_options_array(0, usize::MAX - 11), None);
+    }
+}
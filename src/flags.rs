@@ -0,0 +1,168 @@
+//! The flags byte of a SOME/IP-SD message header.
+//!
+//! Bit 7 is the reboot flag and bit 6 the unicast flag. Bits 0-5 are reserved
+//! by the base specification; earlier revisions used bit 5 as the explicit
+//! initial data control flag. All bits are accessible through `Flags::bit`
+//! for forward compatibility with newer or vendor-specific flag bits.
+
+/// Reboot flag bit (set after startup until the session ID wraps).
+pub const REBOOT: u8 = 0x80;
+
+/// Unicast flag bit (the sender supports receiving unicast messages).
+pub const UNICAST: u8 = 0x40;
+
+/// Explicit initial data control flag bit (bit 5, earlier revisions only).
+pub const EXPLICIT_INITIAL_DATA_CONTROL: u8 = 0x20;
+
+/// Mask of the bits the base specification reserves (bits 0-5).
+pub const RESERVED_MASK: u8 = 0x3F;
+
+/// The 8-bit flags field of a SOME/IP-SD header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Flags(u8);
+
+/// Alias of `Flags` under the SD-prefixed name.
+pub type SdFlags = Flags;
+
+impl Flags {
+    /// Creates flags with every bit cleared.
+    pub fn new() -> Self {
+        Flags(0)
+    }
+
+    /// Creates from raw u8 value.
+    ///
+    /// # Parameters
+    ///
+    /// * `value` - Raw flags byte from wire format
+    pub fn from_u8(value: u8) -> Self {
+        Flags(value)
+    }
+
+    /// Converts to raw u8 value for wire format.
+    pub fn as_u8(&self) -> u8 {
+        self.0
+    }
+
+    /// Returns true if the reboot flag (bit 7) is set.
+    pub fn reboot(&self) -> bool {
+        self.0 & REBOOT != 0
+    }
+
+    /// Sets or clears the reboot flag (bit 7).
+    ///
+    /// # Parameters
+    ///
+    /// * `value` - New flag state
+    pub fn set_reboot(&mut self, value: bool) {
+        self.set_bit(7, value);
+    }
+
+    /// Returns true if the unicast flag (bit 6) is set.
+    pub fn unicast(&self) -> bool {
+        self.0 & UNICAST != 0
+    }
+
+    /// Sets or clears the unicast flag (bit 6).
+    ///
+    /// # Parameters
+    ///
+    /// * `value` - New flag state
+    pub fn set_unicast(&mut self, value: bool) {
+        self.set_bit(6, value);
+    }
+
+    /// Returns true if the explicit initial data control flag (bit 5) is set.
+    ///
+    /// Only meaningful to peers implementing the earlier revisions that
+    /// defined it; the base specification reserves this bit.
+    pub fn explicit_initial_data_control(&self) -> bool {
+        self.0 & EXPLICIT_INITIAL_DATA_CONTROL != 0
+    }
+
+    /// Sets or clears the explicit initial data control flag (bit 5).
+    ///
+    /// # Parameters
+    ///
+    /// * `value` - New flag state
+    pub fn set_explicit_initial_data_control(&mut self, value: bool) {
+        self.set_bit(5, value);
+    }
+
+    /// Returns the reserved bits (0-5), including bit 5.
+    ///
+    /// Setting the reboot or unicast flag leaves these bits untouched, so
+    /// they survive a parse/modify/emit round trip.
+    pub fn reserved(&self) -> u8 {
+        self.0 & RESERVED_MASK
+    }
+
+    /// Reads an arbitrary flag bit.
+    ///
+    /// # Parameters
+    ///
+    /// * `n` - Bit number, 0 (least significant) to 7 (reboot)
+    ///
+    /// # Returns
+    ///
+    /// Whether the bit is set; always false for `n` above 7
+    pub fn bit(&self, n: u8) -> bool {
+        n < 8 && self.0 & (1 << n) != 0
+    }
+
+    /// Sets or clears an arbitrary flag bit.
+    ///
+    /// # Parameters
+    ///
+    /// * `n` - Bit number, 0 (least significant) to 7 (reboot); ignored above 7
+    /// * `value` - New bit state
+    pub fn set_bit(&mut self, n: u8, value: bool) {
+        if n >= 8 {
+            return;
+        }
+        if value {
+            self.0 |= 1 << n;
+        } else {
+            self.0 &= !(1 << n);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flags() {
+        let mut flags = Flags::from_u8(0xC0);
+        assert!(flags.reboot());
+        assert!(flags.unicast());
+        assert!(!flags.explicit_initial_data_control());
+
+        flags.set_reboot(false);
+        flags.set_explicit_initial_data_control(true);
+        assert_eq!(flags.as_u8(), 0x60);
+
+        flags.set_bit(0, true);
+        assert!(flags.bit(0));
+        assert!(flags.bit(5));
+        assert!(!flags.bit(7));
+        assert_eq!(flags.as_u8(), 0x61);
+
+        flags.set_bit(8, true);
+        assert!(!flags.bit(8));
+        assert_eq!(flags.as_u8(), 0x61);
+        assert_eq!(Flags::default(), Flags::new());
+    }
+
+    #[test]
+    fn test_flags_reserved_round_trip() {
+        let mut flags = SdFlags::from_u8(0x2A);
+        assert_eq!(flags.reserved(), 0x2A);
+        flags.set_reboot(true);
+        flags.set_unicast(true);
+        assert_eq!((flags.as_u8(), flags.reserved()), (0xEA, 0x2A));
+        flags.set_reboot(false);
+        assert_eq!(flags.as_u8(), 0x6A);
+    }
+}
@@ -0,0 +1,86 @@
+//! Length-prefixed framing for carrying SD messages over a byte stream.
+//!
+//! SOME/IP-SD is UDP-based, but tunneling setups sometimes carry it over TCP.
+//! Each frame is a 4-byte big-endian length followed by that many bytes of
+//! SD message.
+
+use crate::error::Error;
+use byteorder::{ByteOrder, NetworkEndian};
+
+/// Result type alias using the crate's Error type.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Size of the length prefix in bytes.
+pub const PREFIX_LEN: usize = 4;
+
+/// Write `msg` into `out` prefixed with its length.
+///
+/// # Parameters
+/// * `msg` - The SD message to frame
+/// * `out` - Buffer receiving the frame
+///
+/// # Returns
+/// * `Ok(usize)` - Number of bytes written (`4 + msg.len()`)
+/// * `Err(Error::BufferTooShort)` if `out` can't hold the frame
+/// * `Err(Error::LengthOverflow)` if `msg` is longer than `u32::MAX` bytes
+pub fn write_framed(msg: &[u8], out: &mut [u8]) -> Result<usize> {
+    let len = u32::try_from(msg.len()).map_err(|_| Error::LengthOverflow)?;
+    let total = PREFIX_LEN + msg.len();
+    if out.len() < total {
+        return Err(Error::BufferTooShort);
+    }
+    NetworkEndian::write_u32(&mut out[..PREFIX_LEN], len);
+    out[PREFIX_LEN..total].copy_from_slice(msg);
+    Ok(total)
+}
+
+/// Read one frame from the start of `buf`.
+///
+/// # Parameters
+/// * `buf` - Stream data starting at a frame boundary
+///
+/// # Returns
+/// * `Ok((&[u8], usize))` - The framed message and the total bytes consumed
+/// * `Err(Error::BufferTooShort)` if the frame is incomplete; read more data
+///   and retry
+pub fn read_framed(buf: &[u8]) -> Result<(&[u8], usize)> {
+    if buf.len() < PREFIX_LEN {
+        return Err(Error::BufferTooShort);
+    }
+    let len = NetworkEndian::read_u32(&buf[..PREFIX_LEN]) as usize;
+    let total = PREFIX_LEN.checked_add(len).ok_or(Error::LengthOverflow)?;
+    if buf.len() < total {
+        return Err(Error::BufferTooShort);
+    }
+    Ok((&buf[PREFIX_LEN..total], total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_framing_roundtrip() {
+        let msg = [0xC0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let mut stream = [0u8; 40];
+        let first = write_framed(&msg, &mut stream).unwrap();
+        assert_eq!(first, 16);
+        assert_eq!(stream[..4], [0, 0, 0, 12]);
+        let second = write_framed(&msg[..8], &mut stream[first..]).unwrap();
+
+        let (read, consumed) = read_framed(&stream).unwrap();
+        assert_eq!((read, consumed), (&msg[..], first));
+        let (read, consumed) = read_framed(&stream[first..]).unwrap();
+        assert_eq!((read, consumed), (&msg[..8], second));
+
+        assert_eq!(write_framed(&msg, &mut stream[..15]), Err(Error::BufferTooShort));
+    }
+
+    #[test]
+    fn test_framing_incomplete() {
+        let stream = [0, 0, 0, 12, 0xC0, 0, 0];
+        assert_eq!(read_framed(&stream[..3]), Err(Error::BufferTooShort));
+        assert_eq!(read_framed(&stream), Err(Error::BufferTooShort));
+        assert_eq!(read_framed(&[0, 0, 0, 0]), Ok((&[][..], 4)));
+    }
+}
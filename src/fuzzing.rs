@@ -0,0 +1,227 @@
+//! `arbitrary::Arbitrary` implementations for the Repr types.
+//!
+//! Generated values are constrained to what the wire format can carry
+//! (valid enum variants, 24-bit TTLs, 4-bit counters and option counts), so a
+//! fuzzer can emit them, parse them back, and assert round-trip equality.
+
+use crate::entries::{EntryType, EventGroupEntryRepr, NumberOfOptions, ReservedAndCounter, ServiceEntryRepr};
+use crate::options::{
+    IPv4EndpointOptionRepr, IPv4MulticastOptionRepr, IPv4SdEndpointOptionRepr, IPv6EndpointOptionRepr,
+    IPv6MulticastOptionRepr, IPv6SdEndpointOptionRepr, LoadBalancingOptionRepr, TransportProtocol,
+};
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+/// Largest value representable by the 24-bit TTL field.
+const MAX_TTL: u32 = 0xFFFFFF;
+
+impl<'a> Arbitrary<'a> for EntryType {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(*u.choose(&[
+            EntryType::FindService,
+            EntryType::OfferService,
+            EntryType::Subscribe,
+            EntryType::SubscribeAck,
+        ])?)
+    }
+}
+
+impl<'a> Arbitrary<'a> for TransportProtocol {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(*u.choose(&[TransportProtocol::TCP, TransportProtocol::UDP])?)
+    }
+}
+
+impl<'a> Arbitrary<'a> for NumberOfOptions {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(NumberOfOptions::from_options(u.int_in_range(0..=15)?, u.int_in_range(0..=15)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for ReservedAndCounter {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(ReservedAndCounter::from_counter(u.int_in_range(0..=15)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for ServiceEntryRepr {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(ServiceEntryRepr {
+            entry_type: *u.choose(&[EntryType::FindService, EntryType::OfferService])?,
+            index_first_option_run: u.arbitrary()?,
+            index_second_option_run: u.arbitrary()?,
+            number_of_options: u.arbitrary()?,
+            service_id: u.arbitrary()?,
+            instance_id: u.arbitrary()?,
+            major_version: u.arbitrary()?,
+            ttl: u.int_in_range(0..=MAX_TTL)?,
+            minor_version: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for EventGroupEntryRepr {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(EventGroupEntryRepr {
+            entry_type: *u.choose(&[EntryType::Subscribe, EntryType::SubscribeAck])?,
+            index_first_option_run: u.arbitrary()?,
+            index_second_option_run: u.arbitrary()?,
+            number_of_options: u.arbitrary()?,
+            service_id: u.arbitrary()?,
+            instance_id: u.arbitrary()?,
+            major_version: u.arbitrary()?,
+            ttl: u.int_in_range(0..=MAX_TTL)?,
+            reserved_and_counter: u.arbitrary()?,
+            eventgroup_id: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for IPv4EndpointOptionRepr {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(IPv4EndpointOptionRepr {
+            ipv4_address: u.arbitrary()?,
+            protocol: u.arbitrary()?,
+            port: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for IPv6EndpointOptionRepr {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(IPv6EndpointOptionRepr {
+            ipv6_address: u.arbitrary()?,
+            protocol: u.arbitrary()?,
+            port: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for IPv4MulticastOptionRepr {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        // Keep the group address in 224.0.0.0/4
+        let mut ipv4_address: [u8; 4] = u.arbitrary()?;
+        ipv4_address[0] = u.int_in_range(224..=239)?;
+        Ok(IPv4MulticastOptionRepr {
+            ipv4_address,
+            port: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for IPv6MulticastOptionRepr {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        // Keep the group address in ff00::/8
+        let mut ipv6_address: [u8; 16] = u.arbitrary()?;
+        ipv6_address[0] = 0xFF;
+        Ok(IPv6MulticastOptionRepr {
+            ipv6_address,
+            port: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for IPv4SdEndpointOptionRepr {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(IPv4SdEndpointOptionRepr {
+            ipv4_address: u.arbitrary()?,
+            protocol: u.arbitrary()?,
+            port: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for IPv6SdEndpointOptionRepr {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(IPv6SdEndpointOptionRepr {
+            ipv6_address: u.arbitrary()?,
+            protocol: u.arbitrary()?,
+            port: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for LoadBalancingOptionRepr {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(LoadBalancingOptionRepr {
+            priority: u.arbitrary()?,
+            weight: u.arbitrary()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entries::{EventGroupEntry, ServiceEntry};
+    use crate::options::{
+        IPv4EndpointOption, IPv4MulticastOption, IPv4SdEndpointOption, IPv6EndpointOption, IPv6MulticastOption,
+        IPv6SdEndpointOption, LoadBalancingOption,
+    };
+
+    const SEED: [u8; 256] = {
+        let mut seed = [0u8; 256];
+        let mut i = 0;
+        while i < seed.len() {
+            seed[i] = (i as u8).wrapping_mul(167).wrapping_add(13);
+            i += 1;
+        }
+        seed
+    };
+
+    #[test]
+    fn test_arbitrary_entries_roundtrip() {
+        let mut u = Unstructured::new(&SEED);
+        for _ in 0..4 {
+            let repr = ServiceEntryRepr::arbitrary(&mut u).unwrap();
+            let mut buf = [0u8; 16];
+            repr.emit(&mut ServiceEntry::new_unchecked(&mut buf[..]));
+            assert_eq!(ServiceEntryRepr::parse(&ServiceEntry::new_unchecked(&buf[..])), Ok(repr));
+
+            let repr = EventGroupEntryRepr::arbitrary(&mut u).unwrap();
+            let mut buf = [0u8; 16];
+            repr.emit(&mut EventGroupEntry::new_unchecked(&mut buf[..]));
+            assert_eq!(EventGroupEntryRepr::parse(&EventGroupEntry::new_unchecked(&buf[..])), Ok(repr));
+        }
+    }
+
+    #[test]
+    fn test_arbitrary_options_roundtrip() {
+        let mut u = Unstructured::new(&SEED);
+        for _ in 0..4 {
+            let repr = IPv4EndpointOptionRepr::arbitrary(&mut u).unwrap();
+            let mut buf = [0u8; 12];
+            repr.emit(&mut buf);
+            assert_eq!(IPv4EndpointOptionRepr::parse(&IPv4EndpointOption::new_unchecked(&buf[..])), Ok(repr));
+
+            let repr = IPv6EndpointOptionRepr::arbitrary(&mut u).unwrap();
+            let mut buf = [0u8; 24];
+            repr.emit(&mut buf);
+            assert_eq!(IPv6EndpointOptionRepr::parse(&IPv6EndpointOption::new_unchecked(&buf[..])), Ok(repr));
+
+            let repr = LoadBalancingOptionRepr::arbitrary(&mut u).unwrap();
+            let mut buf = [0u8; 8];
+            repr.emit(&mut buf);
+            assert_eq!(LoadBalancingOptionRepr::parse(&LoadBalancingOption::new_unchecked(&buf[..])), Ok(repr));
+
+            let repr = IPv4MulticastOptionRepr::arbitrary(&mut u).unwrap();
+            let mut buf = [0u8; 12];
+            repr.emit(&mut buf);
+            assert_eq!(IPv4MulticastOptionRepr::parse(&IPv4MulticastOption::new_unchecked(&buf[..])), Ok(repr));
+
+            let repr = IPv6MulticastOptionRepr::arbitrary(&mut u).unwrap();
+            let mut buf = [0u8; 24];
+            repr.emit(&mut buf);
+            assert_eq!(IPv6MulticastOptionRepr::parse(&IPv6MulticastOption::new_unchecked(&buf[..])), Ok(repr));
+
+            let repr = IPv4SdEndpointOptionRepr::arbitrary(&mut u).unwrap();
+            let mut buf = [0u8; 12];
+            repr.emit(&mut buf);
+            assert_eq!(IPv4SdEndpointOptionRepr::parse(&IPv4SdEndpointOption::new_unchecked(&buf[..])), Ok(repr));
+
+            let repr = IPv6SdEndpointOptionRepr::arbitrary(&mut u).unwrap();
+            let mut buf = [0u8; 24];
+            repr.emit(&mut buf);
+            assert_eq!(IPv6SdEndpointOptionRepr::parse(&IPv6SdEndpointOption::new_unchecked(&buf[..])), Ok(repr));
+        }
+    }
+}
@@ -1,4 +1,4 @@
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 #![warn(missing_docs)]
 
 //! # SOME/IP-SD-wire
@@ -14,6 +14,9 @@
 //! - Support for all SOME/IP-SD message types
 //! - Clean enum-based API for entry and option types
 //! - Wire format using smoltcp-inspired zero-copy pattern
+//! - Optional `alloc`/`std` features for owned message storage
+//! - Optional `bench-util` feature exposing representative benchmark workloads
+//! - Optional `arbitrary` feature implementing `arbitrary::Arbitrary` for the Repr types
 //!
 //! ## Architecture
 //!
@@ -22,12 +25,30 @@
 //! - `repr` - High-level representation for parsing/emitting
 //! - `entries` - Zero-copy wrappers for service/eventgroup entries
 //! - `options` - Zero-copy wrappers for various option types
+//! - `builder` - Handle-based builder for complete messages
 //! - `config` - DNS-SD TXT record configuration options
+//! - `framing` - Length-prefixed framing for SD over TCP
+//! - `registry` - Offered-service cache driven by decoded messages
+//! - `flags` - Header flags byte
 //! - `field` - Field offset definitions
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+/// Representative decode/encode workloads for benchmarks.
+#[cfg(feature = "bench-util")]
+pub mod bench_util;
+
+/// Handle-based builder for complete SOME/IP-SD messages.
+pub mod builder;
+
 /// DNS-SD TXT record style configuration options for SOME/IP-SD.
 pub mod config;
 
+/// Reading SD messages from `std::io` streams.
+#[cfg(feature = "std")]
+pub mod decode;
+
 /// Service and EventGroup entry types with zero-copy wrappers.
 pub mod entries;
 
@@ -37,18 +58,31 @@ pub mod error;
 /// Field offset definitions for all wire format structures.
 pub mod field;
 
+/// Typed access to the header flags byte.
+pub mod flags;
+
+/// Length-prefixed framing for SD messages carried over a byte stream.
+pub mod framing;
+
 /// SOME/IP-SD option types (IPv4/IPv6 Endpoint, LoadBalancing, etc.).
 pub mod options;
 
 /// Zero-copy packet wrapper for SOME/IP-SD messages.
 pub mod packet;
 
+/// Bounded cache of offered services built from decoded messages.
+pub mod registry;
+
 /// High-level representation for parse/emit operations.
 pub mod repr;
 
 /// Prelude module for convenient imports.
 pub mod prelude;
 
+/// `arbitrary::Arbitrary` implementations for fuzzing the encode path.
+#[cfg(feature = "arbitrary")]
+mod fuzzing;
+
 #[cfg(test)]
 mod zero_cost_tests {
     use super::*;
@@ -120,7 +154,8 @@ mod zero_cost_tests {
 
 // Compile-time assertion that we don't link against an allocator in no_std mode
 // This will fail to compile if somehow an allocator is required
-#[cfg(not(test))]
+// (the `alloc` feature opts into allocation explicitly)
+#[cfg(not(any(test, feature = "alloc")))]
 unsafe extern "C" {
     // This symbol should NOT exist - if it's required, compilation will fail with "undefined reference"
     // Remove this if you ever need to add allocation support
@@ -1,17 +1,24 @@
-/// Option types for SOME/IP-SD
-///
-/// This module provides zero-copy wrappers around various option types
-/// used in SOME/IP Service Discovery messages. Options provide additional
-/// information like endpoint addresses, load balancing parameters, and
-/// configuration strings.
+//! Option types for SOME/IP-SD
+//!
+//! This module provides zero-copy wrappers around various option types
+//! used in SOME/IP Service Discovery messages. Options provide additional
+//! information like endpoint addresses, load balancing parameters, and
+//! configuration strings.
 
 use crate::error::Error;
 use crate::field;
 use byteorder::{ByteOrder, NetworkEndian};
+use core::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
 
 /// Result type alias using the crate's Error type.
 pub type Result<T> = core::result::Result<T, Error>;
 
+/// Well-known UDP port used for SOME/IP-SD traffic.
+///
+/// The SD multicast group address, by contrast, has no standard value and is
+/// deployment-specific, so it must be taken from the system configuration.
+pub const DEFAULT_SD_PORT: u16 = 30490;
+
 /// Option type enumeration for SOME/IP-SD options.
 ///
 /// Defines the type field in option headers which determines how to
@@ -67,6 +74,20 @@ impl OptionType {
     pub fn as_u8(&self) -> u8 {
         *self as u8
     }
+
+    /// Get the option type's name for logging, e.g. "IPv4Endpoint".
+    pub fn name(&self) -> &'static str {
+        match self {
+            OptionType::Configuration => "Configuration",
+            OptionType::LoadBalancing => "LoadBalancing",
+            OptionType::IPv4Endpoint => "IPv4Endpoint",
+            OptionType::IPv6Endpoint => "IPv6Endpoint",
+            OptionType::IPv4Multicast => "IPv4Multicast",
+            OptionType::IPv6Multicast => "IPv6Multicast",
+            OptionType::IPv4SdEndpoint => "IPv4SdEndpoint",
+            OptionType::IPv6SdEndpoint => "IPv6SdEndpoint",
+        }
+    }
 }
 
 /// Transport protocol enumeration.
@@ -184,6 +205,12 @@ impl DiscardableFlag {
     }
 }
 
+impl Default for DiscardableFlag {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Zero-copy wrapper around Option header (4 bytes).
 ///
 /// All SOME/IP-SD options start with this 4-byte header containing
@@ -243,6 +270,16 @@ impl<T: AsRef<[u8]>> OptionHeader<T> {
         Ok(())
     }
 
+    /// Get a reference to the underlying buffer.
+    pub fn buffer(&self) -> &T {
+        &self.buffer
+    }
+
+    /// Consume the wrapper and return the underlying buffer.
+    pub fn into_buffer(self) -> T {
+        self.buffer
+    }
+
     /// Validate the option type field contains a known option type.
     ///
     /// # Returns
@@ -255,6 +292,22 @@ impl<T: AsRef<[u8]>> OptionHeader<T> {
             .ok_or(Error::InvalidOptionType(type_val))
     }
 
+    /// Validate the Length field of a fixed-size option.
+    ///
+    /// # Parameters
+    /// * `expected` - The Length value the option type requires (e.g. 9 for
+    ///   an IPv4 endpoint option)
+    ///
+    /// # Returns
+    /// * `Ok(())` if the Length field equals `expected`
+    /// * `Err(Error::LengthOverflow)` if it doesn't
+    pub fn check_length(&self, expected: u16) -> Result<()> {
+        if self.length() != expected {
+            return Err(Error::LengthOverflow);
+        }
+        Ok(())
+    }
+
     /// Get the Length field (2 bytes at offset 0-1, network byte order).
     ///
     /// # Returns
@@ -306,6 +359,184 @@ impl<T: AsRef<[u8]> + AsMut<[u8]>> OptionHeader<T> {
     }
 }
 
+/// Total wire size of the option starting at `buffer[0]`.
+///
+/// The header length field counts every byte after the Type field, so the
+/// option occupies `3 + length` bytes.
+///
+/// # Returns
+/// * `Ok(usize)` - Size of the option (header + payload) in bytes
+/// * `Err(Error::BufferTooShort)` if the 4-byte header doesn't fit
+/// * `Err(Error::LengthOverflow)` if the declared length is zero or runs past `buffer`
+pub(crate) fn option_wire_len(buffer: &[u8]) -> Result<usize> {
+    let header = OptionHeader::new_checked(buffer)?;
+    if header.length() == 0 {
+        return Err(Error::LengthOverflow);
+    }
+    let len = field::option_header::TYPE.end + header.length() as usize;
+    if len > buffer.len() {
+        return Err(Error::LengthOverflow);
+    }
+    Ok(len)
+}
+
+/// Get the byte offset of the option at `index` within an options array.
+///
+/// Walks the option headers from the start of the array, so the cost is
+/// linear in `index`. The option found at `index` is itself length-checked.
+///
+/// # Parameters
+/// * `options_array` - The options array of an SD message (e.g. `Repr::options`)
+/// * `index` - Zero-based option index, as used by entry option runs
+///
+/// # Returns
+/// * `Ok(usize)` - Byte offset where the option begins
+/// * `Err(Error::LengthOverflow)` if there aren't that many options or a header is malformed
+/// * `Err(Error::BufferTooShort)` if an option header is truncated
+pub fn offset_of(options_array: &[u8], index: usize) -> Result<usize> {
+    let mut pos = 0;
+    for _ in 0..index {
+        if pos >= options_array.len() {
+            return Err(Error::LengthOverflow);
+        }
+        pos += option_wire_len(&options_array[pos..])?;
+    }
+    if pos >= options_array.len() {
+        return Err(Error::LengthOverflow);
+    }
+    option_wire_len(&options_array[pos..])?;
+    Ok(pos)
+}
+
+/// Check that the reserved bits of every option header are zero.
+///
+/// Only the 7 bits sharing the discardable byte are checked; type-specific
+/// reserved fields are left to the per-option checks.
+///
+/// # Parameters
+/// * `options_array` - The options array of an SD message (e.g. `Repr::options`)
+///
+/// # Returns
+/// * `Ok(())` if every option header is clean
+/// * `Err(Error::ReservedNotZero(index))` for the first option with reserved bits set
+/// * `Err(Error::LengthOverflow)` / `Err(Error::BufferTooShort)` if the array is malformed
+pub fn check_all_reserved(options_array: &[u8]) -> Result<()> {
+    let mut pos = 0;
+    let mut index = 0;
+    while pos < options_array.len() {
+        let len = option_wire_len(&options_array[pos..])?;
+        if options_array[pos + field::option_header::DISCARDABLE_FLAG_AND_RESERVED.start] & 0x7F != 0 {
+            return Err(Error::ReservedNotZero(index));
+        }
+        pos += len;
+        index += 1;
+    }
+    Ok(())
+}
+
+/// Get the type and address length of an endpoint, multicast or SD endpoint
+/// option, after checking its header.
+fn endpoint_layout(buffer: &[u8]) -> Result<(OptionType, usize)> {
+    let type_byte = *buffer.get(field::option_header::TYPE.start).ok_or(Error::BufferTooShort)?;
+    let (option_type, address_len) = match OptionType::from_u8(type_byte) {
+        Some(t @ (OptionType::IPv4Endpoint | OptionType::IPv4Multicast | OptionType::IPv4SdEndpoint)) => (t, 4),
+        Some(t @ (OptionType::IPv6Endpoint | OptionType::IPv6Multicast | OptionType::IPv6SdEndpoint)) => (t, 16),
+        _ => return Err(Error::InvalidOptionType(type_byte)),
+    };
+    check_typed_option(buffer, option_type, 4 + address_len + 4)?;
+    Ok((option_type, address_len))
+}
+
+/// Check whether two options describe the same endpoint.
+///
+/// True only if both options have the same type, address, protocol and port;
+/// the discardable flag and reserved bits are ignored. Endpoints of different
+/// address families, or an endpoint and a multicast option, never match.
+///
+/// # Parameters
+/// * `a` - First option, header included
+/// * `b` - Second option, header included
+///
+/// # Returns
+/// * `Ok(bool)` - Whether both describe the same endpoint
+/// * `Err(Error::InvalidOptionType)` if either option isn't an endpoint,
+///   multicast or SD endpoint option
+/// * `Err(Error::BufferTooShort)` / `Err(Error::LengthOverflow)` if either is malformed
+pub fn same_logical_endpoint(a: &[u8], b: &[u8]) -> Result<bool> {
+    let (type_a, address_len) = endpoint_layout(a)?;
+    let (type_b, _) = endpoint_layout(b)?;
+    if type_a != type_b {
+        return Ok(false);
+    }
+    // Payload layout: address, reserved (1), protocol (1), port (2)
+    let address = 4..4 + address_len;
+    let protocol_and_port = 4 + address_len + 1..4 + address_len + 4;
+    Ok(a[address.clone()] == b[address] && a[protocol_and_port.clone()] == b[protocol_and_port])
+}
+
+/// Address, protocol, and port advertised by an SD endpoint option.
+///
+/// SD endpoint options (0x24/0x26) tell a peer where to send SOME/IP-SD
+/// traffic, as opposed to service endpoints which carry application traffic.
+/// Keeping them in a dedicated type avoids mixing the two up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndpointInfo {
+    /// IPv4 SD endpoint (option type 0x24).
+    IPv4 {
+        /// IPv4 address (4 bytes)
+        address: [u8; 4],
+        /// Transport protocol (TCP=0x06, UDP=0x11)
+        protocol: TransportProtocol,
+        /// Port number
+        port: u16,
+    },
+    /// IPv6 SD endpoint (option type 0x26).
+    IPv6 {
+        /// IPv6 address (16 bytes)
+        address: [u8; 16],
+        /// Transport protocol (TCP=0x06, UDP=0x11)
+        protocol: TransportProtocol,
+        /// Port number
+        port: u16,
+    },
+}
+
+impl EndpointInfo {
+    /// Get the port number of the SD endpoint.
+    pub fn port(&self) -> u16 {
+        match self {
+            EndpointInfo::IPv4 { port, .. } | EndpointInfo::IPv6 { port, .. } => *port,
+        }
+    }
+
+    /// Get the transport protocol of the SD endpoint.
+    pub fn protocol(&self) -> TransportProtocol {
+        match self {
+            EndpointInfo::IPv4 { protocol, .. } | EndpointInfo::IPv6 { protocol, .. } => *protocol,
+        }
+    }
+
+    /// Decode a complete option if it is an SD endpoint option.
+    ///
+    /// # Returns
+    /// * `Ok(Some(EndpointInfo))` for a well-formed IPv4/IPv6 SD endpoint option
+    /// * `Ok(None)` if the option is of any other type
+    /// * `Err(Error)` if the SD endpoint option is truncated, has a bad length
+    ///   field or a bad protocol
+    pub(crate) fn from_sd_option(option: &[u8]) -> Result<Option<Self>> {
+        let header = OptionHeader::new_checked(option)?;
+        match OptionType::from_u8(header.option_type()) {
+            Some(OptionType::IPv4SdEndpoint) => {
+                IPv4SdEndpointOptionRepr::parse(&IPv4SdEndpointOption::parse_from(option)?).map(|r| Some(r.into()))
+            }
+            Some(OptionType::IPv6SdEndpoint) => {
+                IPv6SdEndpointOptionRepr::parse(&IPv6SdEndpointOption::parse_from(option)?).map(|r| Some(r.into()))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
 /// Zero-copy wrapper around IPv4 Endpoint Option (12 bytes total: 4 header + 8 data).
 ///
 /// IPv4 endpoint options convey IPv4 address, port, and transport protocol
@@ -369,6 +600,16 @@ impl<T: AsRef<[u8]>> IPv4EndpointOption<T> {
         Ok(())
     }
 
+    /// Get a reference to the underlying buffer.
+    pub fn buffer(&self) -> &T {
+        &self.buffer
+    }
+
+    /// Consume the wrapper and return the underlying buffer.
+    pub fn into_buffer(self) -> T {
+        self.buffer
+    }
+
     /// Get a view of the option header (first 4 bytes).
     ///
     /// # Returns
@@ -377,6 +618,14 @@ impl<T: AsRef<[u8]>> IPv4EndpointOption<T> {
         OptionHeader::new_unchecked(&self.buffer.as_ref()[..4])
     }
 
+    /// Get the complete option (header and payload), bounded to `LENGTH`.
+    ///
+    /// # Returns
+    /// The option's wire bytes, for forwarding it verbatim
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer.as_ref()[..Self::LENGTH]
+    }
+
     /// Get the IPv4 address (4 bytes at offset 4-7).
     ///
     /// # Returns
@@ -413,6 +662,58 @@ impl<T: AsRef<[u8]>> IPv4EndpointOption<T> {
     pub fn port(&self) -> u16 {
         NetworkEndian::read_u16(&self.buffer.as_ref()[4 + field::ipv4_endpoint_option::PORT.start..])
     }
+
+    /// Get the IPv4 address as a `core::net::Ipv4Addr`.
+    pub fn ipv4_addr(&self) -> Ipv4Addr {
+        Ipv4Addr::from(self.ipv4_address())
+    }
+
+    /// Get the address and port together as a `core::net::SocketAddrV4`.
+    pub fn socket_addr(&self) -> SocketAddrV4 {
+        SocketAddrV4::new(self.ipv4_addr(), self.port())
+    }
+}
+
+/// Check that `buffer` starts with a fixed-size option of the expected type.
+///
+/// # Returns
+/// * `Ok(())` if the buffer holds `length` bytes, the type byte matches, and the
+///   header length field equals `length - 3`
+/// * `Err(Error::BufferTooShort)` if the buffer is shorter than `length`
+/// * `Err(Error::InvalidOptionType)` if the type byte doesn't match
+/// * `Err(Error::LengthOverflow)` if the header length field is wrong
+fn check_typed_option(buffer: &[u8], option_type: OptionType, length: usize) -> Result<()> {
+    if buffer.len() < length {
+        return Err(Error::BufferTooShort);
+    }
+    let header = OptionHeader::new_unchecked(buffer);
+    if header.option_type() != option_type.as_u8() {
+        return Err(Error::InvalidOptionType(header.option_type()));
+    }
+    if header.length() as usize != length - field::option_header::TYPE.end {
+        return Err(Error::LengthOverflow);
+    }
+    Ok(())
+}
+
+impl<'a> IPv4EndpointOption<&'a [u8]> {
+    /// Parse a buffer strictly as an IPv4 endpoint option.
+    ///
+    /// Unlike `new_checked`, this also verifies the header type byte (0x04)
+    /// and the header length field.
+    ///
+    /// # Parameters
+    /// * `buf` - The buffer starting with the option
+    ///
+    /// # Returns
+    /// * `Ok(IPv4EndpointOption)` if the buffer holds a well-formed option of this type
+    /// * `Err(Error::BufferTooShort)` if the buffer is too short
+    /// * `Err(Error::InvalidOptionType)` if the option has another type
+    /// * `Err(Error::LengthOverflow)` if the header length field is wrong
+    pub fn parse_from(buf: &'a [u8]) -> Result<Self> {
+        check_typed_option(buf, OptionType::IPv4Endpoint, Self::LENGTH)?;
+        Ok(Self::new_unchecked(buf))
+    }
 }
 
 impl<T: AsRef<[u8]> + AsMut<[u8]>> IPv4EndpointOption<T> {
@@ -439,6 +740,14 @@ impl<T: AsRef<[u8]> + AsMut<[u8]>> IPv4EndpointOption<T> {
     pub fn set_port(&mut self, port: u16) {
         NetworkEndian::write_u16(&mut self.buffer.as_mut()[4 + field::ipv4_endpoint_option::PORT.start..], port);
     }
+
+    /// Set the IPv4 address from a `core::net::Ipv4Addr`.
+    ///
+    /// # Parameters
+    /// * `addr` - The IPv4 address
+    pub fn set_ipv4_addr(&mut self, addr: Ipv4Addr) {
+        self.set_ipv4_address(addr.octets());
+    }
 }
 
 /// Zero-copy wrapper around IPv6 Endpoint Option (24 bytes total: 4 header + 20 data).
@@ -507,6 +816,16 @@ impl<T: AsRef<[u8]>> IPv6EndpointOption<T> {
         Ok(())
     }
 
+    /// Get a reference to the underlying buffer.
+    pub fn buffer(&self) -> &T {
+        &self.buffer
+    }
+
+    /// Consume the wrapper and return the underlying buffer.
+    pub fn into_buffer(self) -> T {
+        self.buffer
+    }
+
     /// Get a view of the option header (first 4 bytes).
     ///
     /// # Returns
@@ -515,6 +834,14 @@ impl<T: AsRef<[u8]>> IPv6EndpointOption<T> {
         OptionHeader::new_unchecked(&self.buffer.as_ref()[..4])
     }
 
+    /// Get the complete option (header and payload), bounded to `LENGTH`.
+    ///
+    /// # Returns
+    /// The option's wire bytes, for forwarding it verbatim
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer.as_ref()[..Self::LENGTH]
+    }
+
     /// Get the IPv6 address (16 bytes at offset 4-19).
     ///
     /// # Returns
@@ -553,6 +880,37 @@ impl<T: AsRef<[u8]>> IPv6EndpointOption<T> {
     pub fn port(&self) -> u16 {
         NetworkEndian::read_u16(&self.buffer.as_ref()[4 + field::ipv6_endpoint_option::PORT.start..])
     }
+
+    /// Get the IPv6 address as a `core::net::Ipv6Addr`.
+    pub fn ipv6_addr(&self) -> Ipv6Addr {
+        Ipv6Addr::from(self.ipv6_address())
+    }
+
+    /// Get the address and port together as a `core::net::SocketAddrV6`.
+    /// The flow info and scope ID are zero; the option doesn't carry them.
+    pub fn socket_addr(&self) -> SocketAddrV6 {
+        SocketAddrV6::new(self.ipv6_addr(), self.port(), 0, 0)
+    }
+}
+
+impl<'a> IPv6EndpointOption<&'a [u8]> {
+    /// Parse a buffer strictly as an IPv6 endpoint option.
+    ///
+    /// Unlike `new_checked`, this also verifies the header type byte (0x06)
+    /// and the header length field.
+    ///
+    /// # Parameters
+    /// * `buf` - The buffer starting with the option
+    ///
+    /// # Returns
+    /// * `Ok(IPv6EndpointOption)` if the buffer holds a well-formed option of this type
+    /// * `Err(Error::BufferTooShort)` if the buffer is too short
+    /// * `Err(Error::InvalidOptionType)` if the option has another type
+    /// * `Err(Error::LengthOverflow)` if the header length field is wrong
+    pub fn parse_from(buf: &'a [u8]) -> Result<Self> {
+        check_typed_option(buf, OptionType::IPv6Endpoint, Self::LENGTH)?;
+        Ok(Self::new_unchecked(buf))
+    }
 }
 
 impl<T: AsRef<[u8]> + AsMut<[u8]>> IPv6EndpointOption<T> {
@@ -579,6 +937,14 @@ impl<T: AsRef<[u8]> + AsMut<[u8]>> IPv6EndpointOption<T> {
     pub fn set_port(&mut self, port: u16) {
         NetworkEndian::write_u16(&mut self.buffer.as_mut()[4 + field::ipv6_endpoint_option::PORT.start..], port);
     }
+
+    /// Set the IPv6 address from a `core::net::Ipv6Addr`.
+    ///
+    /// # Parameters
+    /// * `addr` - The IPv6 address
+    pub fn set_ipv6_addr(&mut self, addr: Ipv6Addr) {
+        self.set_ipv6_address(addr.octets());
+    }
 }
 
 /// Zero-copy wrapper around Load Balancing Option (8 bytes total: 4 header + 4 data).
@@ -641,6 +1007,16 @@ impl<T: AsRef<[u8]>> LoadBalancingOption<T> {
         Ok(())
     }
 
+    /// Get a reference to the underlying buffer.
+    pub fn buffer(&self) -> &T {
+        &self.buffer
+    }
+
+    /// Consume the wrapper and return the underlying buffer.
+    pub fn into_buffer(self) -> T {
+        self.buffer
+    }
+
     /// Get a view of the option header (first 4 bytes).
     ///
     /// # Returns
@@ -649,6 +1025,14 @@ impl<T: AsRef<[u8]>> LoadBalancingOption<T> {
         OptionHeader::new_unchecked(&self.buffer.as_ref()[..4])
     }
 
+    /// Get the complete option (header and payload), bounded to `LENGTH`.
+    ///
+    /// # Returns
+    /// The option's wire bytes, for forwarding it verbatim
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer.as_ref()[..Self::LENGTH]
+    }
+
     /// Get the priority value (2 bytes at offset 4-5, network byte order).
     ///
     /// # Returns
@@ -666,6 +1050,26 @@ impl<T: AsRef<[u8]>> LoadBalancingOption<T> {
     }
 }
 
+impl<'a> LoadBalancingOption<&'a [u8]> {
+    /// Parse a buffer strictly as a load balancing option.
+    ///
+    /// Unlike `new_checked`, this also verifies the header type byte (0x02)
+    /// and the header length field.
+    ///
+    /// # Parameters
+    /// * `buf` - The buffer starting with the option
+    ///
+    /// # Returns
+    /// * `Ok(LoadBalancingOption)` if the buffer holds a well-formed option of this type
+    /// * `Err(Error::BufferTooShort)` if the buffer is too short
+    /// * `Err(Error::InvalidOptionType)` if the option has another type
+    /// * `Err(Error::LengthOverflow)` if the header length field is wrong
+    pub fn parse_from(buf: &'a [u8]) -> Result<Self> {
+        check_typed_option(buf, OptionType::LoadBalancing, Self::LENGTH)?;
+        Ok(Self::new_unchecked(buf))
+    }
+}
+
 impl<T: AsRef<[u8]> + AsMut<[u8]>> LoadBalancingOption<T> {
     /// Set the priority value (2 bytes at offset 4-5, network byte order).
     ///
@@ -684,109 +1088,1342 @@ impl<T: AsRef<[u8]> + AsMut<[u8]>> LoadBalancingOption<T> {
     }
 }
 
-/// High-level representation of an IPv4 Endpoint Option.
+/// Zero-copy wrapper around IPv4 Multicast Option (12 bytes total: 4 header + 8 data).
 ///
-/// This provides a builder-style API for constructing and parsing IPv4 endpoint options
-/// without manually managing byte arrays.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct IPv4EndpointOptionRepr {
-    /// IPv4 address (4 bytes)
-    pub ipv4_address: [u8; 4],
-    /// Transport protocol (TCP=0x06, UDP=0x11)
-    pub protocol: TransportProtocol,
-    /// Port number
-    pub port: u16,
+/// IPv4 multicast options carry the multicast group and port an
+/// OfferService delivers events to, or a SubscribeAck confirms. Multicast is
+/// UDP-only, so `check_protocol` rejects TCP.
+///
+/// Wire format (12 bytes):
+/// ```text
+/// 0               1               2               3
+/// 0 1 2 3 4 5 6 7 0 1 2 3 4 5 6 7 0 1 2 3 4 5 6 7 0 1 2 3 4 5 6 7
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |           Length              |     Type      |D|  Reserved   |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                  IPv4 Multicast Address                       |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |    Reserved   |   Protocol    |             Port              |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct IPv4MulticastOption<T: AsRef<[u8]>> {
+    buffer: T,
 }
 
-impl IPv4EndpointOptionRepr {
-    /// Parse an IPv4EndpointOption into a high-level representation.
+impl<T: AsRef<[u8]>> IPv4MulticastOption<T> {
+    /// IPv4 multicast option wire format size in bytes (4 header + 8 data).
+    pub const LENGTH: usize = 12;
+
+    /// Create an IPv4MulticastOption without validation.
     ///
     /// # Parameters
-    /// * `option` - The IPv4EndpointOption to parse
-    ///
-    /// # Returns
-    /// IPv4EndpointOptionRepr with all fields populated
+    /// * `buffer` - The buffer containing the 12-byte option
     ///
-    /// # Errors
-    /// Returns Error::InvalidProtocol if protocol is not TCP or UDP
-    pub fn parse<T: AsRef<[u8]>>(option: &IPv4EndpointOption<T>) -> Result<Self> {
-        option.check_protocol()?;
-        
-        let protocol = TransportProtocol::from_u8(option.transport_protocol())
-            .ok_or(Error::InvalidProtocol(option.transport_protocol()))?;
-
-        Ok(IPv4EndpointOptionRepr {
-            ipv4_address: option.ipv4_address(),
-            protocol,
-            port: option.port(),
-        })
+    /// # Safety
+    /// This does not validate buffer length. Use `new_checked` for validation.
+    pub fn new_unchecked(buffer: T) -> Self {
+        IPv4MulticastOption { buffer }
     }
 
-    /// Emit this representation into a buffer.
+    /// Create an IPv4MulticastOption from a buffer with length validation.
     ///
     /// # Parameters
-    /// * `buffer` - 12-byte buffer to write the option into
+    /// * `buffer` - The buffer containing the 12-byte option
     ///
     /// # Returns
-    /// Number of bytes written (always 12)
-    pub fn emit(&self, buffer: &mut [u8]) -> usize {
-        let mut header = OptionHeader::new_unchecked(&mut buffer[..4]);
-        header.set_length(9);
-        header.set_option_type(OptionType::IPv4Endpoint.as_u8());
-        
-        let mut option = IPv4EndpointOption::new_unchecked(buffer);
-        option.set_ipv4_address(self.ipv4_address);
-        option.set_transport_protocol(self.protocol.as_u8());
-        option.set_port(self.port);
-        
-        Self::buffer_len()
+    /// * `Ok(IPv4MulticastOption)` if buffer is at least 12 bytes
+    /// * `Err(Error)` if buffer is too short
+    pub fn new_checked(buffer: T) -> Result<Self> {
+        let option = Self::new_unchecked(buffer);
+        option.check_len()?;
+        Ok(option)
     }
 
-    /// Get the wire format size of this option (always 12 bytes: 4 header + 8 payload).
-    pub const fn buffer_len() -> usize {
-        12
+    /// Validate that the buffer is at least 12 bytes long.
+    ///
+    /// # Returns
+    /// * `Ok(())` if buffer meets minimum length requirement
+    /// * `Err(Error)` if buffer is too short
+    pub fn check_len(&self) -> Result<()> {
+        if self.buffer.as_ref().len() < Self::LENGTH {
+            return Err(Error::BufferTooShort);
+        }
+        Ok(())
     }
-}
 
-/// High-level representation of an IPv6 Endpoint Option.
-///
-/// This provides a builder-style API for constructing and parsing IPv6 endpoint options
-/// without manually managing byte arrays.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct IPv6EndpointOptionRepr {
-    /// IPv6 address (16 bytes)
-    pub ipv6_address: [u8; 16],
-    /// Transport protocol (TCP=0x06, UDP=0x11)
-    pub protocol: TransportProtocol,
-    /// Port number
-    pub port: u16,
-}
+    /// Get a reference to the underlying buffer.
+    pub fn buffer(&self) -> &T {
+        &self.buffer
+    }
 
-impl IPv6EndpointOptionRepr {
-    /// Parse an IPv6EndpointOption into a high-level representation.
-    ///
-    /// # Parameters
-    /// * `option` - The IPv6EndpointOption to parse
+    /// Consume the wrapper and return the underlying buffer.
+    pub fn into_buffer(self) -> T {
+        self.buffer
+    }
+
+    /// Get a view of the option header (first 4 bytes).
     ///
     /// # Returns
-    /// IPv6EndpointOptionRepr with all fields populated
-    ///
-    /// # Errors
-    /// Returns Error::InvalidProtocol if protocol is not TCP or UDP
-    pub fn parse<T: AsRef<[u8]>>(option: &IPv6EndpointOption<T>) -> Result<Self> {
-        option.check_protocol()?;
-        
-        let protocol = TransportProtocol::from_u8(option.transport_protocol())
-            .ok_or(Error::InvalidProtocol(option.transport_protocol()))?;
+    /// OptionHeader wrapper around the header bytes
+    pub fn header(&self) -> OptionHeader<&[u8]> {
+        OptionHeader::new_unchecked(&self.buffer.as_ref()[..4])
+    }
 
-        Ok(IPv6EndpointOptionRepr {
-            ipv6_address: option.ipv6_address(),
-            protocol,
-            port: option.port(),
-        })
+    /// Get the complete option (header and payload), bounded to `LENGTH`.
+    ///
+    /// # Returns
+    /// The option's wire bytes, for forwarding it verbatim
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer.as_ref()[..Self::LENGTH]
     }
 
-    /// Emit this representation into a buffer.
+    /// Get the IPv4 multicast address (4 bytes at offset 4-7).
+    ///
+    /// # Returns
+    /// The multicast group address as a 4-byte array in network byte order
+    pub fn ipv4_multicast_address(&self) -> [u8; 4] {
+        let bytes = &self.buffer.as_ref()[4 + field::ipv4_multicast_option::IPV4_MULTICAST_ADDRESS.start..];
+        [bytes[0], bytes[1], bytes[2], bytes[3]]
+    }
+
+    /// Get the transport protocol (1 byte at offset 9).
+    ///
+    /// # Returns
+    /// Protocol value (0x11=UDP for a valid multicast option)
+    pub fn transport_protocol(&self) -> u8 {
+        self.buffer.as_ref()[4 + field::ipv4_multicast_option::TRANSPORT_PROTOCOL.start]
+    }
+
+    /// Validate the transport protocol field.
+    ///
+    /// TCP cannot be multicast, so unlike the endpoint options only UDP is
+    /// accepted.
+    ///
+    /// # Returns
+    /// * `Ok(())` if protocol is UDP (0x11)
+    /// * `Err(Error::InvalidProtocol)` for any other value, TCP included
+    pub fn check_protocol(&self) -> Result<()> {
+        let proto = self.transport_protocol();
+        match TransportProtocol::from_u8(proto) {
+            Some(TransportProtocol::UDP) => Ok(()),
+            _ => Err(Error::InvalidProtocol(proto)),
+        }
+    }
+
+    /// Get the port number (2 bytes at offset 10-11, network byte order).
+    ///
+    /// # Returns
+    /// The port number
+    pub fn port(&self) -> u16 {
+        NetworkEndian::read_u16(&self.buffer.as_ref()[4 + field::ipv4_multicast_option::PORT.start..])
+    }
+
+    /// Get the IPv4 address as a `core::net::Ipv4Addr`.
+    pub fn ipv4_addr(&self) -> Ipv4Addr {
+        Ipv4Addr::from(self.ipv4_multicast_address())
+    }
+
+    /// Get the address and port together as a `core::net::SocketAddrV4`.
+    pub fn socket_addr(&self) -> SocketAddrV4 {
+        SocketAddrV4::new(self.ipv4_addr(), self.port())
+    }
+}
+
+impl<'a> IPv4MulticastOption<&'a [u8]> {
+    /// Parse a buffer strictly as an IPv4 multicast option.
+    ///
+    /// Unlike `new_checked`, this also verifies the header type byte (0x14)
+    /// and the header length field.
+    ///
+    /// # Parameters
+    /// * `buf` - The buffer starting with the option
+    ///
+    /// # Returns
+    /// * `Ok(IPv4MulticastOption)` if the buffer holds a well-formed option of this type
+    /// * `Err(Error::BufferTooShort)` if the buffer is too short
+    /// * `Err(Error::InvalidOptionType)` if the option has another type
+    /// * `Err(Error::LengthOverflow)` if the header length field is wrong
+    pub fn parse_from(buf: &'a [u8]) -> Result<Self> {
+        check_typed_option(buf, OptionType::IPv4Multicast, Self::LENGTH)?;
+        Ok(Self::new_unchecked(buf))
+    }
+}
+
+impl<T: AsRef<[u8]> + AsMut<[u8]>> IPv4MulticastOption<T> {
+    /// Set the IPv4 multicast address (4 bytes at offset 4-7).
+    ///
+    /// # Parameters
+    /// * `addr` - The multicast group address in network byte order
+    pub fn set_ipv4_multicast_address(&mut self, addr: [u8; 4]) {
+        let start = 4 + field::ipv4_multicast_option::IPV4_MULTICAST_ADDRESS.start;
+        self.buffer.as_mut()[start..start + 4].copy_from_slice(&addr);
+    }
+
+    /// Set the transport protocol (1 byte at offset 9).
+    ///
+    /// # Parameters
+    /// * `proto` - Protocol value (0x11=UDP)
+    pub fn set_transport_protocol(&mut self, proto: u8) {
+        self.buffer.as_mut()[4 + field::ipv4_multicast_option::TRANSPORT_PROTOCOL.start] = proto;
+    }
+
+    /// Set the port number (2 bytes at offset 10-11, network byte order).
+    ///
+    /// # Parameters
+    /// * `port` - The port number
+    pub fn set_port(&mut self, port: u16) {
+        NetworkEndian::write_u16(&mut self.buffer.as_mut()[4 + field::ipv4_multicast_option::PORT.start..], port);
+    }
+
+    /// Set the IPv4 address from a `core::net::Ipv4Addr`.
+    ///
+    /// # Parameters
+    /// * `addr` - The IPv4 address
+    pub fn set_ipv4_addr(&mut self, addr: Ipv4Addr) {
+        self.set_ipv4_multicast_address(addr.octets());
+    }
+}
+
+/// Zero-copy wrapper around IPv6 Multicast Option (24 bytes total: 4 header + 20 data).
+///
+/// The IPv6 analog of `IPv4MulticastOption`; IPv6 multicast options carry
+/// the multicast group and port an OfferService delivers events to, or a
+/// SubscribeAck confirms. Multicast is UDP-only, so `check_protocol` rejects
+/// TCP.
+///
+/// Wire format (24 bytes):
+/// ```text
+/// 0               1               2               3
+/// 0 1 2 3 4 5 6 7 0 1 2 3 4 5 6 7 0 1 2 3 4 5 6 7 0 1 2 3 4 5 6 7
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |           Length              |     Type      |D|  Reserved   |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                                                               |
+/// |              IPv6 Multicast Address (16 bytes)                |
+/// |                                                               |
+/// |                                                               |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |    Reserved   |   Protocol    |             Port              |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct IPv6MulticastOption<T: AsRef<[u8]>> {
+    buffer: T,
+}
+
+impl<T: AsRef<[u8]>> IPv6MulticastOption<T> {
+    /// IPv6 multicast option wire format size in bytes (4 header + 20 data).
+    pub const LENGTH: usize = 24;
+
+    /// Create an IPv6MulticastOption without validation.
+    ///
+    /// # Parameters
+    /// * `buffer` - The buffer containing the 24-byte option
+    ///
+    /// # Safety
+    /// This does not validate buffer length. Use `new_checked` for validation.
+    pub fn new_unchecked(buffer: T) -> Self {
+        IPv6MulticastOption { buffer }
+    }
+
+    /// Create an IPv6MulticastOption from a buffer with length validation.
+    ///
+    /// # Parameters
+    /// * `buffer` - The buffer containing the 24-byte option
+    ///
+    /// # Returns
+    /// * `Ok(IPv6MulticastOption)` if buffer is at least 24 bytes
+    /// * `Err(Error)` if buffer is too short
+    pub fn new_checked(buffer: T) -> Result<Self> {
+        let option = Self::new_unchecked(buffer);
+        option.check_len()?;
+        Ok(option)
+    }
+
+    /// Validate that the buffer is at least 24 bytes long.
+    ///
+    /// # Returns
+    /// * `Ok(())` if buffer meets minimum length requirement
+    /// * `Err(Error)` if buffer is too short
+    pub fn check_len(&self) -> Result<()> {
+        if self.buffer.as_ref().len() < Self::LENGTH {
+            return Err(Error::BufferTooShort);
+        }
+        Ok(())
+    }
+
+    /// Get a reference to the underlying buffer.
+    pub fn buffer(&self) -> &T {
+        &self.buffer
+    }
+
+    /// Consume the wrapper and return the underlying buffer.
+    pub fn into_buffer(self) -> T {
+        self.buffer
+    }
+
+    /// Get a view of the option header (first 4 bytes).
+    ///
+    /// # Returns
+    /// OptionHeader wrapper around the header bytes
+    pub fn header(&self) -> OptionHeader<&[u8]> {
+        OptionHeader::new_unchecked(&self.buffer.as_ref()[..4])
+    }
+
+    /// Get the complete option (header and payload), bounded to `LENGTH`.
+    ///
+    /// # Returns
+    /// The option's wire bytes, for forwarding it verbatim
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer.as_ref()[..Self::LENGTH]
+    }
+
+    /// Get the IPv6 multicast address (16 bytes at offset 4-19).
+    ///
+    /// # Returns
+    /// The multicast group address as a 16-byte array in network byte order
+    pub fn ipv6_multicast_address(&self) -> [u8; 16] {
+        let start = 4 + field::ipv6_multicast_option::IPV6_MULTICAST_ADDRESS.start;
+        let mut addr = [0u8; 16];
+        addr.copy_from_slice(&self.buffer.as_ref()[start..start + 16]);
+        addr
+    }
+
+    /// Get the transport protocol (1 byte at offset 21).
+    ///
+    /// # Returns
+    /// Protocol value (0x11=UDP for a valid multicast option)
+    pub fn transport_protocol(&self) -> u8 {
+        self.buffer.as_ref()[4 + field::ipv6_multicast_option::TRANSPORT_PROTOCOL.start]
+    }
+
+    /// Validate the transport protocol field.
+    ///
+    /// TCP cannot be multicast, so unlike the endpoint options only UDP is
+    /// accepted.
+    ///
+    /// # Returns
+    /// * `Ok(())` if protocol is UDP (0x11)
+    /// * `Err(Error::InvalidProtocol)` for any other value, TCP included
+    pub fn check_protocol(&self) -> Result<()> {
+        let proto = self.transport_protocol();
+        match TransportProtocol::from_u8(proto) {
+            Some(TransportProtocol::UDP) => Ok(()),
+            _ => Err(Error::InvalidProtocol(proto)),
+        }
+    }
+
+    /// Get the port number (2 bytes at offset 22-23, network byte order).
+    ///
+    /// # Returns
+    /// The port number
+    pub fn port(&self) -> u16 {
+        NetworkEndian::read_u16(&self.buffer.as_ref()[4 + field::ipv6_multicast_option::PORT.start..])
+    }
+
+    /// Get the IPv6 address as a `core::net::Ipv6Addr`.
+    pub fn ipv6_addr(&self) -> Ipv6Addr {
+        Ipv6Addr::from(self.ipv6_multicast_address())
+    }
+
+    /// Get the address and port together as a `core::net::SocketAddrV6`.
+    /// The flow info and scope ID are zero; the option doesn't carry them.
+    pub fn socket_addr(&self) -> SocketAddrV6 {
+        SocketAddrV6::new(self.ipv6_addr(), self.port(), 0, 0)
+    }
+}
+
+impl<'a> IPv6MulticastOption<&'a [u8]> {
+    /// Parse a buffer strictly as an IPv6 multicast option.
+    ///
+    /// Unlike `new_checked`, this also verifies the header type byte (0x16)
+    /// and the header length field.
+    ///
+    /// # Parameters
+    /// * `buf` - The buffer starting with the option
+    ///
+    /// # Returns
+    /// * `Ok(IPv6MulticastOption)` if the buffer holds a well-formed option of this type
+    /// * `Err(Error::BufferTooShort)` if the buffer is too short
+    /// * `Err(Error::InvalidOptionType)` if the option has another type
+    /// * `Err(Error::LengthOverflow)` if the header length field is wrong
+    pub fn parse_from(buf: &'a [u8]) -> Result<Self> {
+        check_typed_option(buf, OptionType::IPv6Multicast, Self::LENGTH)?;
+        Ok(Self::new_unchecked(buf))
+    }
+}
+
+impl<T: AsRef<[u8]> + AsMut<[u8]>> IPv6MulticastOption<T> {
+    /// Set the IPv6 multicast address (16 bytes at offset 4-19).
+    ///
+    /// # Parameters
+    /// * `addr` - The multicast group address in network byte order
+    pub fn set_ipv6_multicast_address(&mut self, addr: [u8; 16]) {
+        let start = 4 + field::ipv6_multicast_option::IPV6_MULTICAST_ADDRESS.start;
+        self.buffer.as_mut()[start..start + 16].copy_from_slice(&addr);
+    }
+
+    /// Set the transport protocol (1 byte at offset 21).
+    ///
+    /// # Parameters
+    /// * `proto` - Protocol value (0x11=UDP)
+    pub fn set_transport_protocol(&mut self, proto: u8) {
+        self.buffer.as_mut()[4 + field::ipv6_multicast_option::TRANSPORT_PROTOCOL.start] = proto;
+    }
+
+    /// Set the port number (2 bytes at offset 22-23, network byte order).
+    ///
+    /// # Parameters
+    /// * `port` - The port number
+    pub fn set_port(&mut self, port: u16) {
+        NetworkEndian::write_u16(&mut self.buffer.as_mut()[4 + field::ipv6_multicast_option::PORT.start..], port);
+    }
+
+    /// Set the IPv6 address from a `core::net::Ipv6Addr`.
+    ///
+    /// # Parameters
+    /// * `addr` - The IPv6 address
+    pub fn set_ipv6_addr(&mut self, addr: Ipv6Addr) {
+        self.set_ipv6_multicast_address(addr.octets());
+    }
+}
+
+/// Zero-copy wrapper around IPv4 SD Endpoint Option (12 bytes total: 4 header + 8 data).
+///
+/// IPv4 SD endpoint options convey the IPv4 address, port, and transport
+/// protocol a peer should send future SD messages to.
+///
+/// Wire format (12 bytes):
+/// ```text
+/// 0               1               2               3
+/// 0 1 2 3 4 5 6 7 0 1 2 3 4 5 6 7 0 1 2 3 4 5 6 7 0 1 2 3 4 5 6 7
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |           Length              |     Type      |D|  Reserved   |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                       IPv4 Address                            |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |    Reserved   |   Protocol    |             Port              |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct IPv4SdEndpointOption<T: AsRef<[u8]>> {
+    buffer: T,
+}
+
+impl<T: AsRef<[u8]>> IPv4SdEndpointOption<T> {
+    /// IPv4 SD endpoint option wire format size in bytes (4 header + 8 data).
+    pub const LENGTH: usize = 12;
+
+    /// Create an IPv4SdEndpointOption without validation.
+    ///
+    /// # Parameters
+    /// * `buffer` - The buffer containing the 12-byte option
+    ///
+    /// # Safety
+    /// This does not validate buffer length. Use `new_checked` for validation.
+    pub fn new_unchecked(buffer: T) -> Self {
+        IPv4SdEndpointOption { buffer }
+    }
+
+    /// Create an IPv4SdEndpointOption from a buffer with length validation.
+    ///
+    /// # Parameters
+    /// * `buffer` - The buffer containing the 12-byte option
+    ///
+    /// # Returns
+    /// * `Ok(IPv4SdEndpointOption)` if buffer is at least 12 bytes
+    /// * `Err(Error)` if buffer is too short
+    pub fn new_checked(buffer: T) -> Result<Self> {
+        let option = Self::new_unchecked(buffer);
+        option.check_len()?;
+        Ok(option)
+    }
+
+    /// Validate that the buffer is at least 12 bytes long.
+    ///
+    /// # Returns
+    /// * `Ok(())` if buffer meets minimum length requirement
+    /// * `Err(Error)` if buffer is too short
+    pub fn check_len(&self) -> Result<()> {
+        if self.buffer.as_ref().len() < Self::LENGTH {
+            return Err(Error::BufferTooShort);
+        }
+        Ok(())
+    }
+
+    /// Get a reference to the underlying buffer.
+    pub fn buffer(&self) -> &T {
+        &self.buffer
+    }
+
+    /// Consume the wrapper and return the underlying buffer.
+    pub fn into_buffer(self) -> T {
+        self.buffer
+    }
+
+    /// Get a view of the option header (first 4 bytes).
+    ///
+    /// # Returns
+    /// OptionHeader wrapper around the header bytes
+    pub fn header(&self) -> OptionHeader<&[u8]> {
+        OptionHeader::new_unchecked(&self.buffer.as_ref()[..4])
+    }
+
+    /// Get the complete option (header and payload), bounded to `LENGTH`.
+    ///
+    /// # Returns
+    /// The option's wire bytes, for forwarding it verbatim
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer.as_ref()[..Self::LENGTH]
+    }
+
+    /// Get the IPv4 address (4 bytes at offset 4-7).
+    ///
+    /// # Returns
+    /// The IPv4 address as a 4-byte array in network byte order
+    pub fn ipv4_address(&self) -> [u8; 4] {
+        let bytes = &self.buffer.as_ref()[4..];
+        [bytes[0], bytes[1], bytes[2], bytes[3]]
+    }
+
+    /// Get the transport protocol (1 byte at offset 9).
+    ///
+    /// # Returns
+    /// Protocol value (0x06=TCP, 0x11=UDP)
+    pub fn transport_protocol(&self) -> u8 {
+        self.buffer.as_ref()[4 + field::ipv4_sd_endpoint_option::TRANSPORT_PROTOCOL.start]
+    }
+
+    /// Validate the transport protocol field.
+    ///
+    /// # Returns
+    /// * `Ok(())` if protocol is TCP (0x06) or UDP (0x11)
+    /// * `Err(Error::InvalidProtocol)` if protocol is unknown
+    pub fn check_protocol(&self) -> Result<()> {
+        let proto = self.transport_protocol();
+        TransportProtocol::from_u8(proto)
+            .map(|_| ())
+            .ok_or(Error::InvalidProtocol(proto))
+    }
+
+    /// Get the port number (2 bytes at offset 10-11, network byte order).
+    ///
+    /// # Returns
+    /// The port number
+    pub fn port(&self) -> u16 {
+        NetworkEndian::read_u16(&self.buffer.as_ref()[4 + field::ipv4_sd_endpoint_option::PORT.start..])
+    }
+
+    /// Get the IPv4 address as a `core::net::Ipv4Addr`.
+    pub fn ipv4_addr(&self) -> Ipv4Addr {
+        Ipv4Addr::from(self.ipv4_address())
+    }
+
+    /// Get the address and port together as a `core::net::SocketAddrV4`.
+    pub fn socket_addr(&self) -> SocketAddrV4 {
+        SocketAddrV4::new(self.ipv4_addr(), self.port())
+    }
+}
+
+impl<'a> IPv4SdEndpointOption<&'a [u8]> {
+    /// Parse a buffer strictly as an IPv4 SD endpoint option.
+    ///
+    /// Unlike `new_checked`, this also verifies the header type byte (0x24)
+    /// and the header length field.
+    ///
+    /// # Parameters
+    /// * `buf` - The buffer starting with the option
+    ///
+    /// # Returns
+    /// * `Ok(IPv4SdEndpointOption)` if the buffer holds a well-formed option of this type
+    /// * `Err(Error::BufferTooShort)` if the buffer is too short
+    /// * `Err(Error::InvalidOptionType)` if the option has another type
+    /// * `Err(Error::LengthOverflow)` if the header length field is wrong
+    pub fn parse_from(buf: &'a [u8]) -> Result<Self> {
+        check_typed_option(buf, OptionType::IPv4SdEndpoint, Self::LENGTH)?;
+        Ok(Self::new_unchecked(buf))
+    }
+}
+
+impl<T: AsRef<[u8]> + AsMut<[u8]>> IPv4SdEndpointOption<T> {
+    /// Set the IPv4 address (4 bytes at offset 4-7).
+    ///
+    /// # Parameters
+    /// * `addr` - The IPv4 address as a 4-byte array in network byte order
+    pub fn set_ipv4_address(&mut self, addr: [u8; 4]) {
+        self.buffer.as_mut()[4..8].copy_from_slice(&addr);
+    }
+
+    /// Set the transport protocol (1 byte at offset 9).
+    ///
+    /// # Parameters
+    /// * `proto` - Protocol value (0x06=TCP, 0x11=UDP)
+    pub fn set_transport_protocol(&mut self, proto: u8) {
+        self.buffer.as_mut()[4 + field::ipv4_sd_endpoint_option::TRANSPORT_PROTOCOL.start] = proto;
+    }
+
+    /// Set the port number (2 bytes at offset 10-11, network byte order).
+    ///
+    /// # Parameters
+    /// * `port` - The port number
+    pub fn set_port(&mut self, port: u16) {
+        NetworkEndian::write_u16(&mut self.buffer.as_mut()[4 + field::ipv4_sd_endpoint_option::PORT.start..], port);
+    }
+
+    /// Set the IPv4 address from a `core::net::Ipv4Addr`.
+    ///
+    /// # Parameters
+    /// * `addr` - The IPv4 address
+    pub fn set_ipv4_addr(&mut self, addr: Ipv4Addr) {
+        self.set_ipv4_address(addr.octets());
+    }
+}
+
+/// Zero-copy wrapper around IPv6 SD Endpoint Option (24 bytes total: 4 header + 20 data).
+///
+/// IPv6 SD endpoint options convey the IPv6 address, port, and transport
+/// protocol a peer should send future SD messages to.
+///
+/// Wire format (24 bytes):
+/// ```text
+/// 0               1               2               3
+/// 0 1 2 3 4 5 6 7 0 1 2 3 4 5 6 7 0 1 2 3 4 5 6 7 0 1 2 3 4 5 6 7
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |           Length              |     Type      |D|  Reserved   |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                                                               |
+/// |                       IPv6 Address (16 bytes)                 |
+/// |                                                               |
+/// |                                                               |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |    Reserved   |   Protocol    |             Port              |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct IPv6SdEndpointOption<T: AsRef<[u8]>> {
+    buffer: T,
+}
+
+impl<T: AsRef<[u8]>> IPv6SdEndpointOption<T> {
+    /// IPv6 SD endpoint option wire format size in bytes (4 header + 20 data).
+    pub const LENGTH: usize = 24;
+
+    /// Create an IPv6SdEndpointOption without validation.
+    ///
+    /// # Parameters
+    /// * `buffer` - The buffer containing the 24-byte option
+    ///
+    /// # Safety
+    /// This does not validate buffer length. Use `new_checked` for validation.
+    pub fn new_unchecked(buffer: T) -> Self {
+        IPv6SdEndpointOption { buffer }
+    }
+
+    /// Create an IPv6SdEndpointOption from a buffer with length validation.
+    ///
+    /// # Parameters
+    /// * `buffer` - The buffer containing the 24-byte option
+    ///
+    /// # Returns
+    /// * `Ok(IPv6SdEndpointOption)` if buffer is at least 24 bytes
+    /// * `Err(Error)` if buffer is too short
+    pub fn new_checked(buffer: T) -> Result<Self> {
+        let option = Self::new_unchecked(buffer);
+        option.check_len()?;
+        Ok(option)
+    }
+
+    /// Validate that the buffer is at least 24 bytes long.
+    ///
+    /// # Returns
+    /// * `Ok(())` if buffer meets minimum length requirement
+    /// * `Err(Error)` if buffer is too short
+    pub fn check_len(&self) -> Result<()> {
+        if self.buffer.as_ref().len() < Self::LENGTH {
+            return Err(Error::BufferTooShort);
+        }
+        Ok(())
+    }
+
+    /// Get a reference to the underlying buffer.
+    pub fn buffer(&self) -> &T {
+        &self.buffer
+    }
+
+    /// Consume the wrapper and return the underlying buffer.
+    pub fn into_buffer(self) -> T {
+        self.buffer
+    }
+
+    /// Get a view of the option header (first 4 bytes).
+    ///
+    /// # Returns
+    /// OptionHeader wrapper around the header bytes
+    pub fn header(&self) -> OptionHeader<&[u8]> {
+        OptionHeader::new_unchecked(&self.buffer.as_ref()[..4])
+    }
+
+    /// Get the complete option (header and payload), bounded to `LENGTH`.
+    ///
+    /// # Returns
+    /// The option's wire bytes, for forwarding it verbatim
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer.as_ref()[..Self::LENGTH]
+    }
+
+    /// Get the IPv6 address (16 bytes at offset 4-19).
+    ///
+    /// # Returns
+    /// The IPv6 address as a 16-byte array in network byte order
+    pub fn ipv6_address(&self) -> [u8; 16] {
+        let bytes = &self.buffer.as_ref()[4..];
+        let mut addr = [0u8; 16];
+        addr.copy_from_slice(&bytes[0..16]);
+        addr
+    }
+
+    /// Get the transport protocol (1 byte at offset 21).
+    ///
+    /// # Returns
+    /// Protocol value (0x06=TCP, 0x11=UDP)
+    pub fn transport_protocol(&self) -> u8 {
+        self.buffer.as_ref()[4 + field::ipv6_sd_endpoint_option::TRANSPORT_PROTOCOL.start]
+    }
+
+    /// Validate the transport protocol field.
+    ///
+    /// # Returns
+    /// * `Ok(())` if protocol is TCP (0x06) or UDP (0x11)
+    /// * `Err(Error::InvalidProtocol)` if protocol is unknown
+    pub fn check_protocol(&self) -> Result<()> {
+        let proto = self.transport_protocol();
+        TransportProtocol::from_u8(proto)
+            .map(|_| ())
+            .ok_or(Error::InvalidProtocol(proto))
+    }
+
+    /// Get the port number (2 bytes at offset 22-23, network byte order).
+    ///
+    /// # Returns
+    /// The port number
+    pub fn port(&self) -> u16 {
+        NetworkEndian::read_u16(&self.buffer.as_ref()[4 + field::ipv6_sd_endpoint_option::PORT.start..])
+    }
+
+    /// Get the IPv6 address as a `core::net::Ipv6Addr`.
+    pub fn ipv6_addr(&self) -> Ipv6Addr {
+        Ipv6Addr::from(self.ipv6_address())
+    }
+
+    /// Get the address and port together as a `core::net::SocketAddrV6`.
+    /// The flow info and scope ID are zero; the option doesn't carry them.
+    pub fn socket_addr(&self) -> SocketAddrV6 {
+        SocketAddrV6::new(self.ipv6_addr(), self.port(), 0, 0)
+    }
+}
+
+impl<'a> IPv6SdEndpointOption<&'a [u8]> {
+    /// Parse a buffer strictly as an IPv6 SD endpoint option.
+    ///
+    /// Unlike `new_checked`, this also verifies the header type byte (0x26)
+    /// and the header length field.
+    ///
+    /// # Parameters
+    /// * `buf` - The buffer starting with the option
+    ///
+    /// # Returns
+    /// * `Ok(IPv6SdEndpointOption)` if the buffer holds a well-formed option of this type
+    /// * `Err(Error::BufferTooShort)` if the buffer is too short
+    /// * `Err(Error::InvalidOptionType)` if the option has another type
+    /// * `Err(Error::LengthOverflow)` if the header length field is wrong
+    pub fn parse_from(buf: &'a [u8]) -> Result<Self> {
+        check_typed_option(buf, OptionType::IPv6SdEndpoint, Self::LENGTH)?;
+        Ok(Self::new_unchecked(buf))
+    }
+}
+
+impl<T: AsRef<[u8]> + AsMut<[u8]>> IPv6SdEndpointOption<T> {
+    /// Set the IPv6 address (16 bytes at offset 4-19).
+    ///
+    /// # Parameters
+    /// * `addr` - The IPv6 address as a 16-byte array in network byte order
+    pub fn set_ipv6_address(&mut self, addr: [u8; 16]) {
+        self.buffer.as_mut()[4..20].copy_from_slice(&addr);
+    }
+
+    /// Set the transport protocol (1 byte at offset 21).
+    ///
+    /// # Parameters
+    /// * `proto` - Protocol value (0x06=TCP, 0x11=UDP)
+    pub fn set_transport_protocol(&mut self, proto: u8) {
+        self.buffer.as_mut()[4 + field::ipv6_sd_endpoint_option::TRANSPORT_PROTOCOL.start] = proto;
+    }
+
+    /// Set the port number (2 bytes at offset 22-23, network byte order).
+    ///
+    /// # Parameters
+    /// * `port` - The port number
+    pub fn set_port(&mut self, port: u16) {
+        NetworkEndian::write_u16(&mut self.buffer.as_mut()[4 + field::ipv6_sd_endpoint_option::PORT.start..], port);
+    }
+
+    /// Set the IPv6 address from a `core::net::Ipv6Addr`.
+    ///
+    /// # Parameters
+    /// * `addr` - The IPv6 address
+    pub fn set_ipv6_addr(&mut self, addr: Ipv6Addr) {
+        self.set_ipv6_address(addr.octets());
+    }
+}
+
+/// High-level representation of an IPv4 Endpoint Option.
+///
+/// This provides a builder-style API for constructing and parsing IPv4 endpoint options
+/// without manually managing byte arrays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IPv4EndpointOptionRepr {
+    /// IPv4 address (4 bytes)
+    pub ipv4_address: [u8; 4],
+    /// Transport protocol (TCP=0x06, UDP=0x11)
+    pub protocol: TransportProtocol,
+    /// Port number
+    pub port: u16,
+}
+
+impl IPv4EndpointOptionRepr {
+    /// Parse an IPv4EndpointOption into a high-level representation.
+    ///
+    /// # Parameters
+    /// * `option` - The IPv4EndpointOption to parse
+    ///
+    /// # Returns
+    /// IPv4EndpointOptionRepr with all fields populated
+    ///
+    /// # Errors
+    /// Returns Error::LengthOverflow if the header length field is wrong, and
+    /// Error::InvalidProtocol if protocol is not TCP or UDP
+    pub fn parse<T: AsRef<[u8]>>(option: &IPv4EndpointOption<T>) -> Result<Self> {
+        option.header().check_length(9)?;
+        option.check_protocol()?;
+        
+        let protocol = TransportProtocol::from_u8(option.transport_protocol())
+            .ok_or(Error::InvalidProtocol(option.transport_protocol()))?;
+
+        Ok(IPv4EndpointOptionRepr {
+            ipv4_address: option.ipv4_address(),
+            protocol,
+            port: option.port(),
+        })
+    }
+
+    /// Emit this representation into a buffer.
+    ///
+    /// # Parameters
+    /// * `buffer` - 12-byte buffer to write the option into
+    ///
+    /// # Returns
+    /// Number of bytes written (always 12)
+    pub fn emit(&self, buffer: &mut [u8]) -> usize {
+        let mut header = OptionHeader::new_unchecked(&mut buffer[..4]);
+        header.set_length(9);
+        header.set_option_type(OptionType::IPv4Endpoint.as_u8());
+        
+        let mut option = IPv4EndpointOption::new_unchecked(buffer);
+        option.set_ipv4_address(self.ipv4_address);
+        option.set_transport_protocol(self.protocol.as_u8());
+        option.set_port(self.port);
+        
+        Self::buffer_len()
+    }
+
+    /// Get the wire format size of this option (always 12 bytes: 4 header + 8 payload).
+    pub const fn buffer_len() -> usize {
+        12
+    }
+
+    /// Get the address and port together.
+    ///
+    /// # Returns
+    /// `(address, port)`
+    pub fn addr_port(&self) -> ([u8; 4], u16) {
+        (self.ipv4_address, self.port)
+    }
+
+    /// Get the address, port and transport protocol together.
+    ///
+    /// # Returns
+    /// `(address, port, protocol)`
+    pub fn endpoint(&self) -> ([u8; 4], u16, TransportProtocol) {
+        (self.ipv4_address, self.port, self.protocol)
+    }
+
+    /// Replace the transport protocol, e.g. after converting from a `SocketAddrV4`.
+    ///
+    /// # Parameters
+    /// * `protocol` - The transport protocol
+    ///
+    /// # Returns
+    /// The representation with `protocol` set
+    pub fn with_protocol(mut self, protocol: TransportProtocol) -> Self {
+        self.protocol = protocol;
+        self
+    }
+}
+
+impl From<SocketAddrV4> for IPv4EndpointOptionRepr {
+    /// Convert a socket address into a UDP endpoint option.
+    fn from(addr: SocketAddrV4) -> Self {
+        IPv4EndpointOptionRepr {
+            ipv4_address: addr.ip().octets(),
+            protocol: TransportProtocol::UDP,
+            port: addr.port(),
+        }
+    }
+}
+
+impl From<IPv4EndpointOptionRepr> for SocketAddrV4 {
+    /// Convert an endpoint option into its socket address, dropping the protocol.
+    fn from(repr: IPv4EndpointOptionRepr) -> Self {
+        SocketAddrV4::new(Ipv4Addr::from(repr.ipv4_address), repr.port)
+    }
+}
+
+/// High-level representation of an IPv6 Endpoint Option.
+///
+/// This provides a builder-style API for constructing and parsing IPv6 endpoint options
+/// without manually managing byte arrays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IPv6EndpointOptionRepr {
+    /// IPv6 address (16 bytes)
+    pub ipv6_address: [u8; 16],
+    /// Transport protocol (TCP=0x06, UDP=0x11)
+    pub protocol: TransportProtocol,
+    /// Port number
+    pub port: u16,
+}
+
+impl IPv6EndpointOptionRepr {
+    /// Parse an IPv6EndpointOption into a high-level representation.
+    ///
+    /// # Parameters
+    /// * `option` - The IPv6EndpointOption to parse
+    ///
+    /// # Returns
+    /// IPv6EndpointOptionRepr with all fields populated
+    ///
+    /// # Errors
+    /// Returns Error::LengthOverflow if the header length field is wrong, and
+    /// Error::InvalidProtocol if protocol is not TCP or UDP
+    pub fn parse<T: AsRef<[u8]>>(option: &IPv6EndpointOption<T>) -> Result<Self> {
+        option.header().check_length(21)?;
+        option.check_protocol()?;
+        
+        let protocol = TransportProtocol::from_u8(option.transport_protocol())
+            .ok_or(Error::InvalidProtocol(option.transport_protocol()))?;
+
+        Ok(IPv6EndpointOptionRepr {
+            ipv6_address: option.ipv6_address(),
+            protocol,
+            port: option.port(),
+        })
+    }
+
+    /// Emit this representation into a buffer.
+    ///
+    /// # Parameters
+    /// * `buffer` - 24-byte buffer to write the option into
+    ///
+    /// # Returns
+    /// Number of bytes written (always 24)
+    pub fn emit(&self, buffer: &mut [u8]) -> usize {
+        let mut header = OptionHeader::new_unchecked(&mut buffer[..4]);
+        header.set_length(21);
+        header.set_option_type(OptionType::IPv6Endpoint.as_u8());
+        
+        let mut option = IPv6EndpointOption::new_unchecked(buffer);
+        option.set_ipv6_address(self.ipv6_address);
+        option.set_transport_protocol(self.protocol.as_u8());
+        option.set_port(self.port);
+        
+        Self::buffer_len()
+    }
+
+    /// Get the wire format size of this option (always 24 bytes: 4 header + 20 payload).
+    pub const fn buffer_len() -> usize {
+        24
+    }
+
+    /// Get the address and port together.
+    ///
+    /// # Returns
+    /// `(address, port)`
+    pub fn addr_port(&self) -> ([u8; 16], u16) {
+        (self.ipv6_address, self.port)
+    }
+
+    /// Get the address, port and transport protocol together.
+    ///
+    /// # Returns
+    /// `(address, port, protocol)`
+    pub fn endpoint(&self) -> ([u8; 16], u16, TransportProtocol) {
+        (self.ipv6_address, self.port, self.protocol)
+    }
+
+    /// Replace the transport protocol, e.g. after converting from a `SocketAddrV6`.
+    ///
+    /// # Parameters
+    /// * `protocol` - The transport protocol
+    ///
+    /// # Returns
+    /// The representation with `protocol` set
+    pub fn with_protocol(mut self, protocol: TransportProtocol) -> Self {
+        self.protocol = protocol;
+        self
+    }
+}
+
+impl From<SocketAddrV6> for IPv6EndpointOptionRepr {
+    /// Convert a socket address into a UDP endpoint option. Flow info and scope ID are dropped.
+    fn from(addr: SocketAddrV6) -> Self {
+        IPv6EndpointOptionRepr {
+            ipv6_address: addr.ip().octets(),
+            protocol: TransportProtocol::UDP,
+            port: addr.port(),
+        }
+    }
+}
+
+impl From<IPv6EndpointOptionRepr> for SocketAddrV6 {
+    /// Convert an endpoint option into its socket address with zero flow info and scope ID, dropping the protocol.
+    fn from(repr: IPv6EndpointOptionRepr) -> Self {
+        SocketAddrV6::new(Ipv6Addr::from(repr.ipv6_address), repr.port, 0, 0)
+    }
+}
+
+/// High-level representation of a Load Balancing Option.
+///
+/// This provides a builder-style API for constructing and parsing load balancing options
+/// without manually managing byte arrays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoadBalancingOptionRepr {
+    /// Priority value (lower = higher priority)
+    pub priority: u16,
+    /// Weight for load distribution
+    pub weight: u16,
+}
+
+impl LoadBalancingOptionRepr {
+    /// Parse a LoadBalancingOption into a high-level representation.
+    ///
+    /// # Parameters
+    /// * `option` - The LoadBalancingOption to parse
+    ///
+    /// # Returns
+    /// LoadBalancingOptionRepr with all fields populated
+    ///
+    /// # Errors
+    /// Returns Error::LengthOverflow if the header length field is not 5
+    pub fn parse<T: AsRef<[u8]>>(option: &LoadBalancingOption<T>) -> Result<Self> {
+        option.header().check_length(5)?;
+        Ok(LoadBalancingOptionRepr {
+            priority: option.priority(),
+            weight: option.weight(),
+        })
+    }
+
+    /// Emit this representation into a buffer.
+    ///
+    /// # Parameters
+    /// * `buffer` - 8-byte buffer to write the option into
+    ///
+    /// # Returns
+    /// Number of bytes written (always 8)
+    pub fn emit(&self, buffer: &mut [u8]) -> usize {
+        let mut header = OptionHeader::new_unchecked(&mut buffer[..4]);
+        header.set_length(5);
+        header.set_option_type(OptionType::LoadBalancing.as_u8());
+        
+        let mut option = LoadBalancingOption::new_unchecked(buffer);
+        option.set_priority(self.priority);
+        option.set_weight(self.weight);
+        
+        Self::buffer_len()
+    }
+
+    /// Get the wire format size of this option (always 8 bytes: 4 header + 4 payload).
+    pub const fn buffer_len() -> usize {
+        8
+    }
+}
+
+/// High-level representation of an IPv4 Multicast Option (type 0x14).
+///
+/// The protocol is always UDP, so it isn't stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IPv4MulticastOptionRepr {
+    /// IPv4 multicast group address (4 bytes)
+    pub ipv4_address: [u8; 4],
+    /// Port number
+    pub port: u16,
+}
+
+impl IPv4MulticastOptionRepr {
+    /// Parse an IPv4MulticastOption into a high-level representation.
+    ///
+    /// # Parameters
+    /// * `option` - The IPv4MulticastOption to parse
+    ///
+    /// # Returns
+    /// IPv4MulticastOptionRepr with all fields populated
+    ///
+    /// # Errors
+    /// Returns Error::LengthOverflow if the header length field is wrong, and
+    /// Error::InvalidProtocol if protocol is not UDP
+    pub fn parse<T: AsRef<[u8]>>(option: &IPv4MulticastOption<T>) -> Result<Self> {
+        option.header().check_length(9)?;
+        option.check_protocol()?;
+        Ok(IPv4MulticastOptionRepr {
+            ipv4_address: option.ipv4_multicast_address(),
+            port: option.port(),
+        })
+    }
+
+    /// Emit this representation into a buffer.
+    ///
+    /// # Parameters
+    /// * `buffer` - 12-byte buffer to write the option into
+    ///
+    /// # Returns
+    /// Number of bytes written (always 12)
+    pub fn emit(&self, buffer: &mut [u8]) -> usize {
+        let mut header = OptionHeader::new_unchecked(&mut buffer[..4]);
+        header.set_length(9);
+        header.set_option_type(OptionType::IPv4Multicast.as_u8());
+        header.set_discardable_flag(DiscardableFlag::new());
+
+        buffer[4 + field::ipv4_multicast_option::RESERVED.start] = 0;
+        let mut option = IPv4MulticastOption::new_unchecked(buffer);
+        option.set_ipv4_multicast_address(self.ipv4_address);
+        option.set_transport_protocol(TransportProtocol::UDP.as_u8());
+        option.set_port(self.port);
+
+        Self::buffer_len()
+    }
+
+    /// Get the wire format size of this option (always 12 bytes: 4 header + 8 payload).
+    pub const fn buffer_len() -> usize {
+        12
+    }
+}
+
+/// High-level representation of an IPv6 Multicast Option (type 0x16).
+///
+/// The IPv6 analog of `IPv4MulticastOptionRepr`; the protocol is always UDP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IPv6MulticastOptionRepr {
+    /// IPv6 multicast group address (16 bytes)
+    pub ipv6_address: [u8; 16],
+    /// Port number
+    pub port: u16,
+}
+
+impl IPv6MulticastOptionRepr {
+    /// Parse an IPv6MulticastOption into a high-level representation.
+    ///
+    /// # Parameters
+    /// * `option` - The IPv6MulticastOption to parse
+    ///
+    /// # Returns
+    /// IPv6MulticastOptionRepr with all fields populated
+    ///
+    /// # Errors
+    /// Returns Error::LengthOverflow if the header length field is wrong, and
+    /// Error::InvalidProtocol if protocol is not UDP
+    pub fn parse<T: AsRef<[u8]>>(option: &IPv6MulticastOption<T>) -> Result<Self> {
+        option.header().check_length(21)?;
+        option.check_protocol()?;
+        Ok(IPv6MulticastOptionRepr {
+            ipv6_address: option.ipv6_multicast_address(),
+            port: option.port(),
+        })
+    }
+
+    /// Emit this representation into a buffer.
+    ///
+    /// # Parameters
+    /// * `buffer` - 24-byte buffer to write the option into
+    ///
+    /// # Returns
+    /// Number of bytes written (always 24)
+    pub fn emit(&self, buffer: &mut [u8]) -> usize {
+        let mut header = OptionHeader::new_unchecked(&mut buffer[..4]);
+        header.set_length(21);
+        header.set_option_type(OptionType::IPv6Multicast.as_u8());
+        header.set_discardable_flag(DiscardableFlag::new());
+
+        buffer[4 + field::ipv6_multicast_option::RESERVED.start] = 0;
+        let mut option = IPv6MulticastOption::new_unchecked(buffer);
+        option.set_ipv6_multicast_address(self.ipv6_address);
+        option.set_transport_protocol(TransportProtocol::UDP.as_u8());
+        option.set_port(self.port);
+
+        Self::buffer_len()
+    }
+
+    /// Get the wire format size of this option (always 24 bytes: 4 header + 20 payload).
+    pub const fn buffer_len() -> usize {
+        24
+    }
+}
+
+/// High-level representation of an IPv4 SD Endpoint Option (type 0x24).
+///
+/// Advertises the socket a peer should send SOME/IP-SD messages to. SD runs
+/// over UDP, so the constructors fix the protocol to UDP; set `protocol`
+/// directly for the rare setup that needs something else.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IPv4SdEndpointOptionRepr {
+    /// IPv4 address (4 bytes)
+    pub ipv4_address: [u8; 4],
+    /// Transport protocol (TCP=0x06, UDP=0x11)
+    pub protocol: TransportProtocol,
+    /// Port number
+    pub port: u16,
+}
+
+impl IPv4SdEndpointOptionRepr {
+    /// Parse an IPv4SdEndpointOption into a high-level representation.
+    ///
+    /// # Parameters
+    /// * `option` - The IPv4SdEndpointOption to parse
+    ///
+    /// # Returns
+    /// IPv4SdEndpointOptionRepr with all fields populated
+    ///
+    /// # Errors
+    /// Returns Error::LengthOverflow if the header length field is wrong, and
+    /// Error::InvalidProtocol if protocol is not TCP or UDP
+    pub fn parse<T: AsRef<[u8]>>(option: &IPv4SdEndpointOption<T>) -> Result<Self> {
+        option.header().check_length(9)?;
+        let protocol = TransportProtocol::from_u8(option.transport_protocol())
+            .ok_or(Error::InvalidProtocol(option.transport_protocol()))?;
+
+        Ok(IPv4SdEndpointOptionRepr {
+            ipv4_address: option.ipv4_address(),
+            protocol,
+            port: option.port(),
+        })
+    }
+
+    /// Create a UDP SD endpoint.
+    ///
+    /// # Parameters
+    /// * `addr` - IPv4 address of the SD socket
+    /// * `port` - Port of the SD socket
+    pub fn udp(addr: [u8; 4], port: u16) -> Self {
+        IPv4SdEndpointOptionRepr {
+            ipv4_address: addr,
+            protocol: TransportProtocol::UDP,
+            port,
+        }
+    }
+
+    /// Create a UDP SD endpoint on `DEFAULT_SD_PORT`.
+    ///
+    /// # Parameters
+    /// * `addr` - IPv4 address of the SD socket
+    pub fn default_port(addr: [u8; 4]) -> Self {
+        Self::udp(addr, DEFAULT_SD_PORT)
+    }
+
+    /// Emit this representation into a buffer.
+    ///
+    /// # Parameters
+    /// * `buffer` - 12-byte buffer to write the option into
+    ///
+    /// # Returns
+    /// Number of bytes written (always 12)
+    pub fn emit(&self, buffer: &mut [u8]) -> usize {
+        let mut header = OptionHeader::new_unchecked(&mut buffer[..4]);
+        header.set_length(9);
+        header.set_option_type(OptionType::IPv4SdEndpoint.as_u8());
+        header.set_discardable_flag(DiscardableFlag::new());
+
+        let payload = &mut buffer[4..Self::buffer_len()];
+        payload[field::ipv4_sd_endpoint_option::IPV4_SD_ENDPOINT_ADDRESS].copy_from_slice(&self.ipv4_address);
+        payload[field::ipv4_sd_endpoint_option::RESERVED.start] = 0;
+        payload[field::ipv4_sd_endpoint_option::TRANSPORT_PROTOCOL.start] = self.protocol.as_u8();
+        NetworkEndian::write_u16(&mut payload[field::ipv4_sd_endpoint_option::PORT], self.port);
+
+        Self::buffer_len()
+    }
+
+    /// Get the wire format size of this option (always 12 bytes: 4 header + 8 payload).
+    pub const fn buffer_len() -> usize {
+        12
+    }
+}
+
+/// High-level representation of an IPv6 SD Endpoint Option (type 0x26).
+///
+/// The IPv6 analog of `IPv4SdEndpointOptionRepr`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IPv6SdEndpointOptionRepr {
+    /// IPv6 address (16 bytes)
+    pub ipv6_address: [u8; 16],
+    /// Transport protocol (TCP=0x06, UDP=0x11)
+    pub protocol: TransportProtocol,
+    /// Port number
+    pub port: u16,
+}
+
+impl IPv6SdEndpointOptionRepr {
+    /// Parse an IPv6SdEndpointOption into a high-level representation.
+    ///
+    /// # Parameters
+    /// * `option` - The IPv6SdEndpointOption to parse
+    ///
+    /// # Returns
+    /// IPv6SdEndpointOptionRepr with all fields populated
+    ///
+    /// # Errors
+    /// Returns Error::LengthOverflow if the header length field is wrong, and
+    /// Error::InvalidProtocol if protocol is not TCP or UDP
+    pub fn parse<T: AsRef<[u8]>>(option: &IPv6SdEndpointOption<T>) -> Result<Self> {
+        option.header().check_length(21)?;
+        let protocol = TransportProtocol::from_u8(option.transport_protocol())
+            .ok_or(Error::InvalidProtocol(option.transport_protocol()))?;
+
+        Ok(IPv6SdEndpointOptionRepr {
+            ipv6_address: option.ipv6_address(),
+            protocol,
+            port: option.port(),
+        })
+    }
+
+    /// Create a UDP SD endpoint.
+    ///
+    /// # Parameters
+    /// * `addr` - IPv6 address of the SD socket
+    /// * `port` - Port of the SD socket
+    pub fn udp(addr: [u8; 16], port: u16) -> Self {
+        IPv6SdEndpointOptionRepr {
+            ipv6_address: addr,
+            protocol: TransportProtocol::UDP,
+            port,
+        }
+    }
+
+    /// Create a UDP SD endpoint on `DEFAULT_SD_PORT`.
+    ///
+    /// # Parameters
+    /// * `addr` - IPv6 address of the SD socket
+    pub fn default_port(addr: [u8; 16]) -> Self {
+        Self::udp(addr, DEFAULT_SD_PORT)
+    }
+
+    /// Emit this representation into a buffer.
     ///
     /// # Parameters
     /// * `buffer` - 24-byte buffer to write the option into
@@ -796,13 +2433,15 @@ impl IPv6EndpointOptionRepr {
     pub fn emit(&self, buffer: &mut [u8]) -> usize {
         let mut header = OptionHeader::new_unchecked(&mut buffer[..4]);
         header.set_length(21);
-        header.set_option_type(OptionType::IPv6Endpoint.as_u8());
-        
-        let mut option = IPv6EndpointOption::new_unchecked(buffer);
-        option.set_ipv6_address(self.ipv6_address);
-        option.set_transport_protocol(self.protocol.as_u8());
-        option.set_port(self.port);
-        
+        header.set_option_type(OptionType::IPv6SdEndpoint.as_u8());
+        header.set_discardable_flag(DiscardableFlag::new());
+
+        let payload = &mut buffer[4..Self::buffer_len()];
+        payload[field::ipv6_sd_endpoint_option::IPV6_SD_ENDPOINT_ADDRESS].copy_from_slice(&self.ipv6_address);
+        payload[field::ipv6_sd_endpoint_option::RESERVED.start] = 0;
+        payload[field::ipv6_sd_endpoint_option::TRANSPORT_PROTOCOL.start] = self.protocol.as_u8();
+        NetworkEndian::write_u16(&mut payload[field::ipv6_sd_endpoint_option::PORT], self.port);
+
         Self::buffer_len()
     }
 
@@ -812,55 +2451,325 @@ impl IPv6EndpointOptionRepr {
     }
 }
 
-/// High-level representation of a Load Balancing Option.
+impl From<IPv4SdEndpointOptionRepr> for EndpointInfo {
+    fn from(repr: IPv4SdEndpointOptionRepr) -> Self {
+        EndpointInfo::IPv4 {
+            address: repr.ipv4_address,
+            protocol: repr.protocol,
+            port: repr.port,
+        }
+    }
+}
+
+impl From<IPv6SdEndpointOptionRepr> for EndpointInfo {
+    fn from(repr: IPv6SdEndpointOptionRepr) -> Self {
+        EndpointInfo::IPv6 {
+            address: repr.ipv6_address,
+            protocol: repr.protocol,
+            port: repr.port,
+        }
+    }
+}
+
+/// High-level representation of any SOME/IP-SD option.
 ///
-/// This provides a builder-style API for constructing and parsing load balancing options
-/// without manually managing byte arrays.
+/// Used wherever options of different types are handled together, e.g. when
+/// building or splitting a message.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct LoadBalancingOptionRepr {
-    /// Priority value (lower = higher priority)
-    pub priority: u16,
-    /// Weight for load distribution
-    pub weight: u16,
+pub enum OptionRepr<'a> {
+    /// Configuration option carrying DNS-SD TXT record data, as produced by
+    /// `ConfigurationOption::serialize` (including the terminating zero).
+    Configuration(&'a [u8]),
+    /// Load balancing option.
+    LoadBalancing(LoadBalancingOptionRepr),
+    /// IPv4 endpoint option.
+    IPv4Endpoint(IPv4EndpointOptionRepr),
+    /// IPv6 endpoint option.
+    IPv6Endpoint(IPv6EndpointOptionRepr),
+    /// IPv4 multicast option.
+    IPv4Multicast(IPv4MulticastOptionRepr),
+    /// IPv6 multicast option.
+    IPv6Multicast(IPv6MulticastOptionRepr),
+    /// IPv4 SD endpoint option.
+    IPv4SdEndpoint(IPv4SdEndpointOptionRepr),
+    /// IPv6 SD endpoint option.
+    IPv6SdEndpoint(IPv6SdEndpointOptionRepr),
+    /// Any other option, kept as its complete wire bytes (header included).
+    Raw(&'a [u8]),
 }
 
-impl LoadBalancingOptionRepr {
-    /// Parse a LoadBalancingOption into a high-level representation.
+impl<'a> OptionRepr<'a> {
+    /// Parse the option at the start of `buffer`.
+    ///
+    /// Option types without a dedicated representation are returned as
+    /// `OptionRepr::Raw`.
     ///
     /// # Parameters
-    /// * `option` - The LoadBalancingOption to parse
+    /// * `buffer` - Buffer starting with the option (e.g. a slice of an options array)
     ///
     /// # Returns
-    /// LoadBalancingOptionRepr with all fields populated
-    pub fn parse<T: AsRef<[u8]>>(option: &LoadBalancingOption<T>) -> Self {
-        LoadBalancingOptionRepr {
-            priority: option.priority(),
-            weight: option.weight(),
+    /// * `Ok(OptionRepr)` for a well-formed option
+    /// * `Err(Error)` if the header is malformed, a known option has the wrong
+    ///   length, or an endpoint option has an invalid protocol
+    pub fn parse(buffer: &'a [u8]) -> Result<Self> {
+        let len = option_wire_len(buffer)?;
+        let option = &buffer[..len];
+        let header = OptionHeader::new_unchecked(option);
+        match OptionType::from_u8(header.option_type()) {
+            Some(OptionType::Configuration) => {
+                Ok(OptionRepr::Configuration(&option[OptionHeader::<&[u8]>::LENGTH..]))
+            }
+            Some(OptionType::LoadBalancing) => Ok(OptionRepr::LoadBalancing(LoadBalancingOptionRepr::parse(
+                &LoadBalancingOption::parse_from(option)?,
+            )?)),
+            Some(OptionType::IPv4Endpoint) => Ok(OptionRepr::IPv4Endpoint(IPv4EndpointOptionRepr::parse(
+                &IPv4EndpointOption::parse_from(option)?,
+            )?)),
+            Some(OptionType::IPv6Endpoint) => Ok(OptionRepr::IPv6Endpoint(IPv6EndpointOptionRepr::parse(
+                &IPv6EndpointOption::parse_from(option)?,
+            )?)),
+            Some(OptionType::IPv4Multicast) => Ok(OptionRepr::IPv4Multicast(IPv4MulticastOptionRepr::parse(
+                &IPv4MulticastOption::parse_from(option)?,
+            )?)),
+            Some(OptionType::IPv6Multicast) => Ok(OptionRepr::IPv6Multicast(IPv6MulticastOptionRepr::parse(
+                &IPv6MulticastOption::parse_from(option)?,
+            )?)),
+            Some(OptionType::IPv4SdEndpoint) => Ok(OptionRepr::IPv4SdEndpoint(IPv4SdEndpointOptionRepr::parse(
+                &IPv4SdEndpointOption::parse_from(option)?,
+            )?)),
+            Some(OptionType::IPv6SdEndpoint) => Ok(OptionRepr::IPv6SdEndpoint(IPv6SdEndpointOptionRepr::parse(
+                &IPv6SdEndpointOption::parse_from(option)?,
+            )?)),
+            _ => Ok(OptionRepr::Raw(option)),
         }
     }
 
     /// Emit this representation into a buffer.
     ///
     /// # Parameters
-    /// * `buffer` - 9-byte buffer to write the option into
+    /// * `buffer` - Buffer of at least `self.buffer_len()` bytes
     ///
     /// # Returns
-    /// Number of bytes written (always 9)
+    /// Number of bytes written
     pub fn emit(&self, buffer: &mut [u8]) -> usize {
-        let mut header = OptionHeader::new_unchecked(&mut buffer[..4]);
-        header.set_length(5);
-        header.set_option_type(OptionType::LoadBalancing.as_u8());
-        
-        let mut option = LoadBalancingOption::new_unchecked(buffer);
-        option.set_priority(self.priority);
-        option.set_weight(self.weight);
-        
-        Self::buffer_len()
+        match self {
+            OptionRepr::Configuration(data) => {
+                let mut header = OptionHeader::new_unchecked(&mut buffer[..4]);
+                // Length covers the discardable/reserved byte plus the TXT data
+                header.set_length((1 + data.len()) as u16);
+                header.set_option_type(OptionType::Configuration.as_u8());
+                header.set_discardable_flag(DiscardableFlag::new());
+                buffer[4..4 + data.len()].copy_from_slice(data);
+                self.buffer_len()
+            }
+            OptionRepr::LoadBalancing(repr) => repr.emit(buffer),
+            OptionRepr::IPv4Endpoint(repr) => repr.emit(buffer),
+            OptionRepr::IPv6Endpoint(repr) => repr.emit(buffer),
+            OptionRepr::IPv4Multicast(repr) => repr.emit(buffer),
+            OptionRepr::IPv6Multicast(repr) => repr.emit(buffer),
+            OptionRepr::IPv4SdEndpoint(repr) => repr.emit(buffer),
+            OptionRepr::IPv6SdEndpoint(repr) => repr.emit(buffer),
+            OptionRepr::Raw(bytes) => {
+                buffer[..bytes.len()].copy_from_slice(bytes);
+                bytes.len()
+            }
+        }
     }
 
-    /// Get the wire format size of this option (always 9 bytes: 4 header + 5 payload).
-    pub const fn buffer_len() -> usize {
-        9
+    /// Get the wire format size of this option (header + payload).
+    pub fn buffer_len(&self) -> usize {
+        match self {
+            OptionRepr::Configuration(data) => OptionHeader::<&[u8]>::LENGTH + data.len(),
+            OptionRepr::LoadBalancing(_) => LoadBalancingOptionRepr::buffer_len(),
+            OptionRepr::IPv4Endpoint(_) => IPv4EndpointOptionRepr::buffer_len(),
+            OptionRepr::IPv6Endpoint(_) => IPv6EndpointOptionRepr::buffer_len(),
+            OptionRepr::IPv4Multicast(_) => IPv4MulticastOptionRepr::buffer_len(),
+            OptionRepr::IPv6Multicast(_) => IPv6MulticastOptionRepr::buffer_len(),
+            OptionRepr::IPv4SdEndpoint(_) => IPv4SdEndpointOptionRepr::buffer_len(),
+            OptionRepr::IPv6SdEndpoint(_) => IPv6SdEndpointOptionRepr::buffer_len(),
+            OptionRepr::Raw(bytes) => bytes.len(),
+        }
+    }
+}
+
+/// Zero-copy view of any SOME/IP-SD option, dispatched on the type byte.
+///
+/// Known option types are returned as their zero-copy wrapper (the
+/// configuration option as its TXT data); unknown types as
+/// `SdOption::Unknown`.
+#[derive(Debug, Clone, Copy)]
+pub enum SdOption<'a> {
+    /// Configuration option; holds the DNS-SD TXT data following the header.
+    Configuration(&'a [u8]),
+    /// Load balancing option.
+    LoadBalancing(LoadBalancingOption<&'a [u8]>),
+    /// IPv4 endpoint option.
+    IPv4Endpoint(IPv4EndpointOption<&'a [u8]>),
+    /// IPv6 endpoint option.
+    IPv6Endpoint(IPv6EndpointOption<&'a [u8]>),
+    /// IPv4 multicast option.
+    IPv4Multicast(IPv4MulticastOption<&'a [u8]>),
+    /// IPv6 multicast option.
+    IPv6Multicast(IPv6MulticastOption<&'a [u8]>),
+    /// IPv4 SD endpoint option.
+    IPv4SdEndpoint(IPv4SdEndpointOption<&'a [u8]>),
+    /// IPv6 SD endpoint option.
+    IPv6SdEndpoint(IPv6SdEndpointOption<&'a [u8]>),
+    /// Option of unknown type.
+    Unknown {
+        /// Raw option type byte
+        type_byte: u8,
+        /// Option payload following the 4-byte header
+        data: &'a [u8],
+    },
+}
+
+impl<'a> SdOption<'a> {
+    /// Parse the option at the start of `buffer`.
+    ///
+    /// # Parameters
+    /// * `buffer` - Buffer starting with the option (e.g. a slice of an options array)
+    ///
+    /// # Returns
+    /// * `Ok((SdOption, usize))` - The option and its total wire size
+    /// * `Err(Error::BufferTooShort)` if the header doesn't fit
+    /// * `Err(Error::LengthOverflow)` if the length field is zero, exceeds the
+    ///   buffer, or doesn't match a fixed-size option type
+    pub fn parse(buffer: &'a [u8]) -> Result<(Self, usize)> {
+        let len = option_wire_len(buffer)?;
+        let option = &buffer[..len];
+        let type_byte = OptionHeader::new_unchecked(option).option_type();
+
+        let parsed = match OptionType::from_u8(type_byte) {
+            Some(OptionType::Configuration) => SdOption::Configuration(&option[OptionHeader::<&[u8]>::LENGTH..]),
+            Some(OptionType::LoadBalancing) => SdOption::LoadBalancing(LoadBalancingOption::parse_from(option)?),
+            Some(OptionType::IPv4Endpoint) => SdOption::IPv4Endpoint(IPv4EndpointOption::parse_from(option)?),
+            Some(OptionType::IPv6Endpoint) => SdOption::IPv6Endpoint(IPv6EndpointOption::parse_from(option)?),
+            Some(OptionType::IPv4Multicast) => SdOption::IPv4Multicast(IPv4MulticastOption::parse_from(option)?),
+            Some(OptionType::IPv6Multicast) => SdOption::IPv6Multicast(IPv6MulticastOption::parse_from(option)?),
+            Some(OptionType::IPv4SdEndpoint) => SdOption::IPv4SdEndpoint(IPv4SdEndpointOption::parse_from(option)?),
+            Some(OptionType::IPv6SdEndpoint) => SdOption::IPv6SdEndpoint(IPv6SdEndpointOption::parse_from(option)?),
+            None => SdOption::Unknown {
+                type_byte,
+                data: &option[OptionHeader::<&[u8]>::LENGTH..],
+            },
+        };
+        Ok((parsed, len))
+    }
+
+    /// Get the raw option type byte, for known and unknown options alike.
+    pub fn type_byte(&self) -> u8 {
+        let option_type = match self {
+            SdOption::Configuration(_) => OptionType::Configuration,
+            SdOption::LoadBalancing(_) => OptionType::LoadBalancing,
+            SdOption::IPv4Endpoint(_) => OptionType::IPv4Endpoint,
+            SdOption::IPv6Endpoint(_) => OptionType::IPv6Endpoint,
+            SdOption::IPv4Multicast(_) => OptionType::IPv4Multicast,
+            SdOption::IPv6Multicast(_) => OptionType::IPv6Multicast,
+            SdOption::IPv4SdEndpoint(_) => OptionType::IPv4SdEndpoint,
+            SdOption::IPv6SdEndpoint(_) => OptionType::IPv6SdEndpoint,
+            SdOption::Unknown { type_byte, .. } => return *type_byte,
+        };
+        option_type.as_u8()
+    }
+}
+
+/// Iterator over the options of an options array.
+///
+/// In strict mode (`new`) an option of unknown type yields
+/// `Error::InvalidOptionType` unless its discardable flag is set, in which
+/// case the receiver may skip it and it's yielded as `SdOption::Unknown`. In
+/// lenient mode (`new_lenient`) every unknown option yields
+/// `SdOption::Unknown`. Since options are variable-length, a malformed header
+/// yields one error and ends the iteration.
+#[derive(Debug, Clone)]
+pub struct OptionsIter<'a> {
+    data: &'a [u8],
+    pos: usize,
+    lenient: bool,
+}
+
+impl<'a> OptionsIter<'a> {
+    /// Create a strict iterator over an options array.
+    ///
+    /// # Parameters
+    /// * `data` - The options array (e.g. `Repr::options`)
+    pub fn new(data: &'a [u8]) -> Self {
+        OptionsIter { data, pos: 0, lenient: false }
+    }
+
+    /// Create a lenient iterator that yields unknown option types as `SdOption::Unknown`.
+    ///
+    /// # Parameters
+    /// * `data` - The options array (e.g. `Repr::options`)
+    pub fn new_lenient(data: &'a [u8]) -> Self {
+        OptionsIter { data, pos: 0, lenient: true }
+    }
+}
+
+impl<'a> Iterator for OptionsIter<'a> {
+    type Item = Result<SdOption<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.data.len() {
+            return None;
+        }
+
+        let start = self.pos;
+        match SdOption::parse(&self.data[start..]) {
+            Ok((option, len)) => {
+                self.pos += len;
+                match option {
+                    SdOption::Unknown { type_byte, .. }
+                        if !self.lenient
+                            && !OptionHeader::new_unchecked(&self.data[start..]).discardable_flag().is_discardable() =>
+                    {
+                        Some(Err(Error::InvalidOptionType(type_byte)))
+                    }
+                    option => Some(Ok(option)),
+                }
+            }
+            Err(e) => {
+                self.pos = self.data.len();
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// A decoded option tagged with the offset where it begins.
+///
+/// The offset is relative to the start of the options array, so it can be
+/// handed to `Packet::rewrite_option_at` without walking the array again.
+#[derive(Debug, Clone, Copy)]
+pub struct SdOptionAt<'a> {
+    /// Byte offset of the option within the options array
+    pub offset: usize,
+    /// The decoded option
+    pub option: SdOption<'a>,
+}
+
+/// Iterator over options with their offsets, created by `OptionsIter::with_offsets`.
+#[derive(Debug, Clone)]
+pub struct OptionsAtIter<'a> {
+    inner: OptionsIter<'a>,
+}
+
+impl<'a> OptionsIter<'a> {
+    /// Tag every yielded option with its offset in the options array.
+    pub fn with_offsets(self) -> OptionsAtIter<'a> {
+        OptionsAtIter { inner: self }
+    }
+}
+
+impl<'a> Iterator for OptionsAtIter<'a> {
+    type Item = Result<SdOptionAt<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.inner.pos;
+        self.inner
+            .next()
+            .map(|option| option.map(|option| SdOptionAt { offset, option }))
     }
 }
 
@@ -911,6 +2820,63 @@ mod tests {
         assert_eq!(option.port(), 30490);
     }
 
+    #[test]
+    fn test_ipv4_multicast_option() {
+        let mut buffer = [0u8; 12];
+        let mut option = IPv4MulticastOption::new_unchecked(&mut buffer[..]);
+        option.set_ipv4_multicast_address([239, 0, 0, 1]);
+        option.set_transport_protocol(TransportProtocol::UDP.as_u8());
+        option.set_port(30501);
+
+        assert_eq!(option.ipv4_multicast_address(), [239, 0, 0, 1]);
+        assert_eq!(option.port(), 30501);
+        assert_eq!(option.check_protocol(), Ok(()));
+        option.set_transport_protocol(TransportProtocol::TCP.as_u8());
+        assert_eq!(option.check_protocol(), Err(Error::InvalidProtocol(0x06)));
+        assert!(IPv4MulticastOption::new_checked(&buffer[..11]).is_err());
+
+        let repr = IPv4MulticastOptionRepr { ipv4_address: [239, 0, 0, 1], port: 30501 };
+        let mut buffer = [0xFFu8; 12];
+        assert_eq!(repr.emit(&mut buffer), 12);
+        assert_eq!(buffer, [0x00, 0x09, 0x14, 0x00, 239, 0, 0, 1, 0x00, 0x11, 0x77, 0x25]);
+        let option = IPv4MulticastOption::parse_from(&buffer[..]).unwrap();
+        assert_eq!(IPv4MulticastOptionRepr::parse(&option), Ok(repr));
+        assert_eq!(OptionRepr::parse(&buffer), Ok(OptionRepr::IPv4Multicast(repr)));
+        assert_eq!(OptionRepr::IPv4Multicast(repr).buffer_len(), 12);
+
+        buffer[2] = 0x04;
+        assert_eq!(IPv4MulticastOption::parse_from(&buffer[..]).err(), Some(Error::InvalidOptionType(0x04)));
+    }
+
+    #[test]
+    fn test_ipv6_multicast_option() {
+        let addr = [0xff, 0x02, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x42];
+        let mut buffer = [0u8; 24];
+        let mut option = IPv6MulticastOption::new_unchecked(&mut buffer[..]);
+        option.set_ipv6_multicast_address(addr);
+        option.set_transport_protocol(TransportProtocol::UDP.as_u8());
+        option.set_port(30502);
+        assert_eq!(option.ipv6_multicast_address(), addr);
+        assert_eq!(option.port(), 30502);
+        assert_eq!(option.header().length(), 0);
+
+        let repr = IPv6MulticastOptionRepr { ipv6_address: addr, port: 30502 };
+        let mut buffer = [0xFFu8; 24];
+        assert_eq!(repr.emit(&mut buffer), 24);
+        assert_eq!(buffer[..4], [0x00, 0x15, 0x16, 0x00]);
+        assert_eq!(buffer[4..20], addr);
+        // Reserved byte cleared, UDP, port
+        assert_eq!(buffer[20..], [0x00, 0x11, 0x77, 0x26]);
+        let option = IPv6MulticastOption::parse_from(&buffer[..]).unwrap();
+        assert_eq!(IPv6MulticastOptionRepr::parse(&option), Ok(repr));
+        assert_eq!(OptionRepr::parse(&buffer), Ok(OptionRepr::IPv6Multicast(repr)));
+
+        buffer[21] = 0x42;
+        let option = IPv6MulticastOption::parse_from(&buffer[..]).unwrap();
+        assert_eq!(IPv6MulticastOptionRepr::parse(&option), Err(Error::InvalidProtocol(0x42)));
+        assert!(IPv6MulticastOption::new_checked(&buffer[..23]).is_err());
+    }
+
     #[test]
     fn test_load_balancing_option() {
         let mut buffer = [0u8; 8];
@@ -923,6 +2889,425 @@ mod tests {
         assert_eq!(option.weight(), 50);
     }
 
+    #[test]
+    fn test_options_network_byte_order() {
+        let mut buffer = [0u8; 4];
+        let mut header = OptionHeader::new_unchecked(&mut buffer[..]);
+        header.set_length(0x0102);
+        assert_eq!(buffer[0..2], [0x01, 0x02]);
+
+        let mut buffer = [0u8; 12];
+        let mut option = IPv4EndpointOption::new_unchecked(&mut buffer[..]);
+        option.set_ipv4_address([0x0A, 0x0B, 0x0C, 0x0D]);
+        option.set_port(0x0304);
+        assert_eq!(buffer[4..8], [0x0A, 0x0B, 0x0C, 0x0D]);
+        assert_eq!(buffer[10..12], [0x03, 0x04]);
+
+        let mut buffer = [0u8; 24];
+        let mut option = IPv6EndpointOption::new_unchecked(&mut buffer[..]);
+        option.set_port(0x0506);
+        assert_eq!(buffer[22..24], [0x05, 0x06]);
+
+        let mut buffer = [0u8; 8];
+        let mut option = LoadBalancingOption::new_unchecked(&mut buffer[..]);
+        option.set_priority(0x0708);
+        option.set_weight(0x090A);
+        assert_eq!(buffer[4..6], [0x07, 0x08]);
+        assert_eq!(buffer[6..8], [0x09, 0x0A]);
+    }
+
+    #[test]
+    fn test_option_repr_emit() {
+        let lb = OptionRepr::LoadBalancing(LoadBalancingOptionRepr { priority: 1, weight: 2 });
+        assert_eq!(lb.buffer_len(), 8);
+        let mut buf = [0u8; 8];
+        assert_eq!(lb.emit(&mut buf), 8);
+        assert_eq!(option_wire_len(&buf), Ok(8));
+
+        let config = OptionRepr::Configuration(b"\x05a=xyz\x00");
+        assert_eq!(config.buffer_len(), 11);
+        let mut buf = [0u8; 11];
+        assert_eq!(config.emit(&mut buf), 11);
+        assert_eq!(buf[..4], [0x00, 0x08, 0x01, 0x00]);
+        assert_eq!(option_wire_len(&buf), Ok(11));
+        assert_eq!(&buf[4..], b"\x05a=xyz\x00");
+        assert_eq!(OptionRepr::parse(&buf), Ok(config));
+
+        let mut buf = [0u8; 12];
+        IPv4SdEndpointOptionRepr::default_port([10, 0, 0, 1]).emit(&mut buf);
+        assert_eq!(
+            OptionRepr::parse(&buf),
+            Ok(OptionRepr::IPv4SdEndpoint(IPv4SdEndpointOptionRepr::default_port([10, 0, 0, 1])))
+        );
+        buf[2] = 0x42;
+        assert_eq!(OptionRepr::parse(&buf), Ok(OptionRepr::Raw(&buf[..])));
+        assert_eq!(OptionRepr::Raw(&buf).buffer_len(), 12);
+
+        let ep = OptionRepr::IPv4Endpoint(IPv4EndpointOptionRepr {
+            ipv4_address: [10, 0, 0, 2],
+            protocol: TransportProtocol::UDP,
+            port: 30509,
+        });
+        ep.emit(&mut buf);
+        assert_eq!(OptionRepr::parse(&buf), Ok(ep));
+        buf[9] = 0x01;
+        assert_eq!(OptionRepr::parse(&buf), Err(Error::InvalidProtocol(0x01)));
+    }
+
+    #[test]
+    fn test_sd_endpoint_from_sd_option() {
+        let repr = IPv4SdEndpointOptionRepr::default_port([192, 168, 0, 1]);
+        let ep = EndpointInfo::from(repr);
+        assert_eq!(ep.port(), DEFAULT_SD_PORT);
+        assert_eq!(ep.protocol(), TransportProtocol::UDP);
+
+        let mut buf = [0u8; 12];
+        assert_eq!(repr.emit(&mut buf), 12);
+        assert_eq!(buf[..4], [0x00, 0x09, 0x24, 0x00]);
+        assert_eq!(EndpointInfo::from_sd_option(&buf), Ok(Some(ep)));
+        // A bad length field is rejected
+        buf[1] = 0x0A;
+        assert_eq!(EndpointInfo::from_sd_option(&buf), Err(Error::LengthOverflow));
+
+        let repr = IPv6SdEndpointOptionRepr::udp([0xFF; 16], 30491);
+        let mut buf = [0u8; 24];
+        assert_eq!(repr.emit(&mut buf), 24);
+        assert_eq!(buf[..4], [0x00, 0x15, 0x26, 0x00]);
+        assert_eq!(EndpointInfo::from_sd_option(&buf), Ok(Some(repr.into())));
+
+        let mut buf = [0u8; 12];
+        IPv4EndpointOptionRepr { ipv4_address: [10, 0, 0, 1], protocol: TransportProtocol::UDP, port: 1 }.emit(&mut buf);
+        assert_eq!(EndpointInfo::from_sd_option(&buf), Ok(None));
+    }
+
+    #[test]
+    fn test_option_parse_from() {
+        let mut buf = [0u8; 24];
+        IPv4EndpointOptionRepr {
+            ipv4_address: [10, 0, 0, 1],
+            protocol: TransportProtocol::TCP,
+            port: 30509,
+        }
+        .emit(&mut buf);
+        let option = IPv4EndpointOption::parse_from(&buf[..12]).unwrap();
+        assert_eq!(option.port(), 30509);
+        assert_eq!(IPv4EndpointOption::parse_from(&buf[..11]).err(), Some(Error::BufferTooShort));
+        assert_eq!(IPv6EndpointOption::parse_from(&buf).err(), Some(Error::InvalidOptionType(0x04)));
+        assert_eq!(LoadBalancingOption::parse_from(&buf).err(), Some(Error::InvalidOptionType(0x04)));
+        buf[1] = 0x0A;
+        assert_eq!(IPv4EndpointOption::parse_from(&buf).err(), Some(Error::LengthOverflow));
+
+        IPv6EndpointOptionRepr {
+            ipv6_address: [0xFE; 16],
+            protocol: TransportProtocol::UDP,
+            port: 1,
+        }
+        .emit(&mut buf);
+        assert!(IPv6EndpointOption::parse_from(&buf).is_ok());
+
+        LoadBalancingOptionRepr { priority: 1, weight: 2 }.emit(&mut buf);
+        assert_eq!(LoadBalancingOption::parse_from(&buf).unwrap().weight(), 2);
+    }
+
+    #[test]
+    fn test_options_iter() {
+        let mut options = [0u8; 12 + 8 + 12 + 6];
+        IPv4EndpointOptionRepr {
+            ipv4_address: [10, 0, 0, 1],
+            protocol: TransportProtocol::UDP,
+            port: 30509,
+        }
+        .emit(&mut options[..12]);
+        LoadBalancingOptionRepr { priority: 1, weight: 2 }.emit(&mut options[12..20]);
+        IPv4SdEndpointOptionRepr::default_port([10, 0, 0, 2]).emit(&mut options[20..32]);
+        // Unknown type 0x30 with a 2-byte payload
+        options[32..38].copy_from_slice(&[0x00, 0x03, 0x30, 0x80, 0xAB, 0xCD]);
+
+        let types: [u8; 4] = [0x04, 0x02, 0x24, 0x30];
+        let mut iter = OptionsIter::new_lenient(&options);
+        for expected in types {
+            assert_eq!(iter.next().unwrap().unwrap().type_byte(), expected);
+        }
+        assert!(iter.next().is_none());
+
+        let (option, len) = SdOption::parse(&options[32..]).unwrap();
+        assert_eq!(len, 6);
+        assert!(matches!(option, SdOption::Unknown { type_byte: 0x30, data: [0xAB, 0xCD] }));
+
+        // Strict mode yields the discardable unknown option as Unknown...
+        let mut iter = OptionsIter::new(&options);
+        assert!(matches!(iter.next(), Some(Ok(SdOption::IPv4Endpoint(_)))));
+        assert!(matches!(iter.next(), Some(Ok(SdOption::LoadBalancing(_)))));
+        assert!(matches!(iter.next(), Some(Ok(SdOption::IPv4SdEndpoint(_)))));
+        assert!(matches!(iter.next(), Some(Ok(SdOption::Unknown { type_byte: 0x30, .. }))));
+        assert!(iter.next().is_none());
+
+        // ...but rejects it without the discardable flag
+        options[35] = 0x00;
+        let mut iter = OptionsIter::new(&options).skip(3);
+        assert!(matches!(iter.next(), Some(Err(Error::InvalidOptionType(0x30)))));
+        assert!(iter.next().is_none());
+        assert!(matches!(OptionsIter::new_lenient(&options).nth(3), Some(Ok(SdOption::Unknown { .. }))));
+
+        let mut iter = OptionsIter::new(&options[..30]);
+        assert!(iter.next().unwrap().is_ok());
+        assert!(iter.next().unwrap().is_ok());
+        assert_eq!(iter.next().unwrap().err(), Some(Error::LengthOverflow));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_endpoint_repr_addr_port() {
+        let v4 = IPv4EndpointOptionRepr { ipv4_address: [10, 0, 0, 1], protocol: TransportProtocol::TCP, port: 30509 };
+        assert_eq!(v4.addr_port(), ([10, 0, 0, 1], 30509));
+        assert_eq!(v4.endpoint(), ([10, 0, 0, 1], 30509, TransportProtocol::TCP));
+
+        let v6 = IPv6EndpointOptionRepr { ipv6_address: [1; 16], protocol: TransportProtocol::UDP, port: 1 };
+        assert_eq!(v6.addr_port(), ([1; 16], 1));
+        assert_eq!(v6.endpoint(), ([1; 16], 1, TransportProtocol::UDP));
+    }
+
+    #[test]
+    fn test_option_as_bytes() {
+        let mut buffer = [0xAAu8; 32];
+        IPv4EndpointOptionRepr { ipv4_address: [10, 0, 0, 1], protocol: TransportProtocol::UDP, port: 30509 }
+            .emit(&mut buffer);
+        assert_eq!(IPv4EndpointOption::new_checked(&buffer[..]).unwrap().as_bytes(), &buffer[..12]);
+
+        IPv6EndpointOptionRepr { ipv6_address: [1; 16], protocol: TransportProtocol::TCP, port: 1 }.emit(&mut buffer);
+        assert_eq!(IPv6EndpointOption::new_checked(&buffer[..]).unwrap().as_bytes(), &buffer[..24]);
+
+        LoadBalancingOptionRepr { priority: 1, weight: 2 }.emit(&mut buffer);
+        let option = LoadBalancingOption::new_checked(&buffer[..]).unwrap();
+        assert_eq!(option.as_bytes().len(), 8);
+        assert_eq!(LoadBalancingOption::parse_from(option.as_bytes()).map(|o| o.priority()), Ok(1));
+    }
+
+    #[test]
+    fn test_same_logical_endpoint() {
+        let endpoint = |address: [u8; 4], protocol, port| {
+            let mut buf = [0u8; 12];
+            IPv4EndpointOptionRepr { ipv4_address: address, protocol, port }.emit(&mut buf);
+            buf
+        };
+        let a = endpoint([10, 0, 0, 1], TransportProtocol::UDP, 30509);
+        let mut b = a;
+        b[3] = 0x80;
+        b[8] = 0x01;
+        assert_eq!(same_logical_endpoint(&a, &b), Ok(true));
+        assert_eq!(same_logical_endpoint(&a, &endpoint([10, 0, 0, 1], TransportProtocol::TCP, 30509)), Ok(false));
+        assert_eq!(same_logical_endpoint(&a, &endpoint([10, 0, 0, 1], TransportProtocol::UDP, 30510)), Ok(false));
+        assert_eq!(same_logical_endpoint(&a, &endpoint([10, 0, 0, 2], TransportProtocol::UDP, 30509)), Ok(false));
+
+        // Same address and port, but a multicast option is a different thing
+        let mut multicast = a;
+        multicast[2] = OptionType::IPv4Multicast.as_u8();
+        assert_eq!(same_logical_endpoint(&a, &multicast), Ok(false));
+
+        let mut v6 = [0u8; 24];
+        IPv6EndpointOptionRepr { ipv6_address: [0; 16], protocol: TransportProtocol::UDP, port: 30509 }.emit(&mut v6);
+        assert_eq!(same_logical_endpoint(&a, &v6), Ok(false));
+
+        let mut balancing = [0u8; 8];
+        LoadBalancingOptionRepr { priority: 1, weight: 2 }.emit(&mut balancing);
+        assert_eq!(same_logical_endpoint(&a, &balancing), Err(Error::InvalidOptionType(0x02)));
+        assert_eq!(same_logical_endpoint(&a, &a[..11]), Err(Error::BufferTooShort));
+    }
+
+    #[test]
+    fn test_options_with_offsets() {
+        let mut options = [0u8; 20];
+        LoadBalancingOptionRepr { priority: 1, weight: 2 }.emit(&mut options[..8]);
+        IPv4SdEndpointOptionRepr::default_port([10, 0, 0, 1]).emit(&mut options[8..]);
+
+        let mut iter = OptionsIter::new(&options).with_offsets();
+        let first = iter.next().unwrap().unwrap();
+        assert_eq!(first.offset, 0);
+        assert!(matches!(first.option, SdOption::LoadBalancing(_)));
+        let second = iter.next().unwrap().unwrap();
+        assert_eq!(second.offset, 8);
+        assert!(matches!(second.option, SdOption::IPv4SdEndpoint(_)));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_option_type_names() {
+        assert_eq!(OptionType::Configuration.name(), "Configuration");
+        assert_eq!(OptionType::IPv6SdEndpoint.name(), "IPv6SdEndpoint");
+        assert_eq!(OptionType::from_u8(0x14).map(|t| t.name()), Some("IPv4Multicast"));
+    }
+
+    #[test]
+    fn test_check_all_reserved() {
+        let mut options = [0u8; 20];
+        IPv4SdEndpointOptionRepr::default_port([10, 0, 0, 1]).emit(&mut options[..12]);
+        LoadBalancingOptionRepr { priority: 1, weight: 2 }.emit(&mut options[12..]);
+        options[12 + 3] = 0x80;
+        assert_eq!(check_all_reserved(&options), Ok(()));
+
+        options[12 + 3] = 0x81;
+        assert_eq!(check_all_reserved(&options), Err(Error::ReservedNotZero(1)));
+        assert_eq!(check_all_reserved(&options[..12]), Ok(()));
+        assert_eq!(check_all_reserved(&options[..14]), Err(Error::BufferTooShort));
+    }
+
+    #[test]
+    fn test_sd_endpoint_repr_udp() {
+        let repr = IPv4SdEndpointOptionRepr::default_port([192, 168, 0, 1]);
+        assert_eq!(repr, IPv4SdEndpointOptionRepr::udp([192, 168, 0, 1], DEFAULT_SD_PORT));
+        assert_eq!(repr.protocol, TransportProtocol::UDP);
+        let mut buf = [0u8; 12];
+        assert_eq!(repr.emit(&mut buf), 12);
+        assert_eq!(EndpointInfo::from_sd_option(&buf), Ok(Some(repr.into())));
+
+        let repr = IPv6SdEndpointOptionRepr::udp([0xFE; 16], 30491);
+        let mut buf = [0u8; 24];
+        assert_eq!(repr.emit(&mut buf), 24);
+        assert_eq!(buf[..4], [0x00, 0x15, 0x26, 0x00]);
+        assert_eq!(EndpointInfo::from_sd_option(&buf), Ok(Some(repr.into())));
+    }
+
+    #[test]
+    fn test_sd_endpoint_option_wrappers() {
+        let mut buffer = [0u8; 12];
+        IPv4SdEndpointOptionRepr::udp([192, 168, 0, 1], 30490).emit(&mut buffer);
+        let option = IPv4SdEndpointOption::parse_from(&buffer[..]).unwrap();
+        assert_eq!(option.header().option_type(), 0x24);
+        assert_eq!(option.ipv4_address(), [192, 168, 0, 1]);
+        assert_eq!(option.transport_protocol(), 0x11);
+        assert_eq!(option.port(), 30490);
+        assert_eq!(
+            IPv4SdEndpointOptionRepr::parse(&option),
+            Ok(IPv4SdEndpointOptionRepr::udp([192, 168, 0, 1], 30490))
+        );
+        let mut option = IPv4SdEndpointOption::new_unchecked(&mut buffer[..]);
+        option.set_transport_protocol(0x42);
+        assert_eq!(option.check_protocol(), Err(Error::InvalidProtocol(0x42)));
+        assert_eq!(IPv4SdEndpointOption::parse_from(&buffer[..11]).unwrap_err(), Error::BufferTooShort);
+
+        let mut buffer = [0u8; 24];
+        OptionHeader::new_unchecked(&mut buffer[..4]).set_length(21);
+        let mut option = IPv6SdEndpointOption::new_unchecked(&mut buffer[..]);
+        option.set_ipv6_address([0xFE; 16]);
+        option.set_transport_protocol(TransportProtocol::TCP.as_u8());
+        option.set_port(30491);
+        let repr = IPv6SdEndpointOptionRepr::parse(&option).unwrap();
+        assert_eq!((repr.ipv6_address, repr.protocol, repr.port), ([0xFE; 16], TransportProtocol::TCP, 30491));
+        assert_eq!(
+            IPv6SdEndpointOption::parse_from(&buffer[..]).unwrap_err(),
+            Error::InvalidOptionType(0x00)
+        );
+        repr.emit(&mut buffer);
+        assert_eq!(OptionRepr::parse(&buffer), Ok(OptionRepr::IPv6SdEndpoint(repr)));
+    }
+
+    #[test]
+    fn test_repr_parse_checks_header_length() {
+        let mut buffer = [0u8; 12];
+        let repr = IPv4EndpointOptionRepr {
+            ipv4_address: [10, 0, 0, 1],
+            protocol: TransportProtocol::UDP,
+            port: 30509,
+        };
+        repr.emit(&mut buffer);
+        assert_eq!(OptionHeader::new_unchecked(&buffer[..]).check_length(9), Ok(()));
+        assert_eq!(IPv4EndpointOptionRepr::parse(&IPv4EndpointOption::new_unchecked(&buffer[..])), Ok(repr));
+        buffer[1] = 5;
+        assert_eq!(OptionHeader::new_unchecked(&buffer[..]).check_length(9), Err(Error::LengthOverflow));
+        assert_eq!(
+            IPv4EndpointOptionRepr::parse(&IPv4EndpointOption::new_unchecked(&buffer[..])),
+            Err(Error::LengthOverflow)
+        );
+
+        let mut buffer = [0u8; 24];
+        IPv6MulticastOptionRepr { ipv6_address: [0xFF; 16], port: 30490 }.emit(&mut buffer);
+        buffer[1] = 25;
+        let option = IPv6MulticastOption::new_unchecked(&buffer[..]);
+        assert_eq!(IPv6MulticastOptionRepr::parse(&option), Err(Error::LengthOverflow));
+
+        let mut buffer = [0u8; 8];
+        LoadBalancingOptionRepr { priority: 1, weight: 2 }.emit(&mut buffer);
+        let option = LoadBalancingOption::new_unchecked(&buffer[..]);
+        assert_eq!(LoadBalancingOptionRepr::parse(&option), Ok(LoadBalancingOptionRepr { priority: 1, weight: 2 }));
+        buffer[1] = 4;
+        let option = LoadBalancingOption::new_unchecked(&buffer[..]);
+        assert_eq!(LoadBalancingOptionRepr::parse(&option), Err(Error::LengthOverflow));
+    }
+
+    #[test]
+    fn test_option_core_net_accessors() {
+        let mut buffer = [0u8; 12];
+        let mut option = IPv4EndpointOption::new_unchecked(&mut buffer[..]);
+        option.set_ipv4_addr(Ipv4Addr::new(192, 168, 0, 1));
+        option.set_port(30509);
+        assert_eq!(option.ipv4_address(), [192, 168, 0, 1]);
+        assert_eq!(option.socket_addr(), "192.168.0.1:30509".parse().unwrap());
+
+        let mut option = IPv4MulticastOption::new_unchecked(&mut buffer[..]);
+        option.set_ipv4_addr(Ipv4Addr::new(239, 0, 0, 1));
+        assert_eq!(option.ipv4_addr(), Ipv4Addr::new(239, 0, 0, 1));
+        let option = IPv4SdEndpointOption::new_unchecked(&buffer[..]);
+        assert_eq!(option.socket_addr(), SocketAddrV4::new(Ipv4Addr::new(239, 0, 0, 1), 30509));
+
+        let mut buffer = [0u8; 24];
+        let mut option = IPv6EndpointOption::new_unchecked(&mut buffer[..]);
+        option.set_ipv6_addr(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1));
+        option.set_port(30490);
+        assert_eq!(option.socket_addr(), "[fe80::1]:30490".parse().unwrap());
+        let mut option = IPv6MulticastOption::new_unchecked(&mut buffer[..]);
+        option.set_ipv6_addr(Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 0x42));
+        assert_eq!(option.ipv6_multicast_address()[..2], [0xff, 0x02]);
+        let mut option = IPv6SdEndpointOption::new_unchecked(&mut buffer[..]);
+        option.set_ipv6_addr(Ipv6Addr::LOCALHOST);
+        assert_eq!(option.socket_addr(), SocketAddrV6::new(Ipv6Addr::LOCALHOST, 30490, 0, 0));
+        assert_eq!(IPv6MulticastOption::new_unchecked(&buffer[..]).ipv6_addr(), Ipv6Addr::LOCALHOST);
+    }
+
+    #[test]
+    fn test_endpoint_repr_socket_addr_conversions() {
+        let addr: SocketAddrV4 = "192.168.0.1:30509".parse().unwrap();
+        let repr = IPv4EndpointOptionRepr::from(addr);
+        assert_eq!(repr.addr_port(), ([192, 168, 0, 1], 30509));
+        assert_eq!(repr.protocol, TransportProtocol::UDP);
+        let tcp = repr.with_protocol(TransportProtocol::TCP);
+        assert_eq!(tcp.protocol, TransportProtocol::TCP);
+        assert_eq!(tcp.addr_port(), repr.addr_port());
+        assert_eq!(SocketAddrV4::from(tcp), addr);
+
+        let addr = SocketAddrV6::new(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), 30490, 7, 3);
+        let repr: IPv6EndpointOptionRepr = addr.into();
+        assert_eq!(repr.protocol, TransportProtocol::UDP);
+        assert_eq!(repr.with_protocol(TransportProtocol::TCP).protocol, TransportProtocol::TCP);
+        assert_eq!(SocketAddrV6::from(repr), SocketAddrV6::new(*addr.ip(), 30490, 0, 0));
+    }
+
+    #[test]
+    fn test_offset_of() {
+        let mut options = [0u8; 12 + 8 + 24];
+        IPv4EndpointOptionRepr {
+            ipv4_address: [192, 168, 1, 1],
+            protocol: TransportProtocol::UDP,
+            port: 30490,
+        }
+        .emit(&mut options[..12]);
+        LoadBalancingOptionRepr { priority: 1, weight: 2 }.emit(&mut options[12..20]);
+        IPv6EndpointOptionRepr {
+            ipv6_address: [0; 16],
+            protocol: TransportProtocol::TCP,
+            port: 30490,
+        }
+        .emit(&mut options[20..]);
+
+        assert_eq!(offset_of(&options, 0), Ok(0));
+        assert_eq!(offset_of(&options, 1), Ok(12));
+        assert_eq!(offset_of(&options, 2), Ok(20));
+        assert_eq!(offset_of(&options, 3), Err(Error::LengthOverflow));
+        assert_eq!(offset_of(&[], 0), Err(Error::LengthOverflow));
+
+        // Truncated last option
+        assert_eq!(offset_of(&options[..40], 2), Err(Error::LengthOverflow));
+    }
+
     #[test]
     fn test_discardable_flag() {
         let mut flag = DiscardableFlag::new();
@@ -2,8 +2,11 @@
 //!
 //! This module contains the `Packet` type, which is a read/write wrapper around a SOME/IP-SD packet buffer.
 
+use crate::entries::{EntriesIter, EntriesIterMut, Entry, NumberOfOptions};
 use crate::error::Error;
 use crate::field;
+use crate::flags::Flags;
+use crate::options::{OptionType, OptionsIter};
 use byteorder::{ByteOrder, NetworkEndian};
 use core::fmt;
 
@@ -26,6 +29,32 @@ pub struct Packet<T: AsRef<[u8]>> {
     buffer: T,
 }
 
+/// The fixed header fields of a SOME/IP-SD packet, read in one pass.
+///
+/// Returned by `Packet::header_fields`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct HeaderView {
+    /// Flags (1 byte)
+    pub flags: u8,
+    /// Reserved field (lower 24 bits)
+    pub reserved: u32,
+    /// Length of the entries array in bytes
+    pub entries_length: u32,
+}
+
+/// The length fields of a SOME/IP-SD packet, validated against the buffer.
+///
+/// Returned by `Packet::lengths`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct PacketLengths {
+    /// Length of the entries array in bytes
+    pub entries: u32,
+    /// Length of the options array in bytes
+    pub options: u32,
+    /// Total packet length in bytes, header included
+    pub total: usize,
+}
+
 #[allow(dead_code)]
 impl<T: AsRef<[u8]>> Packet<T> {
     /// Creates a new unchecked `Packet`.
@@ -60,7 +89,8 @@ impl<T: AsRef<[u8]>> Packet<T> {
     ///
     /// # Returns
     ///
-    /// * `Result<()>` - Ok if the length is valid, otherwise an error.
+    /// * `Result<()>` - Ok if the length is valid, otherwise an error
+    ///   (`Error::LengthOverflow` if the declared lengths overflow `usize`).
     pub fn check_len(&self) -> Result<()> {
         let len = self.buffer.as_ref().len();
         if len < field::entries::MIN_HEADER_LEN {
@@ -71,7 +101,9 @@ impl<T: AsRef<[u8]>> Packet<T> {
         let entries_len = self.entries_length();
         
         // Need at least: up to and including OPTIONS_LENGTH field
-        let min_with_entries = field::entries::OPTIONS_LENGTH(entries_len).end;
+        let min_with_entries = field::entries::try_options_length(entries_len)
+            .ok_or(Error::LengthOverflow)?
+            .end;
         if len < min_with_entries {
             return Err(Error::BufferTooShort);
         }
@@ -79,7 +111,9 @@ impl<T: AsRef<[u8]>> Packet<T> {
         let options_len = self.options_length();
         
         // Full length: everything including OPTIONS_ARRAY
-        let required_len = field::entries::OPTIONS_ARRAY(entries_len, options_len).end;
+        let required_len = field::entries::try_options_array(entries_len, options_len)
+            .ok_or(Error::LengthOverflow)?
+            .end;
         if len < required_len {
             return Err(Error::BufferTooShort);
         }
@@ -87,6 +121,38 @@ impl<T: AsRef<[u8]>> Packet<T> {
         Ok(())
     }
 
+    /// Checks the whole packet for structural validity.
+    ///
+    /// In addition to `check_len`, verifies that the reserved header field
+    /// is zero, that the entries array holds whole 16-byte entries of known
+    /// types, and that the option headers tile the options array exactly
+    /// with known types and their fixed lengths. Unknown options with the
+    /// discardable flag set are accepted.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<()>` - Ok if the packet is well-formed, otherwise the error
+    ///   of the first violation (`Error::HeaderReservedNotZero`,
+    ///   `Error::LengthOverflow`, `Error::InvalidEntryType` or
+    ///   `Error::InvalidOptionType`).
+    pub fn check_all(&self) -> Result<()> {
+        self.check_len()?;
+        if self.reserved() != 0 {
+            return Err(Error::HeaderReservedNotZero);
+        }
+        let entries = self.entries_array();
+        if !entries.len().is_multiple_of(Entry::<&[u8]>::LENGTH) {
+            return Err(Error::LengthOverflow);
+        }
+        for entry in EntriesIter::new(entries) {
+            entry?;
+        }
+        for option in OptionsIter::new(self.options_array()) {
+            option?;
+        }
+        Ok(())
+    }
+
     /// Returns the inner buffer.
     ///
     /// # Returns
@@ -96,6 +162,15 @@ impl<T: AsRef<[u8]>> Packet<T> {
         self.buffer
     }
 
+    /// Returns a reference to the inner buffer.
+    ///
+    /// # Returns
+    ///
+    /// * `&T` - The inner buffer.
+    pub fn buffer(&self) -> &T {
+        &self.buffer
+    }
+
     /// Returns a reference to the inner buffer.
     ///
     /// # Returns
@@ -115,6 +190,15 @@ impl<T: AsRef<[u8]>> Packet<T> {
         self.buffer.as_ref()[field::header::FLAGS.start]
     }
 
+    /// Returns the Flags byte as a typed bitfield
+    ///
+    /// # Returns
+    ///
+    /// * `Flags` - The reboot, unicast and reserved flag bits
+    pub fn flags_typed(&self) -> Flags {
+        Flags::from_u8(self.flags())
+    }
+
     /// Returns the Reserved field (3 bytes, should be 0x000000)
     ///
     /// # Returns
@@ -135,6 +219,54 @@ impl<T: AsRef<[u8]>> Packet<T> {
         NetworkEndian::read_u32(&self.buffer.as_ref()[field::entries::LENGTH]) as usize
     }
 
+    /// Returns the fixed header fields in one read.
+    ///
+    /// The first 8 bytes are loaded as a single big-endian `u64` and split
+    /// into flags, reserved and entries length, instead of slicing the buffer
+    /// once per field. Like the individual accessors, this panics if the
+    /// buffer is shorter than 8 bytes; use `new_checked` first.
+    ///
+    /// # Returns
+    ///
+    /// * `HeaderView` - Copy of the flags, reserved and entries length fields
+    #[inline]
+    pub fn header_fields(&self) -> HeaderView {
+        let word = NetworkEndian::read_u64(&self.buffer.as_ref()[..field::entries::MIN_HEADER_LEN]);
+        HeaderView {
+            flags: (word >> 56) as u8,
+            reserved: ((word >> 32) & 0x00FF_FFFF) as u32,
+            entries_length: word as u32,
+        }
+    }
+
+    /// Returns both length fields and the total length, without panicking.
+    ///
+    /// Every offset is computed with checked arithmetic, so this is safe to
+    /// call on an unchecked packet.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PacketLengths)` - The declared lengths and the total packet length
+    /// * `Err(Error::BufferTooShort)` - If the buffer ends before the declared content
+    /// * `Err(Error::LengthOverflow)` - If the declared lengths overflow `usize`
+    pub fn lengths(&self) -> Result<PacketLengths> {
+        let buffer = self.buffer.as_ref();
+        if buffer.len() < field::entries::MIN_HEADER_LEN {
+            return Err(Error::BufferTooShort);
+        }
+        let entries = NetworkEndian::read_u32(&buffer[field::entries::LENGTH]);
+        let options_field =
+            field::entries::try_options_length(entries as usize).ok_or(Error::LengthOverflow)?;
+        let options = NetworkEndian::read_u32(buffer.get(options_field).ok_or(Error::BufferTooShort)?);
+        let total = field::entries::try_options_array(entries as usize, options as usize)
+            .ok_or(Error::LengthOverflow)?
+            .end;
+        if buffer.len() < total {
+            return Err(Error::BufferTooShort);
+        }
+        Ok(PacketLengths { entries, options, total })
+    }
+
     /// Returns the Entries Array
     ///
     /// # Returns
@@ -146,6 +278,26 @@ impl<T: AsRef<[u8]>> Packet<T> {
         &self.buffer.as_ref()[range]
     }
 
+    /// Iterate over the entries array together with each entry's position.
+    ///
+    /// The index is the entry's 0-based position in the entries array, so it
+    /// can be used to address the entry for later edits. Unknown entry types
+    /// are yielded as `Entry::Unknown` rather than skipped, keeping the
+    /// indices aligned with the wire. A trailing partial entry (an entries
+    /// length that isn't a multiple of 16) is not yielded.
+    ///
+    /// Never panics on a packet that passed `check_len`.
+    ///
+    /// # Returns
+    ///
+    /// * `impl Iterator<Item = (usize, Entry<&[u8]>)>` - Each entry with its index
+    pub fn entries_indexed(&self) -> impl Iterator<Item = (usize, Entry<&[u8]>)> {
+        self.entries_array()
+            .chunks_exact(Entry::<&[u8]>::LENGTH)
+            .enumerate()
+            .filter_map(|(index, chunk)| Entry::from_buffer_lenient(chunk).ok().map(|entry| (index, entry)))
+    }
+
     /// Returns the Length of Options Array (4 bytes)
     ///
     /// # Returns
@@ -190,6 +342,15 @@ impl<T: AsRef<[u8]> + AsMut<[u8]>> Packet<T> {
         self.buffer.as_mut()[field::header::FLAGS.start] = flags;
     }
 
+    /// Sets the Flags byte from a typed bitfield
+    ///
+    /// # Arguments
+    ///
+    /// * `flags` - The flags to set
+    pub fn set_flags_typed(&mut self, flags: Flags) {
+        self.set_flags(flags.as_u8());
+    }
+
     /// Sets the Reserved field (3 bytes, should be 0x000000)
     ///
     /// # Arguments
@@ -222,6 +383,18 @@ impl<T: AsRef<[u8]> + AsMut<[u8]>> Packet<T> {
         &mut self.buffer.as_mut()[range]
     }
 
+    /// Returns an iterator over the entries for in-place edits
+    ///
+    /// Each item is a mutable `Entry` view dispatched on its type byte, so
+    /// e.g. every TTL can be rewritten without re-encoding the message.
+    ///
+    /// # Returns
+    ///
+    /// * `EntriesIterMut` - Iterator over the entries array
+    pub fn entries_mut(&mut self) -> EntriesIterMut<'_> {
+        EntriesIterMut::new(self.entries_array_mut())
+    }
+
     /// Sets the Length of Options Array (4 bytes)
     ///
     /// # Arguments
@@ -242,6 +415,195 @@ impl<T: AsRef<[u8]> + AsMut<[u8]>> Packet<T> {
         let options_len = self.options_length();
         &mut self.buffer.as_mut()[field::entries::OPTIONS_ARRAY(entries_len, options_len)]
     }
+
+    /// Removes an option and fixes up every entry referencing the options array.
+    ///
+    /// Subsequent options are shifted left to close the gap and the options
+    /// length field shrinks accordingly. Entries whose option run contains the
+    /// removed option lose one option from that run; runs starting after it
+    /// have their index decremented. The bytes freed at the end of the
+    /// packet are zeroed.
+    ///
+    /// # Arguments
+    ///
+    /// * `option_index` - Index of the option to remove
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the option was removed
+    /// * `Err(Error::BufferTooShort)` if the packet is truncated
+    /// * `Err(Error::LengthOverflow)` if there is no option at `option_index`,
+    ///   the options array is malformed, or the entries array isn't a whole
+    ///   number of entries
+    pub fn remove_option(&mut self, option_index: usize) -> Result<()> {
+        self.check_len()?;
+        let entries_len = self.entries_length();
+        if !entries_len.is_multiple_of(Entry::<&[u8]>::LENGTH) {
+            return Err(Error::LengthOverflow);
+        }
+
+        let options_len = self.options_length();
+        let options_range = field::entries::OPTIONS_ARRAY(entries_len, options_len);
+        let (start, len) = {
+            let options = &self.buffer.as_ref()[options_range.clone()];
+            let offset = crate::options::offset_of(options, option_index)?;
+            (options_range.start + offset, crate::options::option_wire_len(&options[offset..])?)
+        };
+
+        let buffer = self.buffer.as_mut();
+        buffer.copy_within(start + len..options_range.end, start);
+        buffer[options_range.end - len..options_range.end].fill(0);
+
+        let entries_range = field::entries::ENTRIES_ARRAY(entries_len);
+        for entry in buffer[entries_range].chunks_mut(Entry::<&[u8]>::LENGTH) {
+            let mut number = NumberOfOptions::from_u8(entry[field::service_entry::NUMBER_OF_OPTIONS.start]);
+            let runs = [
+                (field::service_entry::INDEX_FIRST_OPTION_RUN.start, number.options1()),
+                (field::service_entry::INDEX_SECOND_OPTION_RUN.start, number.options2()),
+            ];
+            for (run, (index_field, count)) in runs.into_iter().enumerate() {
+                if count == 0 {
+                    continue;
+                }
+                let index = entry[index_field] as usize;
+                if (index..index + count as usize).contains(&option_index) {
+                    if run == 0 {
+                        number.set_options1(count - 1);
+                    } else {
+                        number.set_options2(count - 1);
+                    }
+                } else if index > option_index {
+                    entry[index_field] -= 1;
+                }
+            }
+            entry[field::service_entry::NUMBER_OF_OPTIONS.start] = number.as_u8();
+        }
+
+        self.set_options_length((options_len - len) as u32);
+        Ok(())
+    }
+
+    /// Appends an option at the end of the options array.
+    ///
+    /// The option is written right after the current options array and the
+    /// options length field grows accordingly; the backing buffer must have
+    /// room for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `option_bytes` - One complete option, header included
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - Index of the new option, for referencing it from an entry
+    /// * `Err(Error::BufferTooShort)` if the packet is truncated or the buffer
+    ///   has no room for the option
+    /// * `Err(Error::LengthOverflow)` if `option_bytes` isn't exactly one
+    ///   well-formed option or the existing options array is malformed
+    pub fn append_option(&mut self, option_bytes: &[u8]) -> Result<usize> {
+        self.check_len()?;
+        if crate::options::option_wire_len(option_bytes)? != option_bytes.len() {
+            return Err(Error::LengthOverflow);
+        }
+
+        let entries_len = self.entries_length();
+        let options_len = self.options_length();
+        let end = field::entries::OPTIONS_ARRAY(entries_len, options_len).end;
+        if self.buffer.as_ref().len() < end + option_bytes.len() {
+            return Err(Error::BufferTooShort);
+        }
+
+        let mut index = 0;
+        let mut pos = 0;
+        let options = self.options_array();
+        while pos < options.len() {
+            pos += crate::options::option_wire_len(&options[pos..])?;
+            index += 1;
+        }
+
+        self.buffer.as_mut()[end..end + option_bytes.len()].copy_from_slice(option_bytes);
+        self.set_options_length((options_len + option_bytes.len()) as u32);
+        Ok(index)
+    }
+
+    /// Replaces the option starting at `offset` with another of the same size.
+    ///
+    /// The offset is relative to the start of the options array, as reported
+    /// by `OptionsIter::with_offsets`, so an option found while decoding can
+    /// be edited without locating it again.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - Byte offset of the option within the options array
+    /// * `option_bytes` - Replacement option, header included
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the option was replaced
+    /// * `Err(Error::BufferTooShort)` if the packet is truncated
+    /// * `Err(Error::LengthOverflow)` if no well-formed option starts at
+    ///   `offset` or the replacement has a different size
+    pub fn rewrite_option_at(&mut self, offset: usize, option_bytes: &[u8]) -> Result<()> {
+        self.check_len()?;
+        let options = self.options_array_mut();
+        let existing = options.get_mut(offset..).ok_or(Error::LengthOverflow)?;
+        if existing.is_empty() {
+            return Err(Error::LengthOverflow);
+        }
+        let len = crate::options::option_wire_len(existing)?;
+        if len != option_bytes.len() {
+            return Err(Error::LengthOverflow);
+        }
+        existing[..len].copy_from_slice(option_bytes);
+        Ok(())
+    }
+
+    /// Zeroes every reserved field in the packet, in place.
+    ///
+    /// Clears the header reserved field, the reserved bits next to each
+    /// option's discardable flag, and the reserved byte of every endpoint,
+    /// multicast and SD endpoint option. Nothing else is touched, so the
+    /// semantic content is preserved. The options array is validated before
+    /// anything is written.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the packet was canonicalized
+    /// * `Err(Error::BufferTooShort)` if the packet or an option is truncated
+    /// * `Err(Error::LengthOverflow)` if an option header is malformed
+    pub fn canonicalize_reserved(&mut self) -> Result<()> {
+        self.check_len()?;
+        let options = self.options_array();
+        let mut pos = 0;
+        while pos < options.len() {
+            pos += crate::options::option_wire_len(&options[pos..])?;
+        }
+
+        self.set_reserved(0);
+        let options = self.options_array_mut();
+        let mut pos = 0;
+        while pos < options.len() {
+            let len = crate::options::option_wire_len(&options[pos..])?;
+            let option = &mut options[pos..pos + len];
+            option[field::option_header::DISCARDABLE_FLAG_AND_RESERVED.start] &= 0x80;
+            let reserved = match OptionType::from_u8(option[field::option_header::TYPE.start]) {
+                Some(OptionType::IPv4Endpoint) => Some(field::ipv4_endpoint_option::RESERVED.start),
+                Some(OptionType::IPv6Endpoint) => Some(field::ipv6_endpoint_option::RESERVED.start),
+                Some(OptionType::IPv4Multicast) => Some(field::ipv4_multicast_option::RESERVED.start),
+                Some(OptionType::IPv6Multicast) => Some(field::ipv6_multicast_option::RESERVED.start),
+                Some(OptionType::IPv4SdEndpoint) => Some(field::ipv4_sd_endpoint_option::RESERVED.start),
+                Some(OptionType::IPv6SdEndpoint) => Some(field::ipv6_sd_endpoint_option::RESERVED.start),
+                Some(OptionType::Configuration | OptionType::LoadBalancing) | None => None,
+            };
+            if let Some(offset) = reserved.map(|r| field::option_header::DISCARDABLE_FLAG_AND_RESERVED.end + r)
+                && offset < len
+            {
+                option[offset] = 0;
+            }
+            pos += len;
+        }
+        Ok(())
+    }
 }
 
 impl<T: AsRef<[u8]>> fmt::Display for Packet<T> {
@@ -282,6 +644,48 @@ mod tests {
         assert_eq!(packet.flags(), 0x80);
     }
 
+    #[test]
+    fn test_packet_check_all() {
+        use crate::options::IPv4SdEndpointOptionRepr;
+
+        let mut buffer = [0u8; 12 + 16 + 12];
+        buffer[7] = 16;
+        buffer[8] = 0x01; // OfferService
+        buffer[12 + 16 - 1] = 12;
+        IPv4SdEndpointOptionRepr::default_port([10, 0, 0, 1]).emit(&mut buffer[28..]);
+        assert_eq!(Packet::new_checked(&buffer[..]).unwrap().check_all(), Ok(()));
+
+        let mut bad = buffer;
+        bad[2] = 1;
+        assert_eq!(Packet::new_unchecked(&bad[..]).check_all(), Err(Error::HeaderReservedNotZero));
+        let mut bad = buffer;
+        bad[8] = 0x42;
+        assert_eq!(Packet::new_unchecked(&bad[..]).check_all(), Err(Error::InvalidEntryType(0x42)));
+        let mut bad = buffer;
+        bad[28 + 2] = 0x30;
+        assert_eq!(Packet::new_unchecked(&bad[..]).check_all(), Err(Error::InvalidOptionType(0x30)));
+        let mut bad = buffer;
+        bad[28 + 1] = 10;
+        assert_eq!(Packet::new_unchecked(&bad[..]).check_all(), Err(Error::LengthOverflow));
+        let mut bad = buffer;
+        bad[7] = 12;
+        assert_eq!(Packet::new_unchecked(&bad[..]).check_all(), Err(Error::LengthOverflow));
+        assert_eq!(Packet::new_unchecked(&buffer[..30]).check_all(), Err(Error::BufferTooShort));
+    }
+
+    #[test]
+    fn test_packet_flags_typed() {
+        let mut buffer = [0u8; 12];
+        let mut packet = Packet::new_unchecked(&mut buffer[..]);
+        packet.set_flags(0x15);
+        let mut flags = packet.flags_typed();
+        flags.set_reboot(true);
+        packet.set_flags_typed(flags);
+        assert!(packet.flags_typed().reboot());
+        assert!(!packet.flags_typed().unicast());
+        assert_eq!(packet.flags(), 0x95);
+    }
+
     #[test]
     fn test_packet_reserved() {
         let mut buffer = [0u8; 12];
@@ -298,6 +702,237 @@ mod tests {
         assert_eq!(packet.entries_length(), 8);
     }
 
+    #[test]
+    fn test_packet_network_byte_order() {
+        let mut buffer = [0u8; 12];
+        let mut packet = Packet::new_unchecked(&mut buffer[..]);
+        packet.set_reserved(0x010203);
+        packet.set_entries_length(0);
+        packet.set_options_length(0x04050607);
+
+        assert_eq!(buffer[1..4], [0x01, 0x02, 0x03]);
+        assert_eq!(buffer[4..8], [0x00, 0x00, 0x00, 0x00]);
+        assert_eq!(buffer[8..12], [0x04, 0x05, 0x06, 0x07]);
+
+        let mut buffer = [0u8; 12];
+        let mut packet = Packet::new_unchecked(&mut buffer[..]);
+        packet.set_entries_length(0x01020304);
+        assert_eq!(buffer[4..8], [0x01, 0x02, 0x03, 0x04]);
+    }
+
+    #[test]
+    fn test_packet_entries_indexed() {
+        let mut buffer = [0u8; 12 + 40];
+        let mut packet = Packet::new_unchecked(&mut buffer[..]);
+        packet.set_entries_length(40);
+        packet.set_options_length(0);
+        let entries = packet.entries_array_mut();
+        entries[0] = 0x01;
+        entries[16] = 0x42;
+        entries[32] = 0x06;
+
+        let packet = Packet::new_checked(&buffer[..]).unwrap();
+        let mut iter = packet.entries_indexed();
+        assert!(matches!(iter.next(), Some((0, Entry::Service(_)))));
+        assert!(matches!(iter.next(), Some((1, Entry::Unknown { type_byte: 0x42, .. }))));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_packet_header_fields() {
+        let buffer = [0xC0, 0x01, 0x02, 0x03, 0x00, 0x00, 0x01, 0x10];
+        let packet = Packet::new_unchecked(&buffer[..]);
+        let header = packet.header_fields();
+
+        assert_eq!(header.flags, packet.flags());
+        assert_eq!(header.reserved, packet.reserved());
+        assert_eq!(header.entries_length as usize, packet.entries_length());
+        assert_eq!(
+            header,
+            HeaderView {
+                flags: 0xC0,
+                reserved: 0x010203,
+                entries_length: 0x110,
+            }
+        );
+    }
+
+    #[test]
+    fn test_packet_lengths() {
+        let mut buffer = [0u8; 12 + 16 + 8];
+        let mut packet = Packet::new_unchecked(&mut buffer[..]);
+        packet.set_entries_length(16);
+        packet.set_options_length(8);
+        assert_eq!(
+            packet.lengths(),
+            Ok(PacketLengths { entries: 16, options: 8, total: 36 })
+        );
+        assert_eq!(packet.lengths().unwrap().total, packet.total_length());
+
+        assert_eq!(Packet::new_unchecked(&buffer[..35]).lengths(), Err(Error::BufferTooShort));
+        assert_eq!(Packet::new_unchecked(&buffer[..20]).lengths(), Err(Error::BufferTooShort));
+        assert_eq!(Packet::new_unchecked(&buffer[..4]).lengths(), Err(Error::BufferTooShort));
+        buffer[4..8].fill(0xFF);
+        assert_eq!(Packet::new_unchecked(&buffer[..]).lengths(), Err(Error::BufferTooShort));
+    }
+
+    #[test]
+    fn test_packet_remove_option() {
+        use crate::options::{IPv4EndpointOptionRepr, LoadBalancingOptionRepr, TransportProtocol};
+
+        // Two entries, three options: endpoint A, load balancing, endpoint B
+        let mut buffer = [0u8; 12 + 32 + 32];
+        let mut packet = Packet::new_unchecked(&mut buffer[..]);
+        packet.set_entries_length(32);
+        packet.set_options_length(32);
+        {
+            let entries = packet.entries_array_mut();
+            entries[0] = 0x01;
+            // First entry: run 1 = [A, LB], run 2 = [B]
+            entries[1..4].copy_from_slice(&[0, 2, 0x21]);
+            entries[16] = 0x01;
+            // Second entry: run 1 = [B]
+            entries[17..20].copy_from_slice(&[2, 0, 0x10]);
+        }
+        {
+            let options = packet.options_array_mut();
+            let endpoint = |port| IPv4EndpointOptionRepr {
+                ipv4_address: [10, 0, 0, 1],
+                protocol: TransportProtocol::UDP,
+                port,
+            };
+            endpoint(1).emit(&mut options[..12]);
+            LoadBalancingOptionRepr { priority: 1, weight: 2 }.emit(&mut options[12..20]);
+            endpoint(2).emit(&mut options[20..32]);
+        }
+
+        packet.remove_option(1).unwrap();
+        assert_eq!(packet.options_length(), 24);
+        assert_eq!(packet.options_array()[14], 0x04);
+        assert_eq!(packet.options_array()[22..24], [0x00, 0x02]);
+        assert_eq!(packet.entries_array()[1..4], [0, 1, 0x11]);
+        assert_eq!(packet.entries_array()[17..20], [1, 0, 0x10]);
+
+        packet.remove_option(0).unwrap();
+        assert_eq!(packet.options_length(), 12);
+        assert_eq!(packet.entries_array()[1..4], [0, 0, 0x01]);
+        assert_eq!(packet.entries_array()[17..20], [0, 0, 0x10]);
+
+        assert_eq!(packet.remove_option(1), Err(Error::LengthOverflow));
+        let buffer = packet.into_inner();
+        assert!(buffer[12 + 32 + 12..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_packet_append_option() {
+        use crate::options::LoadBalancingOptionRepr;
+
+        let mut option = [0u8; 8];
+        LoadBalancingOptionRepr { priority: 1, weight: 2 }.emit(&mut option);
+
+        let mut buffer = [0u8; 12 + 16 + 16 + 4];
+        let mut packet = Packet::new_unchecked(&mut buffer[..]);
+        packet.set_entries_length(16);
+        packet.set_options_length(0);
+
+        assert_eq!(packet.append_option(&option), Ok(0));
+        assert_eq!(packet.append_option(&option), Ok(1));
+        assert_eq!(packet.options_length(), 16);
+        assert_eq!(packet.options_array()[8..], option);
+        assert_eq!(packet.append_option(&option), Err(Error::BufferTooShort));
+        assert_eq!(packet.append_option(&option[..7]), Err(Error::LengthOverflow));
+        assert_eq!(packet.options_length(), 16);
+    }
+
+    #[test]
+    fn test_packet_rewrite_option_at() {
+        use crate::options::{IPv4SdEndpointOptionRepr, LoadBalancingOptionRepr, OptionsIter, SdOption};
+
+        let mut balancing = [0u8; 8];
+        LoadBalancingOptionRepr { priority: 1, weight: 2 }.emit(&mut balancing);
+        let mut endpoint = [0u8; 12];
+        IPv4SdEndpointOptionRepr::default_port([10, 0, 0, 1]).emit(&mut endpoint);
+
+        let mut buffer = [0u8; 12 + 8 + 12];
+        let mut packet = Packet::new_unchecked(&mut buffer[..]);
+        packet.set_entries_length(0);
+        packet.set_options_length(0);
+        packet.append_option(&balancing).unwrap();
+        packet.append_option(&endpoint).unwrap();
+
+        let offset = OptionsIter::new(packet.options_array())
+            .with_offsets()
+            .find_map(|o| o.ok().filter(|o| matches!(o.option, SdOption::IPv4SdEndpoint(_))))
+            .unwrap()
+            .offset;
+        let mut moved = [0u8; 12];
+        IPv4SdEndpointOptionRepr::default_port([10, 0, 0, 2]).emit(&mut moved);
+        assert_eq!(packet.rewrite_option_at(offset, &moved), Ok(()));
+        assert_eq!(packet.options_array()[8..], moved);
+
+        assert_eq!(packet.rewrite_option_at(0, &moved), Err(Error::LengthOverflow));
+        assert_eq!(packet.rewrite_option_at(20, &moved), Err(Error::LengthOverflow));
+        assert_eq!(packet.options_array()[..8], balancing);
+    }
+
+    #[test]
+    fn test_packet_canonicalize_reserved() {
+        use crate::options::LoadBalancingOptionRepr;
+
+        let mut endpoint = [0u8; 12];
+        crate::options::IPv4SdEndpointOptionRepr::default_port([10, 0, 0, 1]).emit(&mut endpoint);
+        let mut balancing = [0u8; 8];
+        LoadBalancingOptionRepr { priority: 1, weight: 2 }.emit(&mut balancing);
+
+        let mut buffer = [0u8; 12 + 12 + 8];
+        let mut packet = Packet::new_unchecked(&mut buffer[..]);
+        packet.set_reserved(0xABCDEF);
+        packet.set_entries_length(0);
+        packet.set_options_length(0);
+        packet.append_option(&endpoint).unwrap();
+        packet.append_option(&balancing).unwrap();
+        let clean = buffer;
+
+        let mut dirty = clean;
+        dirty[1..4].fill(0xFF);
+        dirty[12 + 3] = 0xFF;
+        dirty[12 + 8] = 0x5A;
+        dirty[24 + 3] = 0x7F;
+        let mut packet = Packet::new_unchecked(&mut dirty[..]);
+        assert_eq!(packet.canonicalize_reserved(), Ok(()));
+        let mut expected = clean;
+        expected[1..4].fill(0);
+        expected[12 + 3] = 0x80;
+        assert_eq!(dirty, expected);
+
+        let mut truncated = clean;
+        truncated[12 + 1] = 0x20;
+        let mut packet = Packet::new_unchecked(&mut truncated[..]);
+        assert!(packet.canonicalize_reserved().is_err());
+        assert_eq!(truncated[1..4], [0xAB, 0xCD, 0xEF]);
+    }
+
+    #[test]
+    fn test_packet_entries_mut() {
+        let mut buffer = [0u8; 12 + 32];
+        let mut packet = Packet::new_unchecked(&mut buffer[..]);
+        packet.set_entries_length(32);
+        {
+            let entries = packet.entries_array_mut();
+            entries[0] = 0x01; // OfferService
+            entries[16] = 0x06; // Subscribe
+        }
+        for entry in packet.entries_mut() {
+            match entry {
+                Entry::Service(mut e) => e.set_ttl(3),
+                Entry::EventGroup(mut e) => e.set_ttl(5),
+                Entry::Unknown { .. } => unreachable!(),
+            }
+        }
+        assert_eq!(buffer[8 + 9..8 + 12], [0, 0, 3]);
+        assert_eq!(buffer[8 + 16 + 9..8 + 16 + 12], [0, 0, 5]);
+    }
+
     #[test]
     fn test_packet_with_entries_and_options() {
         // Create a packet with 16 bytes of entries and 8 bytes of options
@@ -5,18 +5,66 @@
 //!
 //! ```
 //! use someip_sd_wire::prelude::*;
+//!
+//! // Build a message with one offer referencing one endpoint...
+//! let mut builder = MessageBuilder::new(0xC0);
+//! let endpoint = builder
+//!     .add_option(OptionRepr::IPv4Endpoint(IPv4EndpointOptionRepr {
+//!         ipv4_address: [192, 168, 0, 1],
+//!         protocol: TransportProtocol::UDP,
+//!         port: 30509,
+//!     }))
+//!     .unwrap();
+//! let offer = ServiceEntryRepr {
+//!     entry_type: EntryType::OfferService,
+//!     index_first_option_run: 0,
+//!     index_second_option_run: 0,
+//!     number_of_options: NumberOfOptions::new(),
+//!     service_id: 0x1234,
+//!     instance_id: 0x0001,
+//!     major_version: 1,
+//!     ttl: 3,
+//!     minor_version: 0,
+//! };
+//! builder.add_entry(EntryRepr::Service(offer), &[endpoint], &[]).unwrap();
+//! let mut buffer = [0u8; 64];
+//! let len = builder.finish(&mut buffer).unwrap();
+//!
+//! // ...and decode it again.
+//! let repr = Repr::parse_bytes(&buffer[..len]).unwrap();
+//! assert!(matches!(EntriesIter::new(repr.entries).next(), Some(Ok(Entry::Service(_)))));
+//! assert!(matches!(OptionsIter::new(repr.options).next(), Some(Ok(SdOption::IPv4Endpoint(_)))));
 //! ```
 
-pub use crate::config::{ConfigEntry, ConfigurationOption};
+pub use crate::config::{ConfigEntry, ConfigurationOption, OwnedConfigEntry};
 pub use crate::entries::{
     EntryType, EventGroupEntry, EventGroupEntryRepr, NumberOfOptions, ReservedAndCounter,
-    ServiceEntry, ServiceEntryRepr,
+    EventGroupKey, ServiceEntry, ServiceEntryRepr, ServiceKey, Ttl,
 };
 pub use crate::error::{ConfigError, Error};
+pub use crate::flags::{Flags, SdFlags};
 pub use crate::options::{
-    DiscardableFlag, IPv4EndpointOption, IPv4EndpointOptionRepr, IPv6EndpointOption,
-    IPv6EndpointOptionRepr, LoadBalancingOption, LoadBalancingOptionRepr, OptionHeader,
-    OptionType, TransportProtocol,
+    DiscardableFlag, EndpointInfo, IPv4EndpointOption, IPv4EndpointOptionRepr,
+    IPv4MulticastOption, IPv4MulticastOptionRepr, IPv4SdEndpointOption, IPv4SdEndpointOptionRepr,
+    IPv6EndpointOption, IPv6EndpointOptionRepr, IPv6MulticastOption, IPv6MulticastOptionRepr,
+    IPv6SdEndpointOption, IPv6SdEndpointOptionRepr,
+    LoadBalancingOption, LoadBalancingOptionRepr, OptionHeader, OptionType, TransportProtocol,
+};
+pub use crate::packet::{HeaderView, Packet, PacketLengths};
+pub use crate::registry::{ServiceRecord, ServiceRegistry};
+pub use crate::repr::{
+    Budget, DecodedItem, ItemResult, KnownOptions, MessageClass, Repr, UnknownOptions, ValidationPolicy,
 };
-pub use crate::packet::Packet;
-pub use crate::repr::Repr;
+#[cfg(feature = "alloc")]
+pub use crate::repr::OwnedRepr;
+
+// Decoding: dispatching views and iterators over entries and options arrays
+pub use crate::entries::{EntriesIter, EntriesIterMut, Entry, EntryMut};
+pub use crate::options::{OptionsAtIter, OptionsIter, SdOption, SdOptionAt};
+pub use crate::repr::{EntryOptionsIter, MessageIter};
+
+// Building: high-level entry/option enums, message builder and MTU splitter
+pub use crate::builder::{MessageBuilder, OptionPool, OptionRef, PoolRef};
+pub use crate::entries::EntryRepr;
+pub use crate::options::OptionRepr;
+pub use crate::repr::Splitter;
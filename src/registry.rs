@@ -0,0 +1,253 @@
+//! A bounded cache of offered services, fed from decoded SD messages.
+//!
+//! `ServiceRegistry` tracks the OfferService entries seen on the wire: offers
+//! insert or refresh a record, StopOffers remove it, and `expire` drops records
+//! whose TTL ran out. The registry has no clock of its own; the caller ticks it
+//! with `expire(now_secs)` and offers applied afterwards expire relative to
+//! that time. Storage is a fixed-size array, so it works without `alloc`.
+
+use crate::entries::{EntriesIter, Entry, EntryType, ServiceEntryRepr, ServiceKey, Ttl};
+use crate::error::Error;
+use crate::options::{IPv4EndpointOptionRepr, IPv6EndpointOptionRepr, OptionsIter, SdOption};
+use crate::repr::Repr;
+
+/// Result type alias using the crate's Error type.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Maximum number of services a `ServiceRegistry` can track.
+pub const MAX_SERVICES: usize = 32;
+
+/// What the registry knows about one offered service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServiceRecord {
+    /// Service ID, instance ID and major version of the offer
+    pub key: ServiceKey,
+    /// Minor version of the offer
+    pub minor_version: u32,
+    /// TTL of the most recent offer
+    pub ttl: Ttl,
+    /// Registry time (seconds) at which the offer expires, `None` if infinite
+    pub expires_at: Option<u64>,
+    /// First IPv4 endpoint option referenced by the offer, if any
+    pub ipv4_endpoint: Option<IPv4EndpointOptionRepr>,
+    /// First IPv6 endpoint option referenced by the offer, if any
+    pub ipv6_endpoint: Option<IPv6EndpointOptionRepr>,
+}
+
+/// Fixed-capacity cache of offered services.
+#[derive(Debug, Clone)]
+pub struct ServiceRegistry {
+    records: [Option<ServiceRecord>; MAX_SERVICES],
+    now: u64,
+}
+
+impl Default for ServiceRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ServiceRegistry {
+    /// Create an empty registry at time 0.
+    pub fn new() -> Self {
+        ServiceRegistry {
+            records: [None; MAX_SERVICES],
+            now: 0,
+        }
+    }
+
+    /// Apply the OfferService and StopOfferService entries of a message.
+    ///
+    /// An offer inserts or replaces the record for its `ServiceKey`, expiring
+    /// `ttl` seconds after the time of the last `expire` call. A StopOffer
+    /// removes the record. FindService, eventgroup and unknown entries are
+    /// ignored. Entries before a failing one stay applied.
+    ///
+    /// # Parameters
+    /// * `repr` - The decoded message
+    ///
+    /// # Returns
+    /// * `Ok(())` if every offer was applied
+    /// * `Err(Error::LengthOverflow)` if a new service doesn't fit in
+    ///   `MAX_SERVICES` records, or an offer references missing options
+    /// * `Err(Error)` if an entry or referenced option fails to parse
+    pub fn apply(&mut self, repr: &Repr) -> Result<()> {
+        for entry in EntriesIter::new_lenient(repr.entries) {
+            let entry = entry?;
+            let Entry::Service(service) = &entry else {
+                continue;
+            };
+            let offer = ServiceEntryRepr::parse(service)?;
+            if offer.entry_type != EntryType::OfferService {
+                continue;
+            }
+            let key = ServiceKey::from(&offer);
+            let ttl = offer.ttl_typed();
+            if ttl.is_stop() {
+                self.remove(key);
+                continue;
+            }
+
+            let mut record = ServiceRecord {
+                key,
+                minor_version: offer.minor_version,
+                ttl,
+                expires_at: (!ttl.is_infinite()).then(|| self.now.saturating_add(ttl.as_u32() as u64)),
+                ipv4_endpoint: None,
+                ipv6_endpoint: None,
+            };
+            for run in [entry.first_run_bytes(repr.options)?, entry.second_run_bytes(repr.options)?] {
+                for option in OptionsIter::new(run) {
+                    match option? {
+                        SdOption::IPv4Endpoint(ep) => {
+                            record.ipv4_endpoint.get_or_insert(IPv4EndpointOptionRepr::parse(&ep)?);
+                        }
+                        SdOption::IPv6Endpoint(ep) => {
+                            record.ipv6_endpoint.get_or_insert(IPv6EndpointOptionRepr::parse(&ep)?);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            self.insert(record)?;
+        }
+        Ok(())
+    }
+
+    /// Advance the registry clock and drop expired records.
+    ///
+    /// # Parameters
+    /// * `now_secs` - Current time in seconds, on the caller's monotonic clock
+    pub fn expire(&mut self, now_secs: u64) {
+        self.now = now_secs;
+        for slot in self.records.iter_mut() {
+            if matches!(slot, Some(ServiceRecord { expires_at: Some(at), .. }) if *at <= now_secs) {
+                *slot = None;
+            }
+        }
+    }
+
+    /// Look up the record for a service.
+    ///
+    /// # Parameters
+    /// * `key` - Service ID, instance ID and major version to look up
+    ///
+    /// # Returns
+    /// * `Some(&ServiceRecord)` if the service is currently offered
+    pub fn lookup(&self, key: ServiceKey) -> Option<&ServiceRecord> {
+        self.records.iter().flatten().find(|record| record.key == key)
+    }
+
+    /// Iterate over all currently offered services.
+    pub fn iter(&self) -> impl Iterator<Item = &ServiceRecord> {
+        self.records.iter().flatten()
+    }
+
+    /// Get the number of services currently offered.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Returns true if no service is currently offered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn insert(&mut self, record: ServiceRecord) -> Result<()> {
+        let slot = match self.records.iter().position(|r| matches!(r, Some(r) if r.key == record.key)) {
+            Some(index) => index,
+            None => self.records.iter().position(Option::is_none).ok_or(Error::LengthOverflow)?,
+        };
+        self.records[slot] = Some(record);
+        Ok(())
+    }
+
+    fn remove(&mut self, key: ServiceKey) {
+        for slot in self.records.iter_mut() {
+            if matches!(slot, Some(record) if record.key == key) {
+                *slot = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::MessageBuilder;
+    use crate::entries::{EntryRepr, TTL_INFINITE};
+    use crate::options::{OptionRepr, TransportProtocol};
+
+    fn offer(service_id: u16, ttl: u32) -> EntryRepr {
+        EntryRepr::Service(ServiceEntryRepr::offer(ServiceKey::new(service_id, 1, 1), 0, ttl))
+    }
+
+    fn apply(registry: &mut ServiceRegistry, entries: &[EntryRepr], port: u16) -> Result<()> {
+        let mut builder = MessageBuilder::new(0xC0);
+        let endpoint = builder.add_option(OptionRepr::IPv4Endpoint(IPv4EndpointOptionRepr {
+            ipv4_address: [10, 0, 0, 1],
+            protocol: TransportProtocol::UDP,
+            port,
+        }))?;
+        for entry in entries {
+            builder.add_entry(*entry, &[endpoint], &[])?;
+        }
+        let mut buffer = [0u8; 256];
+        let len = builder.finish(&mut buffer)?;
+        registry.apply(&Repr::parse_bytes(&buffer[..len])?)
+    }
+
+    #[test]
+    fn test_registry_offer_update_stop() {
+        let mut registry = ServiceRegistry::new();
+        let key = ServiceKey::new(0x1234, 1, 1);
+        apply(&mut registry, &[offer(0x1234, 3), offer(0x5678, TTL_INFINITE)], 30509).unwrap();
+        assert_eq!(registry.len(), 2);
+        let record = registry.lookup(key).unwrap();
+        assert_eq!(record.expires_at, Some(3));
+        assert_eq!(record.ipv4_endpoint.unwrap().port, 30509);
+        assert_eq!(record.ipv6_endpoint, None);
+
+        // A changed offer replaces the record
+        registry.expire(1);
+        apply(&mut registry, &[offer(0x1234, 5)], 30510).unwrap();
+        let record = registry.lookup(key).unwrap();
+        assert_eq!((record.expires_at, record.ipv4_endpoint.unwrap().port), (Some(6), 30510));
+        assert_eq!(registry.len(), 2);
+
+        // StopOffer removes it
+        apply(&mut registry, &[offer(0x1234, 0)], 30510).unwrap();
+        assert!(registry.lookup(key).is_none());
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn test_registry_expire() {
+        let mut registry = ServiceRegistry::default();
+        apply(&mut registry, &[offer(0x1000, 3), offer(0x2000, TTL_INFINITE)], 30509).unwrap();
+        registry.expire(2);
+        assert_eq!(registry.len(), 2);
+        registry.expire(3);
+        assert!(registry.lookup(ServiceKey::new(0x1000, 1, 1)).is_none());
+        assert_eq!(registry.lookup(ServiceKey::new(0x2000, 1, 1)).unwrap().expires_at, None);
+        registry.expire(u64::MAX);
+        assert_eq!(registry.len(), 1);
+
+        // The expiry time saturates at the end of the clock
+        registry.expire(u64::MAX - 1);
+        apply(&mut registry, &[offer(0x1000, 3)], 30509).unwrap();
+        assert_eq!(registry.lookup(ServiceKey::new(0x1000, 1, 1)).unwrap().expires_at, Some(u64::MAX));
+    }
+
+    #[test]
+    fn test_registry_full() {
+        let mut registry = ServiceRegistry::new();
+        for service_id in 0..MAX_SERVICES as u16 {
+            apply(&mut registry, &[offer(service_id, 3)], 30509).unwrap();
+        }
+        assert_eq!(apply(&mut registry, &[offer(0xFFF0, 3)], 30509), Err(Error::LengthOverflow));
+        // Refreshing a known service still works
+        apply(&mut registry, &[offer(0, 3)], 30509).unwrap();
+        assert!(!registry.is_empty());
+    }
+}
@@ -1,4 +1,13 @@
 use crate::{error::*, packet::*};
+use crate::entries::{EntriesIter, Entry, EntryRepr, EntryType, EventGroupEntryRepr, ServiceEntryRepr, ServiceKey, TTL_INFINITE};
+use crate::config::ConfigurationOption;
+use crate::options::{
+    DiscardableFlag, EndpointInfo, IPv4EndpointOption, IPv4MulticastOption, IPv4SdEndpointOption, IPv6EndpointOption,
+    IPv6MulticastOption, IPv6SdEndpointOption, OptionHeader, OptionRepr, OptionType, OptionsIter, SdOption,
+    TransportProtocol,
+};
+use crate::field;
+use crate::flags::Flags;
 use core::fmt;
 
 /// A high-level representation of a SOME/IP-SD message.
@@ -43,6 +52,72 @@ impl<'a> Repr<'a> {
         }
     }
 
+    /// Get the flags byte as a typed bitfield.
+    ///
+    /// # Returns
+    ///
+    /// * `Flags` - The reboot, unicast and reserved flag bits
+    pub fn flags_typed(&self) -> Flags {
+        Flags::from_u8(self.flags)
+    }
+
+    /// Set the flags byte from a typed bitfield.
+    ///
+    /// # Arguments
+    ///
+    /// * `flags` - The flags to set
+    pub fn set_flags_typed(&mut self, flags: Flags) {
+        self.flags = flags.as_u8();
+    }
+
+    /// Parse a SOME/IP-SD packet, bounding the work spent on untrusted input.
+    ///
+    /// Like `parse`, then counts the entries, walks the options and counts the
+    /// configuration entries of every Configuration option, aborting as soon
+    /// as a `budget` limit is exceeded. A Configuration option whose TXT data
+    /// is malformed is only counted up to the first bad entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `packet` - The packet to parse
+    /// * `budget` - Limits on the message content
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Repr)` - The parsed representation
+    /// * `Err(Error::BudgetExceeded)` - If any limit is exceeded
+    /// * `Err(Error)` - If the packet or an option header is malformed
+    pub fn parse_within<T>(packet: &'a Packet<T>, budget: &Budget) -> core::result::Result<Repr<'a>, Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        let repr = Repr::parse(packet)?;
+        if repr.entries.len().div_ceil(EntryRepr::buffer_len()) > budget.max_entries {
+            return Err(Error::BudgetExceeded);
+        }
+
+        let mut options = 0;
+        let mut config_entries = 0;
+        let mut pos = 0;
+        while pos < repr.options.len() {
+            options += 1;
+            if options > budget.max_options {
+                return Err(Error::BudgetExceeded);
+            }
+            let option = &repr.options[pos..];
+            pos += crate::options::option_wire_len(option)?;
+            if option[field::option_header::TYPE.start] == OptionType::Configuration.as_u8() {
+                for _ in ConfigurationOption::parse_from(option)?.map_while(|entry| entry.ok()) {
+                    config_entries += 1;
+                    if config_entries > budget.max_config_entries {
+                        return Err(Error::BudgetExceeded);
+                    }
+                }
+            }
+        }
+        Ok(repr)
+    }
+
     /// Parse a SOME/IP-SD packet into a high-level representation
     ///
     /// # Arguments
@@ -71,51 +146,1767 @@ impl<'a> Repr<'a> {
         })
     }
 
-    /// Emits the high-level representation of the SOME/IP-SD packet into the provided packet/buffer.
+    /// Parse a SOME/IP-SD packet and validate it against a policy
+    ///
+    /// This is the recommended entrypoint for untrusted input: it combines
+    /// `parse` and `validate_with`, so a `Repr` is only handed out once
+    /// every check the policy enables has passed.
+    ///
+    /// # Arguments
+    ///
+    /// * `packet` - The packet to parse
+    /// * `policy` - Which checks to run, e.g. `ValidationPolicy::relaxed()`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Repr)` - The parsed representation, if the message is valid
+    /// * `Err(Error)` - The parse error, or the error of the first failing check
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use someip_sd_wire::packet::Packet;
+    /// use someip_sd_wire::repr::{Repr, ValidationPolicy};
+    ///
+    /// let datagram = [0xC0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    /// let packet = Packet::new_checked(&datagram[..]).unwrap();
+    /// let repr = Repr::parse_validated(&packet, &ValidationPolicy::strict()).unwrap();
+    /// assert!(repr.is_empty());
+    /// ```
+    pub fn parse_validated<T>(
+        packet: &'a Packet<T>,
+        policy: &ValidationPolicy,
+    ) -> core::result::Result<Repr<'a>, Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        let repr = Self::parse(packet)?;
+        repr.validate_with(policy)?;
+        Ok(repr)
+    }
+
+    /// Parse a SOME/IP-SD message directly from a byte slice.
+    ///
+    /// This is the one-call decode entrypoint: it validates the buffer with
+    /// `Packet::new_checked` and returns a `Repr` borrowing from `buffer`.
+    /// Use `Repr::parse` when you already hold a `Packet`.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - The received SD message bytes
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Repr>` - The parsed representation or an error
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use someip_sd_wire::repr::Repr;
+    ///
+    /// let mut datagram = [0u8; 1500];
+    /// datagram[0] = 0xC0; // Reboot + Unicast
+    /// let n = 12; // bytes received
+    ///
+    /// let repr = Repr::parse_bytes(&datagram[..n]).unwrap();
+    /// assert_eq!(repr.flags, 0xC0);
+    /// assert!(repr.entries.is_empty());
+    /// ```
+    pub fn parse_bytes(buffer: &'a [u8]) -> core::result::Result<Repr<'a>, Error> {
+        let packet = Packet::new_checked(buffer)?;
+        let entries_len = packet.entries_length();
+        let options_len = packet.options_length();
+
+        Ok(Repr {
+            flags: packet.flags(),
+            reserved: packet.reserved(),
+            entries: &buffer[field::entries::ENTRIES_ARRAY(entries_len)],
+            options: &buffer[field::entries::OPTIONS_ARRAY(entries_len, options_len)],
+        })
+    }
+
+    /// Parse the SD message at the start of a buffer that may hold more data.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - Bytes starting with an SD message
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Repr, usize))` - The message and its total length in bytes
+    /// * `Err(Error::BufferTooShort)` - If the message is truncated
+    pub fn parse_with_len(buffer: &'a [u8]) -> core::result::Result<(Repr<'a>, usize), Error> {
+        let packet = Packet::new_checked(buffer)?;
+        let len = packet.total_length();
+        Ok((Repr::parse_bytes(&buffer[..len])?, len))
+    }
+
+    /// Emits the high-level representation of the SOME/IP-SD packet into the provided packet/buffer.
+    ///
+    /// This is the unchecked fast path: it panics if the packet's buffer is
+    /// shorter than `buffer_len()`. Prefer `emit_checked` unless the buffer
+    /// is known to be sized for the message.
+    ///
+    /// # Arguments
+    ///
+    /// * `packet` - A mutable reference to the packet where the high-level representation will be written.
+    pub fn emit<T>(&self, packet: &mut Packet<&mut T>)
+    where
+        T: AsRef<[u8]> + AsMut<[u8]> + ?Sized,
+    {
+        packet.set_flags(self.flags);
+        packet.set_reserved(self.reserved);
+        packet.set_entries_length(self.entries.len() as u32);
+        
+        // Copy entries data
+        let entries_mut = packet.entries_array_mut();
+        entries_mut.copy_from_slice(self.entries);
+
+        packet.set_options_length(self.options.len() as u32);
+        
+        // Copy options data
+        let options_mut = packet.options_array_mut();
+        options_mut.copy_from_slice(self.options);
+    }
+
+    /// Emits the message into a packet, checking the buffer size first.
+    ///
+    /// The safe default for emitting: the header length fields are written
+    /// from the slices, so the packet may have been created with
+    /// `Packet::new_unchecked` over a zeroed or reused buffer. Bytes past
+    /// `buffer_len()` are left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `packet` - The packet to write the message into
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the message was written
+    /// * `Err(Error::BufferTooShort)` - If the packet's buffer is shorter than `buffer_len()`
+    pub fn emit_checked<T>(&self, packet: &mut Packet<&mut T>) -> core::result::Result<(), Error>
+    where
+        T: AsRef<[u8]> + AsMut<[u8]> + ?Sized,
+    {
+        if packet.as_slice().len() < self.buffer_len() {
+            return Err(Error::BufferTooShort);
+        }
+        self.emit(packet);
+        Ok(())
+    }
+
+    /// Serializes the message field by field into a `std::io::Write`.
+    ///
+    /// Produces the same bytes as `emit` without an intermediate buffer, so
+    /// host tools can write straight into a file or socket.
+    ///
+    /// # Arguments
+    ///
+    /// * `w` - Destination writer
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - Number of bytes written (always `buffer_len()`)
+    /// * `Err(std::io::Error)` - The first error reported by the writer
+    #[cfg(feature = "std")]
+    pub fn write_to<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<usize> {
+        let reserved = self.reserved.to_be_bytes();
+        w.write_all(&[self.flags, reserved[1], reserved[2], reserved[3]])?;
+        w.write_all(&(self.entries.len() as u32).to_be_bytes())?;
+        w.write_all(self.entries)?;
+        w.write_all(&(self.options.len() as u32).to_be_bytes())?;
+        w.write_all(self.options)?;
+        Ok(self.buffer_len())
+    }
+
+    /// Re-emits the message with every finite entry TTL reduced by `delta_secs`.
+    ///
+    /// Used by caching relays to account for the time a message spent in the
+    /// cache. TTLs floor at 0, which turns the entry into a StopOffer or
+    /// StopSubscribe; infinite TTLs are left untouched. Options are copied
+    /// verbatim.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta_secs` - Elapsed time in seconds
+    /// * `out` - Buffer receiving the rewritten message
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - Length of the rewritten message
+    /// * `Err(Error::BufferTooShort)` - If `out` is too small
+    /// * `Err(Error::LengthOverflow)` - If the entries array is misaligned
+    /// * `Err(Error)` - If an entry fails to decode
+    pub fn with_decremented_ttls(&self, delta_secs: u32, out: &mut [u8]) -> core::result::Result<usize, Error> {
+        if !self.entries.len().is_multiple_of(EntryRepr::buffer_len()) {
+            return Err(Error::LengthOverflow);
+        }
+        let len = self.buffer_len();
+        let out = out.get_mut(..len).ok_or(Error::BufferTooShort)?;
+        self.emit(&mut Packet::new_unchecked(&mut *out));
+
+        let entries_range = field::entries::ENTRIES_ARRAY(self.entries.len());
+        for chunk in out[entries_range].chunks_exact_mut(EntryRepr::buffer_len()) {
+            let mut entry = EntryRepr::parse(chunk)?;
+            let ttl = match &mut entry {
+                EntryRepr::Service(service) => &mut service.ttl,
+                EntryRepr::EventGroup(eventgroup) => &mut eventgroup.ttl,
+            };
+            if *ttl != TTL_INFINITE {
+                *ttl = ttl.saturating_sub(delta_secs);
+            }
+            entry.emit(chunk);
+        }
+        Ok(len)
+    }
+
+    /// Iterate over the SD endpoint options (0x24/0x26) in this message.
+    ///
+    /// Service endpoint, multicast, and all other options are skipped, so the
+    /// result only ever describes where to send SOME/IP-SD traffic. A client
+    /// uses this to learn a server's SD socket for unicast discovery.
+    ///
+    /// # Returns
+    ///
+    /// An iterator yielding `Result<EndpointInfo>`; it yields one error and stops
+    /// if the options array is malformed.
+    pub fn sd_endpoints(&self) -> impl Iterator<Item = core::result::Result<EndpointInfo, Error>> + 'a {
+        let options = self.options;
+        let mut pos = 0;
+        core::iter::from_fn(move || {
+            while pos < options.len() {
+                let len = match crate::options::option_wire_len(&options[pos..]) {
+                    Ok(len) => len,
+                    Err(e) => {
+                        pos = options.len();
+                        return Some(Err(e));
+                    }
+                };
+                let option = &options[pos..pos + len];
+                pos += len;
+                if let Some(info) = EndpointInfo::from_sd_option(option).transpose() {
+                    return Some(info);
+                }
+            }
+            None
+        })
+    }
+
+    /// Iterate over the eventgroup entries belonging to one service.
+    ///
+    /// Only Subscribe/SubscribeAck entries whose service ID, instance ID, and
+    /// major version equal `key` are yielded; service entries and entries of
+    /// unknown type are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The service to match
+    ///
+    /// # Returns
+    ///
+    /// An iterator yielding `Result<EventGroupEntryRepr>`; a truncated entries
+    /// array yields `Error::BufferTooShort` as its last item.
+    pub fn eventgroups_for(
+        &self,
+        key: ServiceKey,
+    ) -> impl Iterator<Item = core::result::Result<EventGroupEntryRepr, Error>> + 'a {
+        EntriesIter::new_lenient(self.entries).filter_map(move |entry| match entry {
+            Ok(Entry::EventGroup(entry)) => match EventGroupEntryRepr::parse(&entry) {
+                Ok(repr) if ServiceKey::from(&repr) == key => Some(Ok(repr)),
+                Ok(_) => None,
+                Err(e) => Some(Err(e)),
+            },
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        })
+    }
+
+    /// Iterate over the (service ID, eventgroup ID) pair of every eventgroup entry.
+    ///
+    /// Service entries and entries of unknown type are skipped.
+    ///
+    /// # Returns
+    ///
+    /// An iterator yielding `Result<(u16, u16)>`; a truncated entries array
+    /// yields `Error::BufferTooShort` as its last item.
+    pub fn eventgroup_ids(&self) -> impl Iterator<Item = core::result::Result<(u16, u16), Error>> + 'a {
+        EntriesIter::new_lenient(self.entries).filter_map(|entry| match entry {
+            Ok(Entry::EventGroup(entry)) => Some(Ok((entry.service_id(), entry.eventgroup_id()))),
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        })
+    }
+
+    /// Validate that every entry's option runs stay within the options array.
+    ///
+    /// Several entries may legitimately reference the same options (e.g. two
+    /// offers sharing one endpoint). This checks the whole sharing graph at
+    /// once: the options array must split cleanly into whole options, and
+    /// every run's `index + count` must not exceed the number of options.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all references are in range
+    /// * `Err(Error::LengthOverflow)` if an option is truncated or a run points past the last option
+    /// * `Err(Error::BufferTooShort)` if the entries array ends in a partial entry
+    pub fn check_option_sharing(&self) -> core::result::Result<(), Error> {
+        let mut option_count = 0;
+        let mut pos = 0;
+        while pos < self.options.len() {
+            pos += crate::options::option_wire_len(&self.options[pos..])?;
+            option_count += 1;
+        }
+
+        for entry in EntriesIter::new_lenient(self.entries) {
+            let entry = entry?;
+            for (index, count) in [entry.first_run(), entry.second_run()].into_iter().flatten() {
+                if index as usize + count as usize > option_count {
+                    return Err(Error::LengthOverflow);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate a received message against a policy.
+    ///
+    /// Runs every check the policy enables, cheapest and most fundamental
+    /// first, and returns the first failure.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - Which checks to run
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the message passes every enabled check
+    /// * `Err(Error)` - The error of the first failing check
+    pub fn validate_with(&self, policy: &ValidationPolicy) -> core::result::Result<(), Error> {
+        if policy.alignment && !self.entries.len().is_multiple_of(Entry::<&[u8]>::LENGTH) {
+            return Err(Error::LengthOverflow);
+        }
+        if policy.exact_lengths {
+            self.check_lengths_exact()?;
+        }
+        if policy.option_lengths {
+            for option in OptionsIter::new_lenient(self.options) {
+                option?;
+            }
+        }
+        if policy.reserved {
+            crate::options::check_all_reserved(self.options)?;
+        }
+        if policy.protocols || policy.multicast_ranges {
+            self.check_endpoint_options(policy.protocols, policy.multicast_ranges)?;
+        }
+        if policy.option_runs {
+            self.check_option_sharing()?;
+        }
+        if policy.orphan_options {
+            self.check_no_orphan_options()?;
+        }
+        if policy.stop_offer_options {
+            for entry in EntriesIter::new_lenient(self.entries) {
+                if let Entry::Service(entry) = entry?
+                    && ServiceEntryRepr::parse(&entry)?.is_stop_offer_with_options()
+                {
+                    return Err(Error::InvalidEntry);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the protocol byte and multicast address range of endpoint options.
+    fn check_endpoint_options(&self, protocols: bool, multicast_ranges: bool) -> core::result::Result<(), Error> {
+        let mut pos = 0;
+        while pos < self.options.len() {
+            let len = crate::options::option_wire_len(&self.options[pos..])?;
+            let option = &self.options[pos..pos + len];
+            let (protocol, in_range) = match OptionType::from_u8(OptionHeader::new_checked(option)?.option_type()) {
+                Some(OptionType::IPv4Endpoint) => (IPv4EndpointOption::new_checked(option)?.check_protocol(), true),
+                Some(OptionType::IPv6Endpoint) => (IPv6EndpointOption::new_checked(option)?.check_protocol(), true),
+                Some(OptionType::IPv4Multicast) => {
+                    let option = IPv4MulticastOption::new_checked(option)?;
+                    (option.check_protocol(), option.ipv4_addr().is_multicast())
+                }
+                Some(OptionType::IPv6Multicast) => {
+                    let option = IPv6MulticastOption::new_checked(option)?;
+                    (option.check_protocol(), option.ipv6_addr().is_multicast())
+                }
+                Some(OptionType::IPv4SdEndpoint) => (IPv4SdEndpointOption::new_checked(option)?.check_protocol(), true),
+                Some(OptionType::IPv6SdEndpoint) => (IPv6SdEndpointOption::new_checked(option)?.check_protocol(), true),
+                Some(OptionType::Configuration | OptionType::LoadBalancing) | None => (Ok(()), true),
+            };
+            if protocols {
+                protocol?;
+            }
+            if multicast_ranges && !in_range {
+                return Err(Error::InvalidAddress);
+            }
+            pos += len;
+        }
+        Ok(())
+    }
+
+    /// Check that every option is referenced by at least one entry.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if no option is orphaned
+    /// * `Err(Error::OrphanOption(index))` for the first unreferenced option
+    /// * `Err(Error::LengthOverflow)` if an option is truncated or a run points past the last option
+    /// * `Err(Error::BufferTooShort)` if the entries array ends in a partial entry
+    pub fn check_no_orphan_options(&self) -> core::result::Result<(), Error> {
+        self.check_option_sharing()?;
+
+        // Runs start at a u8 index and span at most 15 options
+        let mut referenced = [0u64; 5];
+        for entry in EntriesIter::new_lenient(self.entries) {
+            let entry = entry?;
+            for (index, count) in [entry.first_run(), entry.second_run()].into_iter().flatten() {
+                for i in index as usize..index as usize + count as usize {
+                    referenced[i / 64] |= 1 << (i % 64);
+                }
+            }
+        }
+
+        let mut index = 0;
+        let mut pos = 0;
+        while pos < self.options.len() {
+            if referenced.get(index / 64).is_none_or(|word| word & (1 << (index % 64)) == 0) {
+                return Err(Error::OrphanOption(index));
+            }
+            pos += crate::options::option_wire_len(&self.options[pos..])?;
+            index += 1;
+        }
+        Ok(())
+    }
+
+    /// Check that the declared lengths match the content exactly.
+    ///
+    /// The entries array must hold a whole number of parseable entries and the
+    /// options array must consist of whole options with no slack. Trailing
+    /// padding and an option overrunning the declared length are both
+    /// rejected.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if both arrays are consumed exactly
+    /// * `Err(Error::LengthOverflow)` if either declared length doesn't match its content
+    /// * `Err(Error::InvalidEntryType)` if an entry has an unknown type
+    pub fn check_lengths_exact(&self) -> core::result::Result<(), Error> {
+        if !self.entries.len().is_multiple_of(Entry::<&[u8]>::LENGTH) {
+            return Err(Error::LengthOverflow);
+        }
+        for entry in EntriesIter::new(self.entries) {
+            entry?;
+        }
+
+        let mut pos = 0;
+        while pos < self.options.len() {
+            pos += crate::options::option_wire_len(&self.options[pos..]).map_err(|_| Error::LengthOverflow)?;
+        }
+        Ok(())
+    }
+
+    /// Resolve every entry's option runs into a list of (entry, options) pairs.
+    ///
+    /// Options referenced by several entries appear under each of them, and
+    /// entries without options get an empty list. Each entry's list holds its
+    /// first run followed by its second run, so the counts in the entry's
+    /// `NumberOfOptions` split it back into the two runs, e.g. to re-encode
+    /// the message with `MessageBuilder`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec)` - One pair per entry, in entries array order
+    /// * `Err(Error)` - If an entry or option fails to parse, or a run
+    ///   references options beyond the end of the options array
+    #[cfg(feature = "alloc")]
+    pub fn entry_option_map(
+        &self,
+    ) -> core::result::Result<alloc::vec::Vec<(EntryRepr, alloc::vec::Vec<OptionRepr<'a>>)>, Error> {
+        use alloc::vec::Vec;
+
+        let mut options = Vec::new();
+        let mut pos = 0;
+        while pos < self.options.len() {
+            let option = OptionRepr::parse(&self.options[pos..])?;
+            pos += option.buffer_len();
+            options.push(option);
+        }
+
+        let mut map = Vec::new();
+        for chunk in self.entries.chunks(Entry::<&[u8]>::LENGTH) {
+            let entry = Entry::from_buffer(chunk)?;
+            let mut resolved = Vec::new();
+            for (index, count) in [entry.first_run(), entry.second_run()].into_iter().flatten() {
+                let run = options
+                    .get(index as usize..index as usize + count as usize)
+                    .ok_or(Error::LengthOverflow)?;
+                resolved.extend_from_slice(run);
+            }
+            map.push((EntryRepr::parse(chunk)?, resolved));
+        }
+        Ok(map)
+    }
+
+    /// Copy the referenced entries and options into an `OwnedRepr`.
+    ///
+    /// # Returns
+    ///
+    /// * `OwnedRepr` - An owned copy of this message
+    #[cfg(feature = "alloc")]
+    pub fn to_owned(&self) -> OwnedRepr {
+        OwnedRepr {
+            flags: self.flags,
+            reserved: self.reserved,
+            entries: self.entries.to_vec(),
+            options: self.options.to_vec(),
+        }
+    }
+
+    /// Decode every option into a fixed-size array, without allocating.
+    ///
+    /// Unknown option types are kept as `SdOption::Unknown`. Slots past the
+    /// returned count are `None`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((array, count))` - The decoded options and how many there are
+    /// * `Err(Error::BufferTooShort)` - If the message has more than `N` options
+    /// * `Err(Error)` - If an option fails to parse
+    pub fn collect_options_array<const N: usize>(
+        &self,
+    ) -> core::result::Result<([Option<SdOption<'a>>; N], usize), Error> {
+        let mut options = [None; N];
+        let mut count = 0;
+        for option in OptionsIter::new_lenient(self.options) {
+            let slot = options.get_mut(count).ok_or(Error::BufferTooShort)?;
+            *slot = Some(option?);
+            count += 1;
+        }
+        Ok((options, count))
+    }
+
+    /// Split the options into recognized and unrecognized ones.
+    ///
+    /// Lets a store-and-forward gateway log option types it doesn't know
+    /// while still forwarding everything. Unrecognized options are kept as
+    /// their complete wire bytes, header included.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((KnownOptions, UnknownOptions))` - Both sets, in options array order
+    /// * `Err(Error::BufferTooShort)` - If either set exceeds `MAX_PARTITIONED_OPTIONS`
+    /// * `Err(Error)` - If an option fails to parse
+    pub fn partition_options(&self) -> core::result::Result<(KnownOptions<'a>, UnknownOptions<'a>), Error> {
+        let mut known = KnownOptions { options: [None; MAX_PARTITIONED_OPTIONS], len: 0 };
+        let mut unknown = UnknownOptions { options: [(0, &[]); MAX_PARTITIONED_OPTIONS], len: 0 };
+        for option in OptionsIter::new_lenient(self.options).with_offsets() {
+            let option = option?;
+            if let SdOption::Unknown { type_byte, data } = option.option {
+                let end = option.offset + field::option_header::DISCARDABLE_FLAG_AND_RESERVED.end + data.len();
+                let slot = unknown.options.get_mut(unknown.len).ok_or(Error::BufferTooShort)?;
+                *slot = (type_byte, &self.options[option.offset..end]);
+                unknown.len += 1;
+            } else {
+                let slot = known.options.get_mut(known.len).ok_or(Error::BufferTooShort)?;
+                *slot = Some(option.option);
+                known.len += 1;
+            }
+        }
+        Ok((known, unknown))
+    }
+
+    /// Check whether a conformant receiver must reject this message.
+    ///
+    /// True if any option has an unrecognized type and its discardable bit
+    /// clear: a receiver may only skip unknown options marked discardable.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - Whether such an option is present
+    /// * `Err(Error)` - If the options array can't be walked
+    pub fn has_nondiscardable_unknown_options(&self) -> core::result::Result<bool, Error> {
+        for option in OptionsIter::new_lenient(self.options).with_offsets() {
+            let option = option?;
+            if matches!(option.option, SdOption::Unknown { .. }) {
+                let flag = self.options[option.offset + field::option_header::DISCARDABLE_FLAG_AND_RESERVED.start];
+                if !DiscardableFlag::from_u8(flag).is_discardable() {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Hash the message's semantic content, for deduplicating announcements.
+    ///
+    /// Covers the same content `semantically_equivalent` compares, minus the
+    /// entry TTLs: two announcements that differ only in TTL, option order
+    /// or option-run indices hash identically. Uses 64-bit FNV-1a, so the
+    /// value is stable across runs and platforms but not collision-resistant
+    /// against adversarial input. A message whose options can't be decoded is
+    /// hashed over its raw bytes instead.
+    ///
+    /// # Returns
+    ///
+    /// * `u64` - The content hash
+    pub fn content_hash(&self) -> u64 {
+        content_hash(self).unwrap_or_else(|_| {
+            let mut hasher = Fnv1a::new();
+            hasher.write(&[self.flags]);
+            hasher.write(&self.reserved.to_be_bytes());
+            hasher.write(self.entries);
+            hasher.write(self.options);
+            hasher.finish()
+        })
+    }
+
+    /// Decode every entry and option, reporting each one to `sink`.
+    ///
+    /// Items are reported in wire order with their byte offset within the SD
+    /// message (the flags byte being offset 0). A bad entry or an unknown
+    /// option type is reported as an error and decoding continues with the
+    /// next item. Only a malformed option header stops the options walk, as
+    /// the next option's position can't be known.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - Called once per item
+    pub fn decode_report(&self, sink: &mut impl FnMut(ItemResult<'a>)) {
+        let entries_start = field::entries::ENTRIES_ARRAY(0).start;
+        for (i, chunk) in self.entries.chunks(Entry::<&[u8]>::LENGTH).enumerate() {
+            sink(ItemResult {
+                offset: entries_start + i * Entry::<&[u8]>::LENGTH,
+                item: Entry::from_buffer(chunk).map(DecodedItem::Entry),
+            });
+        }
+
+        let options_start = field::entries::OPTIONS_ARRAY(self.entries.len(), 0).start;
+        let mut pos = 0;
+        while pos < self.options.len() {
+            let offset = options_start + pos;
+            match SdOption::parse(&self.options[pos..]) {
+                Ok((SdOption::Unknown { type_byte, .. }, len)) => {
+                    sink(ItemResult { offset, item: Err(Error::InvalidOptionType(type_byte)) });
+                    pos += len;
+                }
+                Ok((option, len)) => {
+                    sink(ItemResult { offset, item: Ok(DecodedItem::Option(option)) });
+                    pos += len;
+                }
+                Err(e) => {
+                    sink(ItemResult { offset, item: Err(e) });
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Iterate over the options referenced by one entry.
+    ///
+    /// The options array is walked once up front to locate every option;
+    /// the iterator then yields the options of the entry's first run
+    /// followed by those of its second run. A run with a zero count is
+    /// empty whatever its index.
+    ///
+    /// # Arguments
+    ///
+    /// * `entry_index` - Position of the entry in the entries array
+    ///
+    /// # Returns
+    ///
+    /// * `EntryOptionsIter` - Yields each referenced option. Yields a single
+    ///   `Err(Error::BufferTooShort)` if there is no entry at `entry_index`,
+    ///   `Err(Error::LengthOverflow)` if the options array is malformed or
+    ///   a run points past the last option, after which iteration ends
+    pub fn options_for_entry(&self, entry_index: usize) -> EntryOptionsIter<'a> {
+        let mut iter = EntryOptionsIter {
+            table: [&[]; MAX_OPTIONS],
+            option_count: 0,
+            runs: [(0, 0); 2],
+            run: 0,
+            error: None,
+        };
+        let entry = entry_index
+            .checked_mul(Entry::<&[u8]>::LENGTH)
+            .and_then(|start| self.entries.get(start..))
+            .ok_or(Error::BufferTooShort)
+            .and_then(Entry::from_buffer_lenient);
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                iter.error = Some(e);
+                return iter;
+            }
+        };
+        match option_table(self.options, &mut iter.table) {
+            Ok(count) => iter.option_count = count,
+            Err(e) => {
+                iter.error = Some(e);
+                return iter;
+            }
+        }
+        for (slot, run) in iter.runs.iter_mut().zip([entry.first_run(), entry.second_run()]) {
+            if let Some((index, count)) = run {
+                *slot = (index as usize, index as usize + count as usize);
+            }
+        }
+        iter
+    }
+
+    /// Write a multi-line, human-readable decode of the message.
+    ///
+    /// The first line describes the header, followed by one line per entry
+    /// with its type, IDs, version and TTL. Each entry's resolved options
+    /// (first run, then second run) are listed indented below it. Nothing
+    /// is allocated; the text goes straight to `out`.
+    ///
+    /// ```text
+    /// SOME/IP-SD flags=0xC0 [reboot unicast] entries=1 options=1
+    ///   [0] OfferService service=0x1234 instance=0x0001 version=1.0 ttl=3s
+    ///       option[0] IPv4Endpoint 192.168.0.1:30509 UDP
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - Sink receiving the text
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the whole message was written
+    /// * `Err(Error::BufferTooShort)` - If `out` refused the text
+    /// * `Err(Error)` - If an entry or referenced option fails to parse
+    pub fn dump(&self, out: &mut impl fmt::Write) -> core::result::Result<(), Error> {
+        let options = OptionsIter::new(self.options).count();
+        write!(out, "SOME/IP-SD flags=0x{:02X} [", self.flags).map_err(|_| Error::BufferTooShort)?;
+        let names = [(0x80, "reboot"), (0x40, "unicast"), (0x20, "explicit-initial-data")];
+        let mut sep = "";
+        for (_, name) in names.into_iter().filter(|(bit, _)| self.flags & bit != 0) {
+            write!(out, "{}{}", sep, name).map_err(|_| Error::BufferTooShort)?;
+            sep = " ";
+        }
+        writeln!(out, "] entries={} options={}", self.entries.len() / Entry::<&[u8]>::LENGTH, options)
+            .map_err(|_| Error::BufferTooShort)?;
+
+        for (i, chunk) in self.entries.chunks(Entry::<&[u8]>::LENGTH).enumerate() {
+            dump_entry(out, i, &EntryRepr::parse(chunk)?).map_err(|_| Error::BufferTooShort)?;
+            let entry = Entry::from_buffer(chunk)?;
+            for (index, count) in [entry.first_run(), entry.second_run()].into_iter().flatten() {
+                let mut pos = crate::options::offset_of(self.options, index as usize)?;
+                for n in 0..count as usize {
+                    let option = OptionRepr::parse(self.options.get(pos..).ok_or(Error::LengthOverflow)?)?;
+                    pos += option.buffer_len();
+                    dump_option(out, index as usize + n, &option).map_err(|_| Error::BufferTooShort)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check whether this is an empty (keep-alive) message.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the message carries neither entries nor options
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty() && self.options.is_empty()
+    }
+
+    /// Classify the message by its overall intent.
+    ///
+    /// A message whose entries all share one type is classified by that type;
+    /// offers are further split by TTL and the reboot flag (0x80), which a
+    /// sender keeps set after startup until its session ID wraps, so early
+    /// cyclic offers also count as initial. Stop entries for subscriptions are
+    /// still a `Subscription`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MessageClass)` - The message's class
+    /// * `Err(Error)` - If an entry fails to parse
+    pub fn classify(&self) -> core::result::Result<MessageClass, Error> {
+        let mut class = None;
+        for chunk in self.entries.chunks(Entry::<&[u8]>::LENGTH) {
+            let entry_class = match EntryRepr::parse(chunk)? {
+                EntryRepr::Service(repr) => match repr.entry_type {
+                    EntryType::FindService => MessageClass::FindQuery,
+                    _ if repr.ttl == 0 => MessageClass::StopOffer,
+                    _ if self.flags & 0x80 != 0 => MessageClass::InitialOffer,
+                    _ => MessageClass::CyclicOffer,
+                },
+                EntryRepr::EventGroup(repr) => match repr.entry_type {
+                    EntryType::SubscribeAck => MessageClass::Acknowledgment,
+                    _ => MessageClass::Subscription,
+                },
+            };
+            match class {
+                Some(c) if c != entry_class => return Ok(MessageClass::Mixed),
+                _ => class = Some(entry_class),
+            }
+        }
+        Ok(class.unwrap_or(MessageClass::KeepAlive))
+    }
+
+    /// Get the total wire format size needed for this representation
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The total size in bytes
+    pub fn buffer_len(&self) -> usize {
+        field::entries::OPTIONS_ARRAY(self.entries.len(), self.options.len()).end
+    }
+}
+
+/// One item of a `Repr::decode_report`.
+#[derive(Debug, Clone, Copy)]
+pub struct ItemResult<'a> {
+    /// Byte offset of the item within the SD message
+    pub offset: usize,
+    /// The decoded item, or why it failed to decode
+    pub item: core::result::Result<DecodedItem<'a>, Error>,
+}
+
+/// A successfully decoded entry or option.
+#[derive(Debug, Clone, Copy)]
+pub enum DecodedItem<'a> {
+    /// An entry from the entries array.
+    Entry(Entry<&'a [u8]>),
+    /// An option from the options array.
+    Option(SdOption<'a>),
+}
+
+/// Capacity of each set returned by `Repr::partition_options`.
+pub const MAX_PARTITIONED_OPTIONS: usize = 32;
+
+/// Options of recognized types, as returned by `Repr::partition_options`.
+#[derive(Debug, Clone, Copy)]
+pub struct KnownOptions<'a> {
+    options: [Option<SdOption<'a>>; MAX_PARTITIONED_OPTIONS],
+    len: usize,
+}
+
+impl<'a> KnownOptions<'a> {
+    /// Number of recognized options.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no recognized options.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Iterate over the recognized options in options array order.
+    pub fn iter(&self) -> impl Iterator<Item = SdOption<'a>> + '_ {
+        self.options[..self.len].iter().flatten().copied()
+    }
+}
+
+/// Options of unrecognized types, as returned by `Repr::partition_options`.
+#[derive(Debug, Clone, Copy)]
+pub struct UnknownOptions<'a> {
+    options: [(u8, &'a [u8]); MAX_PARTITIONED_OPTIONS],
+    len: usize,
+}
+
+impl<'a> UnknownOptions<'a> {
+    /// Get the unrecognized options as `(type_byte, option_bytes)` pairs.
+    ///
+    /// The bytes cover the complete option, header included, so they can be
+    /// forwarded verbatim.
+    pub fn as_slice(&self) -> &[(u8, &'a [u8])] {
+        &self.options[..self.len]
+    }
+}
+
+/// Limits on the content `Repr::parse_within` accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Budget {
+    /// Maximum number of entries
+    pub max_entries: usize,
+    /// Maximum number of options
+    pub max_options: usize,
+    /// Maximum number of configuration entries, summed over all
+    /// Configuration options
+    pub max_config_entries: usize,
+}
+
+impl Default for Budget {
+    /// 256 of each: the 8-bit option run index can address at most 256
+    /// options, and the other limits use the same bound.
+    fn default() -> Self {
+        Budget {
+            max_entries: 256,
+            max_options: 256,
+            max_config_entries: 256,
+        }
+    }
+}
+
+/// Which checks `Repr::validate_with` runs on a received message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationPolicy {
+    /// Entries array is a whole number of 16-byte entries
+    pub alignment: bool,
+    /// Option header reserved bits are zero
+    pub reserved: bool,
+    /// Known option types have their fixed lengths
+    pub option_lengths: bool,
+    /// Endpoint options carry TCP or UDP
+    pub protocols: bool,
+    /// Multicast options carry multicast addresses
+    pub multicast_ranges: bool,
+    /// Option runs stay within the options array
+    pub option_runs: bool,
+    /// Every option is referenced by some entry
+    pub orphan_options: bool,
+    /// Declared lengths match the content with no slack
+    pub exact_lengths: bool,
+    /// StopOffer entries reference no options
+    pub stop_offer_options: bool,
+}
+
+impl ValidationPolicy {
+    /// Enable every check, for conformance testing.
+    pub const fn strict() -> Self {
+        ValidationPolicy {
+            alignment: true,
+            reserved: true,
+            option_lengths: true,
+            protocols: true,
+            multicast_ranges: true,
+            option_runs: true,
+            orphan_options: true,
+            exact_lengths: true,
+            stop_offer_options: true,
+        }
+    }
+
+    /// Enable only the checks needed to process a message safely, tolerating
+    /// the sloppiness seen from real-world senders.
+    pub const fn relaxed() -> Self {
+        ValidationPolicy {
+            alignment: true,
+            reserved: false,
+            option_lengths: true,
+            protocols: false,
+            multicast_ranges: false,
+            option_runs: true,
+            orphan_options: false,
+            exact_lengths: false,
+            stop_offer_options: false,
+        }
+    }
+}
+
+/// Overall intent of a SOME/IP-SD message, as determined by `Repr::classify`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageClass {
+    /// Offers sent with the reboot flag set, i.e. shortly after startup.
+    InitialOffer,
+    /// Offers repeated during the main phase.
+    CyclicOffer,
+    /// Offers with TTL 0 withdrawing services.
+    StopOffer,
+    /// FindService queries.
+    FindQuery,
+    /// Subscribe (or StopSubscribe) entries.
+    Subscription,
+    /// SubscribeAck (or SubscribeNack) entries.
+    Acknowledgment,
+    /// Entries of more than one of the above classes.
+    Mixed,
+    /// No entries at all.
+    KeepAlive,
+}
+
+/// An owned copy of a SOME/IP-SD message.
+///
+/// Holds the entries and options arrays in `Vec`s so a decoded message can
+/// outlive the receive buffer, e.g. to be sent across a channel or stored.
+/// Use `OwnedRepr::as_repr` to borrow it back as a `Repr`.
+#[cfg(feature = "alloc")]
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct OwnedRepr {
+    /// Flags (1 byte) - typically used for reboot/unicast flags
+    pub flags: u8,
+    /// Reserved field (3 bytes) - should be 0x000000
+    pub reserved: u32,
+    /// Entries array (variable length)
+    pub entries: alloc::vec::Vec<u8>,
+    /// Options array (variable length)
+    pub options: alloc::vec::Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl OwnedRepr {
+    /// Borrow this message as a `Repr`.
+    ///
+    /// # Returns
+    ///
+    /// A `Repr` referencing the owned entries and options arrays
+    pub fn as_repr(&self) -> Repr<'_> {
+        Repr {
+            flags: self.flags,
+            reserved: self.reserved,
+            entries: &self.entries,
+            options: &self.options,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<Repr<'a>> for OwnedRepr {
+    fn from(repr: Repr<'a>) -> Self {
+        repr.to_owned()
+    }
+}
+
+impl<'a> fmt::Display for Repr<'a> {
+    /// Formats the high-level representation as a string.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SOME/IP-SD Message: flags=0x{:02X}, entries_len={}, options_len={}",
+            self.flags,
+            self.entries.len(),
+            self.options.len()
+        )
+    }
+}
+
+/// Write one entry line of `Repr::dump`.
+fn dump_entry(out: &mut impl fmt::Write, i: usize, entry: &EntryRepr) -> fmt::Result {
+    match entry {
+        EntryRepr::Service(e) => writeln!(
+            out,
+            "  [{}] {} service=0x{:04X} instance=0x{:04X} version={}.{} ttl={}",
+            i,
+            e.entry_type.name(),
+            e.service_id,
+            e.instance_id,
+            e.major_version,
+            e.minor_version,
+            e.ttl_typed()
+        ),
+        EntryRepr::EventGroup(e) => writeln!(
+            out,
+            "  [{}] {} service=0x{:04X} instance=0x{:04X} eventgroup=0x{:04X} version={} counter={} ttl={}",
+            i,
+            e.entry_type.name(),
+            e.service_id,
+            e.instance_id,
+            e.eventgroup_id,
+            e.major_version,
+            e.reserved_and_counter.counter(),
+            e.ttl_typed()
+        ),
+    }
+}
+
+/// Write one option line of `Repr::dump`.
+fn dump_option(out: &mut impl fmt::Write, index: usize, option: &OptionRepr) -> fmt::Result {
+    write!(out, "      option[{}] ", index)?;
+    let (name, endpoint) = match *option {
+        OptionRepr::IPv4Endpoint(o) => (
+            "IPv4Endpoint",
+            EndpointInfo::IPv4 { address: o.ipv4_address, protocol: o.protocol, port: o.port },
+        ),
+        OptionRepr::IPv6Endpoint(o) => (
+            "IPv6Endpoint",
+            EndpointInfo::IPv6 { address: o.ipv6_address, protocol: o.protocol, port: o.port },
+        ),
+        OptionRepr::IPv4Multicast(o) => (
+            "IPv4Multicast",
+            EndpointInfo::IPv4 { address: o.ipv4_address, protocol: TransportProtocol::UDP, port: o.port },
+        ),
+        OptionRepr::IPv6Multicast(o) => (
+            "IPv6Multicast",
+            EndpointInfo::IPv6 { address: o.ipv6_address, protocol: TransportProtocol::UDP, port: o.port },
+        ),
+        OptionRepr::IPv4SdEndpoint(o) => ("IPv4SdEndpoint", o.into()),
+        OptionRepr::IPv6SdEndpoint(o) => ("IPv6SdEndpoint", o.into()),
+        OptionRepr::Configuration(bytes) => return writeln!(out, "Configuration ({} bytes)", bytes.len()),
+        OptionRepr::LoadBalancing(o) => {
+            return writeln!(out, "LoadBalancing priority={} weight={}", o.priority, o.weight);
+        }
+        OptionRepr::Raw(bytes) => {
+            let type_byte = bytes.get(field::option_header::TYPE.start).copied().unwrap_or(0);
+            return writeln!(out, "Unknown(0x{:02X}) ({} bytes)", type_byte, bytes.len());
+        }
+    };
+    write!(out, "{} ", name)?;
+    match endpoint {
+        EndpointInfo::IPv4 { address: [a, b, c, d], protocol, port } => {
+            writeln!(out, "{}.{}.{}.{}:{} {:?}", a, b, c, d, port, protocol)
+        }
+        EndpointInfo::IPv6 { address, protocol, port } => {
+            write!(out, "[")?;
+            for (i, group) in address.chunks(2).enumerate() {
+                let sep = if i == 0 { "" } else { ":" };
+                write!(out, "{}{:x}", sep, u16::from_be_bytes([group[0], group[1]]))?;
+            }
+            writeln!(out, "]:{} {:?}", port, protocol)
+        }
+    }
+}
+
+/// Maximum number of options per message supported by the whole-message helpers.
+pub const MAX_OPTIONS: usize = 64;
+
+/// Split an options array into per-option slices.
+///
+/// # Returns
+/// * `Ok(usize)` - Number of options written into `table`
+/// * `Err(Error::LengthOverflow)` if an option header is malformed or there are
+///   more than `MAX_OPTIONS` options
+fn option_table<'a>(options: &'a [u8], table: &mut [&'a [u8]; MAX_OPTIONS]) -> core::result::Result<usize, Error> {
+    let mut count = 0;
+    let mut pos = 0;
+    while pos < options.len() {
+        if count == MAX_OPTIONS {
+            return Err(Error::LengthOverflow);
+        }
+        let len = crate::options::option_wire_len(&options[pos..])?;
+        table[count] = &options[pos..pos + len];
+        count += 1;
+        pos += len;
+    }
+    Ok(count)
+}
+
+/// 64-bit FNV-1a hasher.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Fnv1a(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    /// Hash a byte string together with its length, so adjacent fields can't
+    /// run into each other.
+    fn write_delimited(&mut self, bytes: &[u8]) {
+        self.write(&(bytes.len() as u32).to_be_bytes());
+        self.write(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Compute `Repr::content_hash`, failing if the options can't be decoded.
+fn content_hash(repr: &Repr) -> core::result::Result<u64, Error> {
+    let mut table: [&[u8]; MAX_OPTIONS] = [&[]; MAX_OPTIONS];
+    let count = option_table(repr.options, &mut table)?;
+    let table = &table[..count];
+
+    let mut hasher = Fnv1a::new();
+    hasher.write(&[repr.flags]);
+    hasher.write(&repr.reserved.to_be_bytes());
+    hasher.write(&(repr.entries.len() as u32).to_be_bytes());
+    for chunk in repr.entries.chunks(Entry::<&[u8]>::LENGTH) {
+        if chunk.len() < Entry::<&[u8]>::LENGTH {
+            return Err(Error::LengthOverflow);
+        }
+        // Skip the option-run indices (1..3) and the TTL (9..12)
+        hasher.write(&chunk[..1]);
+        hasher.write(&chunk[3..field::service_entry::TTL.start]);
+        hasher.write(&chunk[field::service_entry::TTL.end..]);
+
+        let entry = Entry::from_buffer_lenient(chunk)?;
+        for run in [entry.first_run(), entry.second_run()] {
+            let mut options: [&[u8]; 16] = [&[]; 16];
+            let len = sorted_run(table, run, &mut options)?;
+            for option in &options[..len] {
+                hasher.write_delimited(option);
+            }
+        }
+    }
+
+    let mut sorted: [&[u8]; MAX_OPTIONS] = [&[]; MAX_OPTIONS];
+    sorted[..count].copy_from_slice(table);
+    sorted[..count].sort_unstable();
+    for option in &sorted[..count] {
+        hasher.write_delimited(option);
+    }
+    Ok(hasher.finish())
+}
+
+/// Collect the options of one run, sorted so runs can be compared regardless of order.
+fn sorted_run<'a>(
+    table: &[&'a [u8]],
+    run: Option<(u8, u8)>,
+    out: &mut [&'a [u8]; 16],
+) -> core::result::Result<usize, Error> {
+    let (index, count) = match run {
+        Some(run) => (run.0 as usize, run.1 as usize),
+        None => return Ok(0),
+    };
+    if index + count > table.len() {
+        return Err(Error::LengthOverflow);
+    }
+    out[..count].copy_from_slice(&table[index..index + count]);
+    out[..count].sort_unstable();
+    Ok(count)
+}
+
+/// Iterate over SD messages concatenated back to back in one buffer.
+///
+/// # Arguments
+///
+/// * `buf` - Buffer holding zero or more complete SD messages
+///
+/// # Returns
+///
+/// * `MessageIter` - Yields each message in turn; a trailing partial message
+///   yields `Err(Error::BufferTooShort)` and ends the iteration
+pub fn messages(buf: &[u8]) -> MessageIter<'_> {
+    MessageIter { data: buf }
+}
+
+/// Iterator over concatenated SD messages, created by `messages`.
+#[derive(Debug, Clone)]
+pub struct MessageIter<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for MessageIter<'a> {
+    type Item = core::result::Result<Repr<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+        match Repr::parse_with_len(self.data) {
+            Ok((repr, len)) => {
+                self.data = &self.data[len..];
+                Some(Ok(repr))
+            }
+            Err(e) => {
+                self.data = &[];
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Iterator over the options of one entry, created by `Repr::options_for_entry`.
+#[derive(Debug, Clone)]
+pub struct EntryOptionsIter<'a> {
+    table: [&'a [u8]; MAX_OPTIONS],
+    option_count: usize,
+    /// Next option index and end index of each run
+    runs: [(usize, usize); 2],
+    run: usize,
+    error: Option<Error>,
+}
+
+impl<'a> Iterator for EntryOptionsIter<'a> {
+    type Item = core::result::Result<SdOption<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(e) = self.error.take() {
+            self.run = self.runs.len();
+            return Some(Err(e));
+        }
+        while let Some((next, end)) = self.runs.get_mut(self.run) {
+            if *next < *end {
+                let index = *next;
+                *next += 1;
+                if index >= self.option_count {
+                    self.run = self.runs.len();
+                    return Some(Err(Error::LengthOverflow));
+                }
+                return Some(SdOption::parse(self.table[index]).map(|(option, _)| option));
+            }
+            self.run += 1;
+        }
+        None
+    }
+}
+
+/// Check whether an offered service answers a find query.
+///
+/// The service ID must match exactly. The find's instance ID `0xFFFF`, major
+/// version `0xFF` and minor version `0xFFFFFFFF` each match any value in the
+/// offer; every other field value must be equal. Wildcards are only honored on
+/// the find side, so a malformed offer carrying wildcard values still only
+/// matches finds with the same values.
+///
+/// # Arguments
+///
+/// * `find` - The FindService entry
+/// * `offer` - The OfferService entry
+///
+/// # Returns
+///
+/// * `bool` - Whether the offer satisfies the find
+pub fn find_matches_offer(find: &ServiceEntryRepr, offer: &ServiceEntryRepr) -> bool {
+    find.service_id == offer.service_id
+        && (find.instance_id == 0xFFFF || find.instance_id == offer.instance_id)
+        && (find.major_version == 0xFF || find.major_version == offer.major_version)
+        && (find.minor_version == 0xFFFF_FFFF || find.minor_version == offer.minor_version)
+}
+
+/// Check whether two SOME/IP-SD messages are equivalent modulo option ordering.
+///
+/// Both messages are decoded and compared semantically: header flags and
+/// reserved field must match, the options arrays must contain the same options
+/// (in any order), and the entries must match pairwise in order, with each
+/// entry's option runs resolving to the same options even when they live at
+/// different indices. Byte comparison of the option-run index fields is skipped.
+///
+/// Only messages with up to `MAX_OPTIONS` options are supported.
+///
+/// # Arguments
+///
+/// * `a` - First SD message
+/// * `b` - Second SD message
+///
+/// # Returns
+///
+/// * `Ok(bool)` - Whether the messages are semantically equivalent
+/// * `Err(Error)` - If either message fails to decode, has a misaligned entries
+///   array, or references options that don't exist
+pub fn semantically_equivalent(a: &[u8], b: &[u8]) -> core::result::Result<bool, Error> {
+    let a = Repr::parse_bytes(a)?;
+    let b = Repr::parse_bytes(b)?;
+
+    if a.entries.len() % Entry::<&[u8]>::LENGTH != 0 || b.entries.len() % Entry::<&[u8]>::LENGTH != 0 {
+        return Err(Error::LengthOverflow);
+    }
+    if a.flags != b.flags || a.reserved != b.reserved || a.entries.len() != b.entries.len() {
+        return Ok(false);
+    }
+
+    let mut a_table: [&[u8]; MAX_OPTIONS] = [&[]; MAX_OPTIONS];
+    let mut b_table: [&[u8]; MAX_OPTIONS] = [&[]; MAX_OPTIONS];
+    let a_count = option_table(a.options, &mut a_table)?;
+    let b_count = option_table(b.options, &mut b_table)?;
+    let a_table = &a_table[..a_count];
+    let b_table = &b_table[..b_count];
+
+    for (a_chunk, b_chunk) in a
+        .entries
+        .chunks(Entry::<&[u8]>::LENGTH)
+        .zip(b.entries.chunks(Entry::<&[u8]>::LENGTH))
+    {
+        // Everything but the two option-run index bytes must match exactly.
+        if a_chunk[0] != b_chunk[0] || a_chunk[3..] != b_chunk[3..] {
+            return Ok(false);
+        }
+
+        let a_entry = Entry::from_buffer_lenient(a_chunk)?;
+        let b_entry = Entry::from_buffer_lenient(b_chunk)?;
+        let runs = [
+            (a_entry.first_run(), b_entry.first_run()),
+            (a_entry.second_run(), b_entry.second_run()),
+        ];
+        for (a_run, b_run) in runs {
+            let mut a_opts: [&[u8]; 16] = [&[]; 16];
+            let mut b_opts: [&[u8]; 16] = [&[]; 16];
+            let a_len = sorted_run(a_table, a_run, &mut a_opts)?;
+            let b_len = sorted_run(b_table, b_run, &mut b_opts)?;
+            if a_opts[..a_len] != b_opts[..b_len] {
+                return Ok(false);
+            }
+        }
+    }
+
+    let mut a_sorted: [&[u8]; MAX_OPTIONS] = [&[]; MAX_OPTIONS];
+    let mut b_sorted: [&[u8]; MAX_OPTIONS] = [&[]; MAX_OPTIONS];
+    a_sorted[..a_count].copy_from_slice(a_table);
+    b_sorted[..b_count].copy_from_slice(b_table);
+    a_sorted[..a_count].sort_unstable();
+    b_sorted[..b_count].sort_unstable();
+
+    Ok(a_sorted[..a_count] == b_sorted[..b_count])
+}
+
+/// Sort key of an option in normalized form: type byte first, then the raw bytes.
+fn option_key<'a>(option: &&'a [u8]) -> (u8, &'a [u8]) {
+    (option[field::option_header::TYPE.start], option)
+}
+
+/// Compare two sorted runs option by option using `option_key`.
+fn cmp_runs(a: &[&[u8]], b: &[&[u8]]) -> core::cmp::Ordering {
+    a.iter().map(option_key).cmp(b.iter().map(option_key))
+}
+
+/// Collect one run of a 16-byte entry, sorted by `option_key`.
+fn normalized_run<'a>(
+    table: &[&'a [u8]],
+    entry: &[u8],
+    second: bool,
+    out: &mut [&'a [u8]; 16],
+) -> core::result::Result<usize, Error> {
+    let entry = Entry::from_buffer_lenient(entry)?;
+    let run = if second { entry.second_run() } else { entry.first_run() };
+    let count = sorted_run(table, run, out)?;
+    out[..count].sort_unstable_by_key(option_key);
+    Ok(count)
+}
+
+/// Find where `run` occurs as a contiguous window of `layout`.
+fn find_run(layout: &[&[u8]], run: &[&[u8]]) -> Option<usize> {
+    layout.windows(run.len()).position(|window| window == run)
+}
+
+/// Rewrite a SOME/IP-SD message into a canonical byte form.
+///
+/// The result doesn't depend on how the sender ordered its options: the
+/// options of each run are sorted by (type, bytes), distinct runs are laid
+/// out in sorted order (a run already present as a contiguous window is
+/// reused), and options no entry references follow at the end, sorted the
+/// same way. Entry option-run indices are rewritten to match, with empty runs
+/// getting index 0. Entries keep their order. Two messages for which
+/// `semantically_equivalent` holds normalize to the same bytes.
+///
+/// Only messages with up to `MAX_OPTIONS` options are supported.
+///
+/// # Arguments
+///
+/// * `input` - The SD message to normalize
+/// * `out` - Buffer receiving the normalized message
+///
+/// # Returns
+///
+/// * `Ok(usize)` - Length of the normalized message
+/// * `Err(Error::BufferTooShort)` - If `input` is truncated or `out` is too small
+/// * `Err(Error::LengthOverflow)` - If the entries array is misaligned, a run
+///   references missing options, or the layout exceeds `MAX_OPTIONS` options
+pub fn normalize(input: &[u8], out: &mut [u8]) -> core::result::Result<usize, Error> {
+    let repr = Repr::parse_bytes(input)?;
+    if !repr.entries.len().is_multiple_of(Entry::<&[u8]>::LENGTH) {
+        return Err(Error::LengthOverflow);
+    }
+    let mut table: [&[u8]; MAX_OPTIONS] = [&[]; MAX_OPTIONS];
+    let count = option_table(repr.options, &mut table)?;
+    let table = &table[..count];
+    let entries = || repr.entries.chunks(Entry::<&[u8]>::LENGTH);
+
+    // Lay out the distinct runs in ascending order, one selection pass each.
+    let mut layout: [&[u8]; MAX_OPTIONS] = [&[]; MAX_OPTIONS];
+    let mut layout_len = 0;
+    let mut previous: Option<([&[u8]; 16], usize)> = None;
+    loop {
+        let mut next: Option<([&[u8]; 16], usize)> = None;
+        for entry in entries() {
+            for second in [false, true] {
+                let mut run: [&[u8]; 16] = [&[]; 16];
+                let len = normalized_run(table, entry, second, &mut run)?;
+                if len == 0 {
+                    continue;
+                }
+                if let Some((prev, prev_len)) = &previous
+                    && cmp_runs(&run[..len], &prev[..*prev_len]).is_le()
+                {
+                    continue;
+                }
+                if let Some((best, best_len)) = &next
+                    && cmp_runs(&run[..len], &best[..*best_len]).is_ge()
+                {
+                    continue;
+                }
+                next = Some((run, len));
+            }
+        }
+        let Some((run, len)) = next else {
+            break;
+        };
+        if find_run(&layout[..layout_len], &run[..len]).is_none() {
+            if layout_len + len > MAX_OPTIONS {
+                return Err(Error::LengthOverflow);
+            }
+            layout[layout_len..layout_len + len].copy_from_slice(&run[..len]);
+            layout_len += len;
+        }
+        previous = Some((run, len));
+    }
+
+    // Append the options no entry references.
+    let mut referenced = [false; MAX_OPTIONS];
+    for entry in entries() {
+        let entry = Entry::from_buffer_lenient(entry)?;
+        for (index, count) in [entry.first_run(), entry.second_run()].into_iter().flatten() {
+            referenced[index as usize..index as usize + count as usize].fill(true);
+        }
+    }
+    let leftover_start = layout_len;
+    for (option, _) in table.iter().zip(referenced).filter(|(_, referenced)| !referenced) {
+        if layout_len == MAX_OPTIONS {
+            return Err(Error::LengthOverflow);
+        }
+        layout[layout_len] = option;
+        layout_len += 1;
+    }
+    layout[leftover_start..layout_len].sort_unstable_by_key(option_key);
+    let layout = &layout[..layout_len];
+
+    let entries_len = repr.entries.len();
+    let options_len: usize = layout.iter().map(|option| option.len()).sum();
+    let total = field::entries::OPTIONS_ARRAY(entries_len, options_len).end;
+    if out.len() < total {
+        return Err(Error::BufferTooShort);
+    }
+
+    let mut packet = Packet::new_unchecked(&mut out[..total]);
+    packet.set_flags(repr.flags);
+    packet.set_reserved(repr.reserved);
+    packet.set_entries_length(entries_len as u32);
+    packet.set_options_length(options_len as u32);
+
+    let out_entries = packet.entries_array_mut();
+    for (entry, out_entry) in entries().zip(out_entries.chunks_mut(Entry::<&[u8]>::LENGTH)) {
+        out_entry.copy_from_slice(entry);
+        let index_fields = [
+            field::service_entry::INDEX_FIRST_OPTION_RUN.start,
+            field::service_entry::INDEX_SECOND_OPTION_RUN.start,
+        ];
+        for (second, index_field) in [false, true].into_iter().zip(index_fields) {
+            let mut run: [&[u8]; 16] = [&[]; 16];
+            let len = normalized_run(table, entry, second, &mut run)?;
+            out_entry[index_field] = if len == 0 {
+                0
+            } else {
+                find_run(layout, &run[..len]).unwrap_or(0) as u8
+            };
+        }
+    }
+
+    let out_options = packet.options_array_mut();
+    let mut offset = 0;
+    for option in layout {
+        out_options[offset..offset + option.len()].copy_from_slice(option);
+        offset += option.len();
+    }
+
+    Ok(total)
+}
+
+/// Number of entries that fit into one message of at most `mtu` bytes.
+///
+/// Accounts for the 12 bytes of fixed header and length fields and the
+/// given options array, so a catalog can be chunked without off-by-12
+/// mistakes. See `split_for_mtu` for splitting a message automatically.
+///
+/// # Arguments
+///
+/// * `mtu` - Maximum size of the SD message in bytes
+/// * `options_wire_len` - Size of the options array in bytes
+///
+/// # Returns
+///
+/// The number of 16-byte entries that fit, 0 if even the options don't fit
+pub const fn entries_capacity(mtu: usize, options_wire_len: usize) -> usize {
+    let header_len = field::entries::OPTIONS_LENGTH(0).end;
+    mtu.saturating_sub(header_len + options_wire_len) / EntryRepr::buffer_len()
+}
+
+/// Split entries and options into successive messages that each fit an MTU.
+///
+/// The returned `Splitter` packs as many entries as fit into each message and
+/// copies the options they reference along with them. Option runs shared by
+/// several entries of the same message are emitted once, and option-run
+/// indices are rewritten to point into each message's own options array.
+///
+/// # Arguments
+///
+/// * `flags` - Flags byte written into every message
+/// * `entries` - All entries to send, in order
+/// * `options` - Options array the entries' option runs index into
+/// * `mtu` - Maximum size of each SD message in bytes
+///
+/// # Returns
+///
+/// A `Splitter` producing the messages one by one via `Splitter::next_message`
+pub fn split_for_mtu<'s, 'a>(
+    flags: u8,
+    entries: &'s [EntryRepr],
+    options: &'s [OptionRepr<'a>],
+    mtu: usize,
+) -> Splitter<'s, 'a> {
+    Splitter {
+        flags,
+        entries,
+        options,
+        mtu,
+        next_entry: 0,
+    }
+}
+
+/// Maximum number of distinct option runs in a single split message.
+const MAX_SPLIT_RUNS: usize = MAX_OPTIONS;
+
+/// An option run copied into a split message.
+#[derive(Debug, Clone, Copy)]
+struct SplitRun {
+    /// Index of the run in the original options array
+    start: u8,
+    /// Number of options in the run
+    count: u8,
+    /// Index of the run in the split message's options array
+    local_start: u8,
+}
+
+/// Emits MTU-bounded SOME/IP-SD messages, created by `split_for_mtu`.
+#[derive(Debug, Clone)]
+pub struct Splitter<'s, 'a> {
+    flags: u8,
+    entries: &'s [EntryRepr],
+    options: &'s [OptionRepr<'a>],
+    mtu: usize,
+    next_entry: usize,
+}
+
+impl<'s, 'a> Splitter<'s, 'a> {
+    /// Write the next message into `out`.
     ///
     /// # Arguments
     ///
-    /// * `packet` - A mutable reference to the packet where the high-level representation will be written.
-    pub fn emit<T>(&self, packet: &mut Packet<&mut T>)
-    where
-        T: AsRef<[u8]> + AsMut<[u8]> + ?Sized,
-    {
-        packet.set_flags(self.flags);
-        packet.set_reserved(self.reserved);
-        packet.set_entries_length(self.entries.len() as u32);
-        
-        // Copy entries data
-        let entries_mut = packet.entries_array_mut();
-        entries_mut.copy_from_slice(self.entries);
-
-        packet.set_options_length(self.options.len() as u32);
-        
-        // Copy options data
-        let options_mut = packet.options_array_mut();
-        options_mut.copy_from_slice(self.options);
-    }
-
-    /// Get the total wire format size needed for this representation
+    /// * `out` - Buffer receiving the SD message
     ///
     /// # Returns
     ///
-    /// * `usize` - The total size in bytes
-    pub fn buffer_len(&self) -> usize {
-        use crate::field;
-        field::entries::OPTIONS_ARRAY(self.entries.len(), self.options.len()).end
-    }
-}
+    /// * `None` - All entries have been emitted
+    /// * `Some(Ok(usize))` - Length of the message written into `out`
+    /// * `Some(Err(Error::BufferTooShort))` - A single entry with its options
+    ///   doesn't fit `mtu`, or `out` is too small for the message
+    /// * `Some(Err(Error::LengthOverflow))` - An entry references options
+    ///   beyond the end of the options array
+    pub fn next_message(&mut self, out: &mut [u8]) -> Option<core::result::Result<usize, Error>> {
+        if self.next_entry >= self.entries.len() {
+            return None;
+        }
 
-impl<'a> fmt::Display for Repr<'a> {
-    /// Formats the high-level representation as a string.
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "SOME/IP-SD Message: flags=0x{:02X}, entries_len={}, options_len={}",
-            self.flags,
-            self.entries.len(),
-            self.options.len()
-        )
+        let mut runs = [SplitRun { start: 0, count: 0, local_start: 0 }; MAX_SPLIT_RUNS];
+        let mut run_count = 0;
+        let mut option_count = 0usize;
+        let mut options_len = 0usize;
+        let mut entry_count = 0usize;
+
+        // First pass: decide which entries and option runs go into this message.
+        for entry in &self.entries[self.next_entry..] {
+            let number = entry.number_of_options();
+            let wanted = [
+                (entry.index_first_option_run(), number.options1()),
+                (entry.index_second_option_run(), number.options2()),
+            ];
+
+            let mut new_runs = [SplitRun { start: 0, count: 0, local_start: 0 }; 2];
+            let mut new_run_count = 0;
+            let mut new_options = 0usize;
+            let mut new_len = 0usize;
+            for (start, count) in wanted {
+                if count == 0 {
+                    continue;
+                }
+                let seen = runs[..run_count]
+                    .iter()
+                    .chain(&new_runs[..new_run_count])
+                    .any(|run| run.start == start && run.count == count);
+                if seen {
+                    continue;
+                }
+                let range = start as usize..start as usize + count as usize;
+                let Some(run_options) = self.options.get(range) else {
+                    self.next_entry = self.entries.len();
+                    return Some(Err(Error::LengthOverflow));
+                };
+                new_runs[new_run_count] = SplitRun {
+                    start,
+                    count,
+                    local_start: 0,
+                };
+                new_run_count += 1;
+                new_options += count as usize;
+                new_len += run_options.iter().map(OptionRepr::buffer_len).sum::<usize>();
+            }
+
+            let entries_len = (entry_count + 1) * EntryRepr::buffer_len();
+            let total = field::entries::OPTIONS_ARRAY(entries_len, options_len + new_len).end;
+            // Every run start must stay addressable by the 8-bit index fields.
+            let last_run = new_runs[..new_run_count].last().map_or(0, |run| run.count as usize);
+            let last_local_start = option_count + new_options - last_run;
+            if total > self.mtu
+                || run_count + new_run_count > MAX_SPLIT_RUNS
+                || last_local_start > u8::MAX as usize
+            {
+                if entry_count == 0 {
+                    self.next_entry = self.entries.len();
+                    return Some(Err(Error::BufferTooShort));
+                }
+                break;
+            }
+
+            for run in &new_runs[..new_run_count] {
+                runs[run_count] = SplitRun {
+                    local_start: option_count as u8,
+                    ..*run
+                };
+                run_count += 1;
+                option_count += run.count as usize;
+            }
+            options_len += new_len;
+            entry_count += 1;
+        }
+
+        // Second pass: write the message.
+        let entries_len = entry_count * EntryRepr::buffer_len();
+        let entries_range = field::entries::ENTRIES_ARRAY(entries_len);
+        let options_range = field::entries::OPTIONS_ARRAY(entries_len, options_len);
+        let total = options_range.end;
+        if out.len() < total {
+            return Some(Err(Error::BufferTooShort));
+        }
+
+        let runs = &runs[..run_count];
+        let local_index = |start: u8, count: u8| {
+            runs.iter()
+                .find(|run| run.start == start && run.count == count)
+                .map_or(0, |run| run.local_start)
+        };
+
+        let mut packet = Packet::new_unchecked(&mut out[..total]);
+        packet.set_flags(self.flags);
+        packet.set_reserved(0);
+        packet.set_entries_length(entries_len as u32);
+        packet.set_options_length(options_len as u32);
+
+        let entries = &self.entries[self.next_entry..self.next_entry + entry_count];
+        for (i, entry) in entries.iter().enumerate() {
+            let number = entry.number_of_options();
+            let mut local = *entry;
+            local.set_option_run_indices(
+                local_index(entry.index_first_option_run(), number.options1()),
+                local_index(entry.index_second_option_run(), number.options2()),
+            );
+            let offset = entries_range.start + i * EntryRepr::buffer_len();
+            local.emit(&mut out[offset..offset + EntryRepr::buffer_len()]);
+        }
+
+        let mut offset = options_range.start;
+        for run in runs {
+            let start = run.start as usize;
+            for option in &self.options[start..start + run.count as usize] {
+                offset += option.emit(&mut out[offset..]);
+            }
+        }
+
+        self.next_entry += entry_count;
+        Some(Ok(total))
     }
 }
 
@@ -157,6 +1948,378 @@ mod tests {
         assert_eq!(parsed.options, original.options);
     }
 
+    #[test]
+    fn test_repr_parse_bytes() {
+        let entries_data = [0xAA; 16];
+        let options_data = [0xBB; 8];
+        let original = Repr::new(0x80, &entries_data, &options_data);
+
+        let mut buffer = [0u8; 12 + 16 + 8];
+        let mut packet = Packet::new_unchecked(&mut buffer[..]);
+        original.emit(&mut packet);
+
+        let parsed = Repr::parse_bytes(&buffer).unwrap();
+        assert_eq!(parsed, original);
+
+        assert_eq!(Repr::parse_bytes(&buffer[..20]), Err(Error::BufferTooShort));
+    }
+
+    fn offer_entry(service_id: u16, index: u8) -> [u8; 16] {
+        use crate::entries::{EntryType, NumberOfOptions, ServiceEntry, ServiceEntryRepr};
+
+        let mut buf = [0u8; 16];
+        ServiceEntryRepr {
+            entry_type: EntryType::OfferService,
+            index_first_option_run: index,
+            index_second_option_run: 0,
+            number_of_options: NumberOfOptions::from_options(1, 0),
+            service_id,
+            instance_id: 1,
+            major_version: 1,
+            ttl: 3,
+            minor_version: 0,
+        }
+        .emit(&mut ServiceEntry::new_unchecked(&mut buf[..]));
+        buf
+    }
+
+    fn endpoint_option(addr: [u8; 4], port: u16) -> [u8; 12] {
+        use crate::options::{IPv4EndpointOptionRepr, TransportProtocol};
+
+        let mut buf = [0u8; 12];
+        IPv4EndpointOptionRepr {
+            ipv4_address: addr,
+            protocol: TransportProtocol::UDP,
+            port,
+        }
+        .emit(&mut buf);
+        buf
+    }
+
+    fn message(entries: &[[u8; 16]], options: &[[u8; 12]], buf: &mut [u8]) -> usize {
+        let mut entries_data = [0u8; 64];
+        let mut options_data = [0u8; 64];
+        for (i, e) in entries.iter().enumerate() {
+            entries_data[i * 16..(i + 1) * 16].copy_from_slice(e);
+        }
+        for (i, o) in options.iter().enumerate() {
+            options_data[i * 12..(i + 1) * 12].copy_from_slice(o);
+        }
+        let repr = Repr::new(
+            0xC0,
+            &entries_data[..entries.len() * 16],
+            &options_data[..options.len() * 12],
+        );
+        let len = repr.buffer_len();
+        repr.emit(&mut Packet::new_unchecked(&mut buf[..len]));
+        len
+    }
+
+    #[test]
+    fn test_find_matches_offer() {
+        use crate::entries::NumberOfOptions;
+
+        let offer = ServiceEntryRepr {
+            entry_type: EntryType::OfferService,
+            index_first_option_run: 0,
+            index_second_option_run: 0,
+            number_of_options: NumberOfOptions::new(),
+            service_id: 0x1234,
+            instance_id: 0x0001,
+            major_version: 2,
+            ttl: 3,
+            minor_version: 7,
+        };
+        let exact = ServiceEntryRepr { entry_type: EntryType::FindService, ..offer };
+
+        // Every combination of the three wildcards matches.
+        for mask in 0..8u8 {
+            let find = ServiceEntryRepr {
+                instance_id: if mask & 1 != 0 { 0xFFFF } else { offer.instance_id },
+                major_version: if mask & 2 != 0 { 0xFF } else { offer.major_version },
+                minor_version: if mask & 4 != 0 { 0xFFFF_FFFF } else { offer.minor_version },
+                ..exact
+            };
+            assert!(find_matches_offer(&find, &offer), "mask {mask}");
+        }
+
+        // Each non-wildcard mismatch fails, even with the other fields wildcarded.
+        let any = ServiceEntryRepr { instance_id: 0xFFFF, major_version: 0xFF, minor_version: 0xFFFF_FFFF, ..exact };
+        assert!(!find_matches_offer(&ServiceEntryRepr { service_id: 0x4321, ..any }, &offer));
+        assert!(!find_matches_offer(&ServiceEntryRepr { instance_id: 0x0002, ..any }, &offer));
+        assert!(!find_matches_offer(&ServiceEntryRepr { major_version: 1, ..any }, &offer));
+        assert!(!find_matches_offer(&ServiceEntryRepr { minor_version: 6, ..any }, &offer));
+
+        // Wildcards only apply on the find side.
+        let wildcard_offer = ServiceEntryRepr { instance_id: 0xFFFF, ..offer };
+        assert!(!find_matches_offer(&exact, &wildcard_offer));
+        assert!(find_matches_offer(&any, &wildcard_offer));
+    }
+
+    #[test]
+    fn test_semantically_equivalent_reordered_options() {
+        let ep1 = endpoint_option([192, 168, 1, 1], 30000);
+        let ep2 = endpoint_option([10, 0, 0, 1], 40000);
+
+        let mut a = [0u8; 128];
+        let a_len = message(&[offer_entry(0x1111, 0), offer_entry(0x2222, 1)], &[ep1, ep2], &mut a);
+        let mut b = [0u8; 128];
+        let b_len = message(&[offer_entry(0x1111, 1), offer_entry(0x2222, 0)], &[ep2, ep1], &mut b);
+        assert_ne!(a[..a_len], b[..b_len]);
+        assert_eq!(semantically_equivalent(&a[..a_len], &b[..b_len]), Ok(true));
+
+        // Entry pointing at the wrong endpoint after reordering
+        let mut c = [0u8; 128];
+        let c_len = message(&[offer_entry(0x1111, 0), offer_entry(0x2222, 1)], &[ep2, ep1], &mut c);
+        assert_eq!(semantically_equivalent(&a[..a_len], &c[..c_len]), Ok(false));
+
+        // Different entry content
+        let mut d = [0u8; 128];
+        let d_len = message(&[offer_entry(0x1111, 0), offer_entry(0x3333, 1)], &[ep1, ep2], &mut d);
+        assert_eq!(semantically_equivalent(&a[..a_len], &d[..d_len]), Ok(false));
+    }
+
+    #[test]
+    fn test_semantically_equivalent_dangling_reference() {
+        let ep1 = endpoint_option([192, 168, 1, 1], 30000);
+        let mut a = [0u8; 128];
+        let a_len = message(&[offer_entry(0x1111, 4)], &[ep1], &mut a);
+        assert_eq!(semantically_equivalent(&a[..a_len], &a[..a_len]), Err(Error::LengthOverflow));
+    }
+
+    #[test]
+    fn test_repr_sd_endpoints() {
+        use crate::options::TransportProtocol;
+
+        let mut options = [0u8; 12 + 12 + 24];
+        options[..12].copy_from_slice(&endpoint_option([192, 168, 1, 1], 30501));
+        // IPv4 SD endpoint
+        options[12..24].copy_from_slice(&endpoint_option([192, 168, 1, 2], 30490));
+        options[12 + 2] = OptionType::IPv4SdEndpoint.as_u8();
+        // IPv6 SD endpoint
+        options[24..26].copy_from_slice(&[0x00, 0x15]);
+        options[26] = OptionType::IPv6SdEndpoint.as_u8();
+        options[28] = 0xFE;
+        options[29] = 0x80;
+        options[24 + 21] = TransportProtocol::UDP.as_u8();
+        options[24 + 22..].copy_from_slice(&30490u16.to_be_bytes());
+
+        let repr = Repr::new(0x00, &[], &options);
+        let mut endpoints = repr.sd_endpoints();
+        assert_eq!(
+            endpoints.next(),
+            Some(Ok(EndpointInfo::IPv4 {
+                address: [192, 168, 1, 2],
+                protocol: TransportProtocol::UDP,
+                port: 30490,
+            }))
+        );
+        match endpoints.next() {
+            Some(Ok(EndpointInfo::IPv6 { address, protocol, port })) => {
+                assert_eq!(address[..2], [0xFE, 0x80]);
+                assert_eq!(protocol, TransportProtocol::UDP);
+                assert_eq!(port, 30490);
+            }
+            other => panic!("expected IPv6 SD endpoint, got {:?}", other),
+        }
+        assert_eq!(endpoints.next(), None);
+
+        // Truncated options array
+        let repr = Repr::new(0x00, &[], &options[..20]);
+        let mut endpoints = repr.sd_endpoints();
+        assert_eq!(endpoints.next(), Some(Err(Error::LengthOverflow)));
+        assert_eq!(endpoints.next(), None);
+    }
+
+    #[test]
+    fn test_repr_eventgroups_for() {
+        use crate::entries::{EntryType, EventGroupEntry, NumberOfOptions, ReservedAndCounter};
+
+        let subscribe = |service_id, eventgroup_id| {
+            let mut buf = [0u8; 16];
+            EventGroupEntryRepr {
+                entry_type: EntryType::SubscribeAck,
+                index_first_option_run: 0,
+                index_second_option_run: 0,
+                number_of_options: NumberOfOptions::new(),
+                service_id,
+                instance_id: 1,
+                major_version: 1,
+                ttl: 3,
+                reserved_and_counter: ReservedAndCounter::new(),
+                eventgroup_id,
+            }
+            .emit(&mut EventGroupEntry::new_unchecked(&mut buf[..]));
+            buf
+        };
+        let mut entries = [0u8; 64];
+        entries[..16].copy_from_slice(&subscribe(0x1234, 0x10));
+        entries[16..32].copy_from_slice(&offer_entry(0x1234, 0));
+        entries[32..48].copy_from_slice(&subscribe(0x5678, 0x20));
+        entries[48..].copy_from_slice(&subscribe(0x1234, 0x30));
+
+        let repr = Repr::new(0x00, &entries, &[]);
+        let mut matches = repr.eventgroups_for(ServiceKey::new(0x1234, 1, 1));
+        assert_eq!(matches.next().unwrap().unwrap().eventgroup_id, 0x10);
+        assert_eq!(matches.next().unwrap().unwrap().eventgroup_id, 0x30);
+        assert!(matches.next().is_none());
+        assert_eq!(repr.eventgroups_for(ServiceKey::new(0x1234, 1, 2)).count(), 0);
+
+        let mut ids = repr.eventgroup_ids();
+        assert_eq!(ids.next(), Some(Ok((0x1234, 0x10))));
+        assert_eq!(ids.next(), Some(Ok((0x5678, 0x20))));
+        assert_eq!(ids.next(), Some(Ok((0x1234, 0x30))));
+        assert!(ids.next().is_none());
+
+        let repr = Repr::new(0x00, &entries[..40], &[]);
+        let mut matches = repr.eventgroups_for(ServiceKey::new(0x5678, 1, 1));
+        assert_eq!(matches.next(), Some(Err(Error::BufferTooShort)));
+        assert!(matches.next().is_none());
+        let mut ids = repr.eventgroup_ids().skip(1);
+        assert_eq!(ids.next(), Some(Err(Error::BufferTooShort)));
+        assert!(ids.next().is_none());
+    }
+
+    #[test]
+    fn test_repr_check_option_sharing() {
+        let ep = endpoint_option([192, 168, 1, 1], 30000);
+        let mut buf = [0u8; 128];
+
+        // Two offers sharing a single endpoint option
+        let len = message(&[offer_entry(0x1111, 0), offer_entry(0x2222, 0)], &[ep], &mut buf);
+        assert_eq!(Repr::parse_bytes(&buf[..len]).unwrap().check_option_sharing(), Ok(()));
+
+        // Second offer points past the last option
+        let len = message(&[offer_entry(0x1111, 0), offer_entry(0x2222, 1)], &[ep], &mut buf);
+        assert_eq!(
+            Repr::parse_bytes(&buf[..len]).unwrap().check_option_sharing(),
+            Err(Error::LengthOverflow)
+        );
+
+        // Options array ending in a partial option
+        let entries = offer_entry(0x1111, 0);
+        let repr = Repr::new(0x00, &entries, &ep[..8]);
+        assert_eq!(repr.check_option_sharing(), Err(Error::LengthOverflow));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_repr_write_to() {
+        let entries = offer_entry(0x1234, 0);
+        let options = endpoint_option([10, 0, 0, 1], 30509);
+        let mut buf = [0u8; 64];
+        let len = message(&[entries], &[options], &mut buf);
+        let repr = Repr { reserved: 0x00ABCDEF, ..Repr::parse_bytes(&buf[..len]).unwrap() };
+
+        let mut written = std::vec::Vec::new();
+        assert_eq!(repr.write_to(&mut written).unwrap(), len);
+        let mut expected = [0u8; 64];
+        repr.emit(&mut Packet::new_unchecked(&mut expected[..len]));
+        assert_eq!(written, &expected[..len]);
+
+        let mut short = [0u8; 20];
+        assert!(repr.write_to(&mut &mut short[..]).is_err());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_repr_to_owned() {
+        let entries = offer_entry(0x1234, 0);
+        let options = endpoint_option([10, 0, 0, 1], 30509);
+        let mut buf = [0u8; 64];
+        let len = message(&[entries], &[options], &mut buf);
+
+        let owned = Repr::parse_bytes(&buf[..len]).unwrap().to_owned();
+        buf.fill(0);
+        assert_eq!(owned.flags, 0xC0);
+        assert_eq!(owned.as_repr(), Repr::new(0xC0, &entries, &options));
+        assert_eq!(OwnedRepr::from(owned.as_repr()), owned);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_repr_entry_option_map() {
+        let mut entries = [0u8; 48];
+        entries[..16].copy_from_slice(&offer_entry(0x1000, 0));
+        entries[16..32].copy_from_slice(&offer_entry(0x1001, 0));
+        entries[32..].copy_from_slice(&offer_entry(0x1002, 0));
+        // Third entry has no options
+        entries[32 + 3] = 0x00;
+        let options = endpoint_option([10, 0, 0, 1], 30509);
+
+        let repr = Repr::new(0x00, &entries, &options);
+        let map = repr.entry_option_map().unwrap();
+        assert_eq!(map.len(), 3);
+        let shared = OptionRepr::parse(&options).unwrap();
+        assert_eq!(map[0].1, [shared]);
+        assert_eq!(map[1].1, [shared]);
+        assert!(map[2].1.is_empty());
+        assert_eq!(map[1].0, EntryRepr::parse(&entries[16..32]).unwrap());
+
+        let repr = Repr::new(0x00, &entries, &[]);
+        assert_eq!(repr.entry_option_map(), Err(Error::LengthOverflow));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_repr_entry_option_map_roundtrip() {
+        use crate::builder::MessageBuilder;
+
+        let mut entries = [0u8; 48];
+        entries[..16].copy_from_slice(&offer_entry(0x1000, 0));
+        entries[16..32].copy_from_slice(&offer_entry(0x1001, 1));
+        entries[32..].copy_from_slice(&offer_entry(0x1002, 0));
+        // Third entry: first run [0], second run [1]
+        entries[32 + 2] = 1;
+        entries[32 + 3] = 0x11;
+        let mut options = [0u8; 24];
+        options[..12].copy_from_slice(&endpoint_option([10, 0, 0, 1], 30509));
+        options[12..].copy_from_slice(&endpoint_option([10, 0, 0, 2], 30510));
+        let repr = Repr::new(0xC0, &entries, &options);
+        let mut buffer = [0u8; 128];
+        let original_len = repr.buffer_len();
+        repr.emit(&mut Packet::new_unchecked(&mut buffer[..original_len]));
+
+        let decoded = repr.entry_option_map().unwrap();
+        assert_eq!(decoded[2].1.len(), 2);
+
+        let mut builder = MessageBuilder::new(0xC0);
+        for (entry, options) in &decoded {
+            let refs: std::vec::Vec<_> = options.iter().map(|o| builder.add_option(*o).unwrap()).collect();
+            let (first_run, second_run) = refs.split_at(entry.number_of_options().options1() as usize);
+            builder.add_entry(*entry, first_run, second_run).unwrap();
+        }
+        let mut rebuilt = [0u8; 128];
+        let rebuilt_len = builder.finish(&mut rebuilt).unwrap();
+        assert!(semantically_equivalent(&buffer[..original_len], &rebuilt[..rebuilt_len]).unwrap());
+        let rebuilt = Repr::parse_bytes(&rebuilt[..rebuilt_len]).unwrap();
+        let roundtrip = rebuilt.entry_option_map().unwrap();
+        for ((entry, options), (original_entry, original_options)) in roundtrip.iter().zip(&decoded) {
+            assert_eq!(options, original_options);
+            assert_eq!(entry.number_of_options(), original_entry.number_of_options());
+        }
+    }
+
+    #[test]
+    fn test_repr_emit_checked() {
+        let entries = offer_entry(0x1234, 0);
+        let options = endpoint_option([10, 0, 0, 1], 30509);
+        let repr = Repr::new(0xC0, &entries, &options);
+
+        // Stale length fields in a reused buffer don't matter
+        let mut buffer = [0xFFu8; 64];
+        repr.emit_checked(&mut Packet::new_unchecked(&mut buffer[..])).unwrap();
+        assert_eq!(Repr::parse_with_len(&buffer), Ok((repr, repr.buffer_len())));
+        assert_eq!(buffer[repr.buffer_len()], 0xFF);
+
+        let mut short = [0u8; 12 + 16 + 11];
+        assert_eq!(
+            repr.emit_checked(&mut Packet::new_unchecked(&mut short[..])),
+            Err(Error::BufferTooShort)
+        );
+        assert_eq!(short, [0u8; 12 + 16 + 11]);
+    }
+
     #[test]
     fn test_repr_buffer_len() {
         let entries = [0u8; 32];
@@ -181,4 +2344,572 @@ mod tests {
         assert_eq!(packet.entries_length(), 0);
         assert_eq!(packet.options_length(), 0);
     }
+
+    #[test]
+    fn test_normalize() {
+        let e1 = endpoint_option([10, 0, 0, 1], 30509);
+        let e2 = endpoint_option([10, 0, 0, 2], 30509);
+        let unused = endpoint_option([10, 0, 0, 3], 30509);
+
+        let mut a = [0u8; 128];
+        let a_len = message(&[offer_entry(0x1111, 0), offer_entry(0x2222, 1)], &[e1, e2, unused], &mut a);
+        let mut b = [0u8; 128];
+        let b_len = message(&[offer_entry(0x1111, 2), offer_entry(0x2222, 0)], &[e2, unused, e1], &mut b);
+        assert_ne!(a[..a_len], b[..b_len]);
+
+        let mut a_norm = [0u8; 128];
+        let mut b_norm = [0u8; 128];
+        let a_norm_len = normalize(&a[..a_len], &mut a_norm).unwrap();
+        let b_norm_len = normalize(&b[..b_len], &mut b_norm).unwrap();
+        assert_eq!(a_norm[..a_norm_len], b_norm[..b_norm_len]);
+        assert_eq!(semantically_equivalent(&a[..a_len], &a_norm[..a_norm_len]), Ok(true));
+
+        let repr = Repr::parse_bytes(&a_norm[..a_norm_len]).unwrap();
+        assert_eq!(repr.check_option_sharing(), Ok(()));
+        assert_eq!(repr.options[..12], e1);
+        assert_eq!(repr.options[12..24], e2);
+        assert_eq!(repr.options[24..], unused);
+
+        assert_eq!(normalize(&a[..a_len], &mut a_norm[..a_len - 1]), Err(Error::BufferTooShort));
+    }
+
+    #[test]
+    fn test_repr_validate_with() {
+        let strict = ValidationPolicy::strict();
+        let relaxed = ValidationPolicy::relaxed();
+        let entries = offer_entry(0x1234, 0);
+        let mut options = [0u8; 24];
+        options[..12].copy_from_slice(&endpoint_option([10, 0, 0, 1], 30509));
+        assert_eq!(Repr::new(0xC0, &entries, &options[..12]).validate_with(&strict), Ok(()));
+
+        // Orphaned second option
+        options[12..].copy_from_slice(&endpoint_option([10, 0, 0, 2], 30509));
+        let orphaned = Repr::new(0xC0, &entries, &options);
+        assert_eq!(orphaned.validate_with(&strict), Err(Error::OrphanOption(1)));
+        assert_eq!(orphaned.validate_with(&relaxed), Ok(()));
+
+        let mut dirty = options;
+        dirty[3] = 0x01;
+        let dirty = Repr::new(0xC0, &entries, &dirty[..12]);
+        assert_eq!(dirty.validate_with(&strict), Err(Error::ReservedNotZero(0)));
+        assert_eq!(dirty.validate_with(&relaxed), Ok(()));
+
+        let mut bad_protocol = options;
+        bad_protocol[9] = 0x42;
+        assert_eq!(
+            Repr::new(0xC0, &entries, &bad_protocol[..12]).validate_with(&strict),
+            Err(Error::InvalidProtocol(0x42))
+        );
+
+        let mut multicast = options;
+        multicast[2] = 0x14;
+        assert_eq!(Repr::new(0xC0, &entries, &multicast[..12]).validate_with(&strict), Err(Error::InvalidAddress));
+        multicast[4] = 239;
+        assert_eq!(Repr::new(0xC0, &entries, &multicast[..12]).validate_with(&strict), Ok(()));
+        // Multicast is UDP-only
+        multicast[9] = TransportProtocol::TCP.as_u8();
+        assert_eq!(
+            Repr::new(0xC0, &entries, &multicast[..12]).validate_with(&strict),
+            Err(Error::InvalidProtocol(0x06))
+        );
+
+        assert_eq!(Repr::new(0xC0, &entries[..8], &[]).validate_with(&relaxed), Err(Error::LengthOverflow));
+        assert_eq!(Repr::new(0xC0, &entries, &[]).validate_with(&relaxed), Err(Error::LengthOverflow));
+    }
+
+    #[test]
+    fn test_repr_stop_offer_with_options() {
+        let mut entries = offer_entry(0x1234, 0);
+        entries[field::service_entry::TTL].fill(0);
+        let options = endpoint_option([10, 0, 0, 1], 30509);
+        let repr = Repr::new(0xC0, &entries, &options);
+
+        let stop = ServiceEntryRepr::parse(&crate::entries::ServiceEntry::new_unchecked(&entries[..])).unwrap();
+        assert!(stop.is_stop_offer_with_options());
+        assert!(!ServiceEntryRepr { ttl: 3, ..stop }.is_stop_offer_with_options());
+        assert!(!ServiceEntryRepr { entry_type: EntryType::FindService, ..stop }.is_stop_offer_with_options());
+
+        // The runs still resolve to the endpoint option
+        let entry = Entry::from_buffer(&entries[..]).unwrap();
+        assert_eq!(entry.first_run(), Some((0, 1)));
+        assert_eq!(crate::options::offset_of(repr.options, 0), Ok(0));
+        assert_eq!(repr.check_option_sharing(), Ok(()));
+        assert_eq!(repr.check_no_orphan_options(), Ok(()));
+
+        assert_eq!(repr.validate_with(&ValidationPolicy::strict()), Err(Error::InvalidEntry));
+        assert_eq!(repr.validate_with(&ValidationPolicy::relaxed()), Ok(()));
+    }
+
+    #[test]
+    fn test_repr_check_no_orphan_options() {
+        let mut options = [0u8; 24];
+        options[..12].copy_from_slice(&endpoint_option([10, 0, 0, 1], 30509));
+        options[12..].copy_from_slice(&endpoint_option([10, 0, 0, 2], 30509));
+        let mut entries = [0u8; 32];
+        entries[..16].copy_from_slice(&offer_entry(0x1234, 0));
+        entries[16..].copy_from_slice(&offer_entry(0x1235, 1));
+
+        assert_eq!(Repr::new(0xC0, &entries, &options).check_no_orphan_options(), Ok(()));
+        assert_eq!(
+            Repr::new(0xC0, &entries[..16], &options).check_no_orphan_options(),
+            Err(Error::OrphanOption(1))
+        );
+        assert_eq!(Repr::new(0xC0, &[], &options).check_no_orphan_options(), Err(Error::OrphanOption(0)));
+        assert_eq!(Repr::new(0xC0, &entries, &options[..12]).check_no_orphan_options(), Err(Error::LengthOverflow));
+    }
+
+    #[test]
+    fn test_repr_check_lengths_exact() {
+        let entries = offer_entry(0x1234, 0);
+        let option = endpoint_option([10, 0, 0, 1], 30509);
+        assert_eq!(Repr::new(0xC0, &entries, &option).check_lengths_exact(), Ok(()));
+
+        let mut padded = [0u8; 14];
+        padded[..12].copy_from_slice(&option);
+        assert_eq!(Repr::new(0xC0, &entries, &padded).check_lengths_exact(), Err(Error::LengthOverflow));
+        assert_eq!(Repr::new(0xC0, &entries, &option[..11]).check_lengths_exact(), Err(Error::LengthOverflow));
+        assert_eq!(Repr::new(0xC0, &entries[..15], &option).check_lengths_exact(), Err(Error::LengthOverflow));
+    }
+
+    #[test]
+    fn test_repr_classify() {
+        use crate::entries::{EventGroupKey, ServiceEntryRepr};
+
+        let key = ServiceKey::new(0x1234, 1, 1);
+        let mut offer = [0u8; 16];
+        EntryRepr::Service(ServiceEntryRepr::offer(key, 0, 3)).emit(&mut offer);
+        let mut stop = [0u8; 16];
+        EntryRepr::Service(ServiceEntryRepr::offer(key, 0, 0)).emit(&mut stop);
+        let mut find = [0u8; 16];
+        EntryRepr::Service(ServiceEntryRepr::find(key, 0, 3)).emit(&mut find);
+        let subscribe = EventGroupEntryRepr::subscribe(EventGroupKey::new(key, 0x10), 0, 3);
+        let mut entries = [0u8; 32];
+        EntryRepr::EventGroup(subscribe).emit(&mut entries[..16]);
+        EntryRepr::EventGroup(EventGroupEntryRepr { entry_type: EntryType::SubscribeAck, ..subscribe })
+            .emit(&mut entries[16..]);
+
+        assert_eq!(Repr::new(0xC0, &[], &[]).classify(), Ok(MessageClass::KeepAlive));
+        assert_eq!(Repr::new(0xC0, &offer, &[]).classify(), Ok(MessageClass::InitialOffer));
+        assert_eq!(Repr::new(0x40, &offer, &[]).classify(), Ok(MessageClass::CyclicOffer));
+        assert_eq!(Repr::new(0x40, &stop, &[]).classify(), Ok(MessageClass::StopOffer));
+        assert_eq!(Repr::new(0x40, &find, &[]).classify(), Ok(MessageClass::FindQuery));
+        assert_eq!(Repr::new(0x40, &entries[..16], &[]).classify(), Ok(MessageClass::Subscription));
+        assert_eq!(Repr::new(0x40, &entries[16..], &[]).classify(), Ok(MessageClass::Acknowledgment));
+        assert_eq!(Repr::new(0x40, &entries, &[]).classify(), Ok(MessageClass::Mixed));
+
+        entries[16] = 0x42;
+        assert_eq!(Repr::new(0x40, &entries, &[]).classify(), Err(Error::InvalidEntryType(0x42)));
+    }
+
+    #[test]
+    fn test_repr_collect_options_array() {
+        let mut options = [0u8; 24];
+        options[..12].copy_from_slice(&endpoint_option([10, 0, 0, 1], 30509));
+        options[12..].copy_from_slice(&endpoint_option([10, 0, 0, 2], 30509));
+        let repr = Repr::new(0xC0, &[], &options);
+
+        let (decoded, count) = repr.collect_options_array::<4>().unwrap();
+        assert_eq!(count, 2);
+        assert!(matches!(decoded[1], Some(SdOption::IPv4Endpoint(_))));
+        assert!(decoded[2].is_none());
+        assert_eq!(repr.collect_options_array::<2>().unwrap().1, 2);
+        assert_eq!(repr.collect_options_array::<1>().map(|(_, n)| n), Err(Error::BufferTooShort));
+        let truncated = Repr::new(0xC0, &[], &options[..20]);
+        assert_eq!(truncated.collect_options_array::<4>().map(|(_, n)| n), Err(Error::LengthOverflow));
+    }
+
+    #[test]
+    fn test_repr_partition_options() {
+        let mut options = [0u8; 12 + 6 + 12];
+        options[..12].copy_from_slice(&endpoint_option([10, 0, 0, 1], 30509));
+        options[12..18].copy_from_slice(&[0x00, 0x03, 0x42, 0x80, 0xAB, 0xCD]);
+        options[18..].copy_from_slice(&endpoint_option([10, 0, 0, 2], 30509));
+
+        let (known, unknown) = Repr::new(0xC0, &[], &options).partition_options().unwrap();
+        assert_eq!(known.len(), 2);
+        assert!(known.iter().all(|o| matches!(o, SdOption::IPv4Endpoint(_))));
+        assert_eq!(unknown.as_slice(), &[(0x42, &options[12..18])]);
+
+        let (known, unknown) = Repr::new(0xC0, &[], &[]).partition_options().unwrap();
+        assert!(known.is_empty());
+        assert!(unknown.as_slice().is_empty());
+        assert!(Repr::new(0xC0, &[], &options[..16]).partition_options().is_err());
+    }
+
+    #[test]
+    fn test_repr_content_hash() {
+        let a = endpoint_option([10, 0, 0, 1], 30509);
+        let b = endpoint_option([10, 0, 0, 2], 30509);
+        let mut options = [0u8; 24];
+        options[..12].copy_from_slice(&a);
+        options[12..].copy_from_slice(&b);
+        let mut swapped = [0u8; 24];
+        swapped[..12].copy_from_slice(&b);
+        swapped[12..].copy_from_slice(&a);
+
+        let mut entries = [0u8; 32];
+        entries[..16].copy_from_slice(&offer_entry(0x1234, 0));
+        entries[16..].copy_from_slice(&offer_entry(0x1235, 1));
+        let mut reindexed = [0u8; 32];
+        reindexed[..16].copy_from_slice(&offer_entry(0x1234, 1));
+        reindexed[16..].copy_from_slice(&offer_entry(0x1235, 0));
+
+        let hash = Repr::new(0xC0, &entries, &options).content_hash();
+        assert_eq!(Repr::new(0xC0, &reindexed, &swapped).content_hash(), hash);
+        assert_eq!(Repr::new(0xC0, &entries, &options).content_hash(), hash);
+
+        // TTL is ignored, other entry fields are not
+        let mut refreshed = entries;
+        refreshed[11] = 0x20;
+        assert_eq!(Repr::new(0xC0, &refreshed, &options).content_hash(), hash);
+        let mut bumped = entries;
+        bumped[15] = 0x01;
+        assert_ne!(Repr::new(0xC0, &bumped, &options).content_hash(), hash);
+
+        // Options must stay attached to the same entries
+        assert_ne!(Repr::new(0xC0, &entries, &swapped).content_hash(), hash);
+        assert_ne!(Repr::new(0x40, &entries, &options).content_hash(), hash);
+        assert_ne!(
+            Repr::new(0xC0, &entries, &options[..20]).content_hash(),
+            Repr::new(0xC0, &entries, &options[..21]).content_hash()
+        );
+    }
+
+    #[test]
+    fn test_repr_decode_report() {
+        let mut entries = [0u8; 40];
+        entries[..16].copy_from_slice(&offer_entry(0x1234, 0));
+        entries[16] = 0x42;
+        let mut options = [0u8; 12 + 6 + 12];
+        options[..12].copy_from_slice(&endpoint_option([10, 0, 0, 1], 30509));
+        options[12..18].copy_from_slice(&[0x00, 0x03, 0x42, 0x00, 0xAB, 0xCD]);
+        options[18..].copy_from_slice(&endpoint_option([10, 0, 0, 2], 30509));
+        options[18 + 1] = 0x20;
+
+        let mut report = [None; 8];
+        let mut count = 0;
+        Repr::new(0xC0, &entries, &options).decode_report(&mut |item: ItemResult| {
+            report[count] = Some((item.offset, item.item.map(|i| matches!(i, DecodedItem::Entry(_)))));
+            count += 1;
+        });
+
+        let options_start = 8 + 40 + 4;
+        assert_eq!(
+            report[..count],
+            [
+                Some((8, Ok(true))),
+                Some((24, Err(Error::InvalidEntryType(0x42)))),
+                Some((40, Err(Error::BufferTooShort))),
+                Some((options_start, Ok(false))),
+                Some((options_start + 12, Err(Error::InvalidOptionType(0x42)))),
+                Some((options_start + 18, Err(Error::LengthOverflow))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repr_with_decremented_ttls() {
+        let mut infinite = offer_entry(0x2000, 0);
+        infinite[9..12].copy_from_slice(&[0xFF, 0xFF, 0xFF]);
+        let option = endpoint_option([10, 0, 0, 1], 30509);
+        let mut buffer = [0u8; 64];
+        let len = message(&[offer_entry(0x1000, 0), infinite], &[option], &mut buffer);
+        let repr = Repr::parse_bytes(&buffer[..len]).unwrap();
+
+        let mut out = [0u8; 64];
+        assert_eq!(repr.with_decremented_ttls(2, &mut out), Ok(len));
+        let decremented = Repr::parse_bytes(&out[..len]).unwrap();
+        assert_eq!(decremented.options, repr.options);
+        let ttls = |repr: Repr| -> [u32; 2] {
+            let mut ttls = [0; 2];
+            for (ttl, chunk) in ttls.iter_mut().zip(repr.entries.chunks(16)) {
+                *ttl = match EntryRepr::parse(chunk).unwrap() {
+                    EntryRepr::Service(service) => service.ttl,
+                    EntryRepr::EventGroup(eventgroup) => eventgroup.ttl,
+                };
+            }
+            ttls
+        };
+        assert_eq!(ttls(decremented), [1, TTL_INFINITE]);
+
+        // Flooring turns the offer into a StopOffer
+        assert_eq!(repr.with_decremented_ttls(10, &mut out), Ok(len));
+        assert_eq!(ttls(Repr::parse_bytes(&out[..len]).unwrap()), [0, TTL_INFINITE]);
+
+        assert_eq!(repr.with_decremented_ttls(1, &mut out[..len - 1]), Err(Error::BufferTooShort));
+    }
+
+    #[test]
+    fn test_repr_has_nondiscardable_unknown_options() {
+        let mut options = [0u8; 24];
+        options[..12].copy_from_slice(&endpoint_option([10, 0, 0, 1], 30509));
+        // Unknown type 0x42 carrying one data byte
+        options[12..17].copy_from_slice(&[0x00, 0x02, 0x42, 0x80, 0xAA]);
+        let repr = Repr::new(0xC0, &[], &options[..17]);
+        assert_eq!(repr.has_nondiscardable_unknown_options(), Ok(false));
+
+        options[15] = 0x00;
+        let repr = Repr::new(0xC0, &[], &options[..17]);
+        assert_eq!(repr.has_nondiscardable_unknown_options(), Ok(true));
+
+        // A known option with the discardable bit clear is fine
+        let repr = Repr::new(0xC0, &[], &options[..12]);
+        assert_eq!(repr.has_nondiscardable_unknown_options(), Ok(false));
+
+        let repr = Repr::new(0xC0, &[], &options[..16]);
+        assert!(repr.has_nondiscardable_unknown_options().is_err());
+    }
+
+    #[test]
+    fn test_repr_parse_within() {
+        let mut entries = [0u8; 32];
+        entries[..16].copy_from_slice(&offer_entry(0x1000, 0));
+        entries[16..].copy_from_slice(&offer_entry(0x1001, 0));
+        let mut options = [0u8; 64];
+        options[..12].copy_from_slice(&endpoint_option([10, 0, 0, 1], 30509));
+        let config_len =
+            ConfigurationOption::build_option(&[("a", None), ("b", Some("1")), ("c", None)], &mut options[12..])
+                .unwrap();
+        let repr = Repr::new(0xC0, &entries, &options[..12 + config_len]);
+        let mut buffer = [0u8; 128];
+        let len = repr.buffer_len();
+        repr.emit(&mut Packet::new_unchecked(&mut buffer[..len]));
+        let packet = Packet::new_checked(&buffer[..len]).unwrap();
+
+        assert_eq!(Repr::parse_within(&packet, &Budget::default()), Ok(repr));
+        let exact = Budget { max_entries: 2, max_options: 2, max_config_entries: 3 };
+        assert_eq!(Repr::parse_within(&packet, &exact), Ok(repr));
+        for budget in [
+            Budget { max_entries: 1, ..exact },
+            Budget { max_options: 1, ..exact },
+            Budget { max_config_entries: 2, ..exact },
+        ] {
+            assert_eq!(Repr::parse_within(&packet, &budget), Err(Error::BudgetExceeded));
+        }
+    }
+
+    #[test]
+    fn test_repr_parse_validated() {
+        let strict = ValidationPolicy::strict();
+        let relaxed = ValidationPolicy::relaxed();
+        let mut buf = [0u8; 128];
+        let len = message(&[offer_entry(0x1234, 0)], &[endpoint_option([10, 0, 0, 1], 30509)], &mut buf);
+        let packet = Packet::new_unchecked(&buf[..len]);
+        let repr = Repr::parse_validated(&packet, &strict).unwrap();
+        assert_eq!(repr, Repr::parse(&packet).unwrap());
+
+        // Declared entries length past the end of the buffer
+        let mut truncated = buf;
+        truncated[7] = 0x40;
+        let packet = Packet::new_unchecked(&truncated[..len]);
+        assert!(Repr::parse_validated(&packet, &relaxed).is_err());
+
+        // Option not referenced by any entry
+        let len = message(
+            &[offer_entry(0x1234, 0)],
+            &[endpoint_option([10, 0, 0, 1], 30509), endpoint_option([10, 0, 0, 2], 30509)],
+            &mut buf,
+        );
+        let packet = Packet::new_unchecked(&buf[..len]);
+        assert_eq!(Repr::parse_validated(&packet, &strict), Err(Error::OrphanOption(1)));
+        assert!(Repr::parse_validated(&packet, &relaxed).is_ok());
+
+        // Bad transport protocol in the first option
+        buf[8 + 16 + 4 + 9] = 0x42;
+        let packet = Packet::new_unchecked(&buf[..len]);
+        assert_eq!(Repr::parse_validated(&packet, &strict), Err(Error::InvalidProtocol(0x42)));
+    }
+
+    #[test]
+    fn test_repr_options_for_entry() {
+        let mut entries = [0u8; 48];
+        entries[..16].copy_from_slice(&offer_entry(0x1000, 0));
+        entries[16..32].copy_from_slice(&offer_entry(0x1001, 1));
+        entries[32..].copy_from_slice(&offer_entry(0x1002, 0));
+        // Third entry: first run [0], second run [1]; second entry: no options
+        entries[32 + 2] = 1;
+        entries[32 + 3] = 0x11;
+        entries[16 + 3] = 0x00;
+        let mut options = [0u8; 24];
+        options[..12].copy_from_slice(&endpoint_option([10, 0, 0, 1], 30509));
+        options[12..].copy_from_slice(&endpoint_option([10, 0, 0, 2], 30510));
+        let repr = Repr::new(0xC0, &entries, &options);
+
+        let port = |option: core::result::Result<SdOption, Error>| match option {
+            Ok(SdOption::IPv4Endpoint(o)) => Ok(o.port()),
+            Ok(_) => Ok(0),
+            Err(e) => Err(e),
+        };
+        assert_eq!(repr.options_for_entry(0).map(port).collect::<std::vec::Vec<_>>(), [Ok(30509)]);
+        assert_eq!(repr.options_for_entry(1).count(), 0);
+        assert_eq!(repr.options_for_entry(2).map(port).collect::<std::vec::Vec<_>>(), [Ok(30509), Ok(30510)]);
+        assert_eq!(
+            repr.options_for_entry(3).map(port).collect::<std::vec::Vec<_>>(),
+            [Err(Error::BufferTooShort)]
+        );
+
+        let repr = Repr::new(0xC0, &entries, &options[..12]);
+        assert_eq!(
+            repr.options_for_entry(2).map(port).collect::<std::vec::Vec<_>>(),
+            [Ok(30509), Err(Error::LengthOverflow)]
+        );
+    }
+
+    #[test]
+    fn test_repr_dump() {
+        let mut entries = [0u8; 32];
+        entries[..16].copy_from_slice(&offer_entry(0x1234, 0));
+        entries[16..].copy_from_slice(&offer_entry(0x5678, 1));
+        let mut options = [0u8; 12 + 24];
+        options[..12].copy_from_slice(&endpoint_option([192, 168, 0, 1], 30509));
+        crate::options::IPv6SdEndpointOptionRepr::udp([0xFE, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1], 30490)
+            .emit(&mut options[12..]);
+        let repr = Repr::new(0xC0, &entries, &options);
+
+        let mut text = std::string::String::new();
+        repr.dump(&mut text).unwrap();
+        assert_eq!(
+            text,
+            "SOME/IP-SD flags=0xC0 [reboot unicast] entries=2 options=2\n\
+             \x20 [0] OfferService service=0x1234 instance=0x0001 version=1.0 ttl=3s\n\
+             \x20     option[0] IPv4Endpoint 192.168.0.1:30509 UDP\n\
+             \x20 [1] OfferService service=0x5678 instance=0x0001 version=1.0 ttl=3s\n\
+             \x20     option[1] IPv6SdEndpoint [fe80:0:0:0:0:0:0:1]:30490 UDP\n"
+        );
+
+        struct Full;
+        impl fmt::Write for Full {
+            fn write_str(&mut self, _: &str) -> fmt::Result {
+                Err(fmt::Error)
+            }
+        }
+        assert_eq!(repr.dump(&mut Full), Err(Error::BufferTooShort));
+        let repr = Repr::new(0xC0, &entries, &options[..12]);
+        assert_eq!(repr.dump(&mut text), Err(Error::LengthOverflow));
+    }
+
+    #[test]
+    fn test_repr_flags_typed() {
+        let mut repr = Repr::new(0x01, &[], &[]);
+        let mut flags = repr.flags_typed();
+        assert!(!flags.reboot());
+        flags.set_reboot(true);
+        flags.set_unicast(true);
+        repr.set_flags_typed(flags);
+        assert_eq!(repr.flags, 0xC1);
+        assert_eq!(repr.flags_typed().reserved(), 0x01);
+    }
+
+    #[test]
+    fn test_repr_is_empty() {
+        let buffer = [0xC0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert!(Repr::parse_bytes(&buffer).unwrap().is_empty());
+
+        let entries = offer_entry(0x1234, 0);
+        assert!(!Repr::new(0xC0, &entries, &[]).is_empty());
+        let option = endpoint_option([10, 0, 0, 1], 30509);
+        assert!(!Repr::new(0xC0, &[], &option).is_empty());
+    }
+
+    #[test]
+    fn test_messages() {
+        let mut buf = [0u8; 128];
+        let first = message(&[], &[], &mut buf);
+        let second = message(&[offer_entry(0x1234, 0)], &[endpoint_option([10, 0, 0, 1], 30509)], &mut buf[first..]);
+        let total = first + second;
+
+        assert_eq!(Repr::parse_with_len(&buf[..total]).unwrap().1, first);
+        let mut iter = messages(&buf[..total]);
+        assert!(iter.next().unwrap().unwrap().entries.is_empty());
+        assert_eq!(iter.next().unwrap().unwrap().entries.len(), 16);
+        assert!(iter.next().is_none());
+
+        let mut iter = messages(&buf[..total - 1]);
+        assert!(iter.next().unwrap().is_ok());
+        assert_eq!(iter.next().unwrap(), Err(Error::BufferTooShort));
+        assert!(iter.next().is_none());
+        assert_eq!(messages(&[]).count(), 0);
+    }
+
+    #[test]
+    fn test_entries_capacity() {
+        assert_eq!(entries_capacity(1400, 0), (1400 - 12) / 16);
+        assert_eq!(entries_capacity(12 + 2 * 16 + 12, 12), 2);
+        assert_eq!(entries_capacity(12 + 2 * 16 + 11, 12), 1);
+        assert_eq!(entries_capacity(20, 12), 0);
+    }
+
+    #[test]
+    fn test_split_for_mtu() {
+        use crate::entries::{EntryRepr, EntryType, NumberOfOptions, ServiceEntryRepr};
+        use crate::options::{IPv4EndpointOptionRepr, LoadBalancingOptionRepr, OptionRepr, TransportProtocol};
+
+        let offer = |service_id, first, second, number_of_options| {
+            EntryRepr::Service(ServiceEntryRepr {
+                entry_type: EntryType::OfferService,
+                index_first_option_run: first,
+                index_second_option_run: second,
+                number_of_options,
+                service_id,
+                instance_id: 1,
+                major_version: 1,
+                ttl: 3,
+                minor_version: 0,
+            })
+        };
+        let one = NumberOfOptions::from_options(1, 0);
+        let entries = [
+            offer(0x1000, 0, 0, one),
+            offer(0x1001, 0, 0, one),
+            offer(0x1002, 0, 0, one),
+            offer(0x1003, 0, 0, one),
+            // Load balancing in the first run, shared endpoint in the second
+            offer(0x1004, 1, 0, NumberOfOptions::from_options(1, 1)),
+        ];
+        let options = [
+            OptionRepr::IPv4Endpoint(IPv4EndpointOptionRepr {
+                ipv4_address: [192, 168, 0, 1],
+                protocol: TransportProtocol::UDP,
+                port: 30509,
+            }),
+            OptionRepr::LoadBalancing(LoadBalancingOptionRepr { priority: 1, weight: 2 }),
+        ];
+
+        // Header + two entries + one shared endpoint option
+        let mtu = 12 + 2 * 16 + 12;
+        let mut splitter = split_for_mtu(0xC0, &entries, &options, mtu);
+        let mut out = [0u8; 128];
+
+        for first_service in [0x1000u16, 0x1002] {
+            let len = splitter.next_message(&mut out).unwrap().unwrap();
+            assert_eq!(len, mtu);
+            let repr = Repr::parse_bytes(&out[..len]).unwrap();
+            assert_eq!(repr.flags, 0xC0);
+            assert_eq!(repr.entries.len(), 32);
+            assert_eq!(repr.options.len(), 12);
+            assert_eq!(&repr.entries[4..6], &first_service.to_be_bytes());
+            assert_eq!(repr.check_option_sharing(), Ok(()));
+            assert_eq!(repr.options[2], 0x04);
+        }
+
+        let len = splitter.next_message(&mut out).unwrap().unwrap();
+        assert_eq!(len, 12 + 16 + 8 + 12);
+        let repr = Repr::parse_bytes(&out[..len]).unwrap();
+        assert_eq!(repr.check_option_sharing(), Ok(()));
+        // Runs are re-indexed into the message's own options array
+        assert_eq!(&repr.entries[1..4], &[0, 1, 0x11]);
+        assert_eq!(repr.options[2], 0x02);
+        assert_eq!(repr.options[10], 0x04);
+
+        assert!(splitter.next_message(&mut out).is_none());
+
+        let mut splitter = split_for_mtu(0x00, &entries, &options, 39);
+        assert_eq!(splitter.next_message(&mut out), Some(Err(Error::BufferTooShort)));
+        assert!(splitter.next_message(&mut out).is_none());
+
+        let mut splitter = split_for_mtu(0x00, &entries[..1], &options[1..], mtu);
+        assert_eq!(splitter.next_message(&mut out), Some(Ok(12 + 16 + 8)));
+        let mut splitter = split_for_mtu(0x00, &entries[4..], &options[..1], mtu);
+        assert_eq!(splitter.next_message(&mut out), Some(Err(Error::LengthOverflow)));
+    }
 }